@@ -0,0 +1,158 @@
+//! Rendering ABI-encoded call data as display strings.
+
+use alloy_primitives::{Address, U256};
+
+use crate::{address_to_checksum_hex_string, bytes_to_hex_string, to_string};
+
+/// Size of an ABI word in bytes
+const WORD: usize = 32;
+
+/// Size of a function selector in bytes
+const SELECTOR: usize = 4;
+
+/// ABI parameter types understood by `decode_static_args`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbiType {
+    /// `uint256` (and narrower uints), rendered in decimal
+    Uint,
+    /// `address`, rendered EIP-55 checksummed
+    Address,
+    /// `bool`, rendered as "true" or "false"
+    Bool,
+    /// `bytes32`, rendered as full "0x" hex
+    Bytes32,
+    /// Dynamic `bytes`, not yet supported
+    Bytes,
+    /// Dynamic `string`, not yet supported
+    String,
+}
+
+/// Error types for ABI decoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbiError {
+    /// Payload is not a selector followed by exactly one word per type
+    InvalidLength { expected: usize, found: usize },
+    /// Type at this argument index is dynamic and cannot be decoded
+    Unsupported(usize),
+    /// Bool word at this argument index is neither 0 nor 1
+    InvalidBool(usize),
+    /// Address word at this argument index has non-zero upper 12 bytes
+    InvalidAddress(usize),
+}
+
+/// Decodes the static arguments following a 4-byte selector and renders
+/// each with its type's formatter.
+///
+/// Decoding is strict, like Solidity's ABI decoder: the payload length must
+/// match exactly and padding bits of `bool` and `address` words must be
+/// zero, so a dirty word is an error rather than silently truncated.
+pub fn decode_static_args(data: &[u8], types: &[AbiType]) -> Result<Vec<String>, AbiError> {
+    let expected = SELECTOR + WORD * types.len();
+    if data.len() != expected {
+        return Err(AbiError::InvalidLength { expected, found: data.len() });
+    }
+
+    types
+        .iter()
+        .enumerate()
+        .map(|(index, ty)| {
+            let start = SELECTOR + WORD * index;
+            decode_word(&data[start..start + WORD], *ty, index)
+        })
+        .collect()
+}
+
+fn decode_word(word: &[u8], ty: AbiType, index: usize) -> Result<String, AbiError> {
+    match ty {
+        AbiType::Uint => Ok(to_string(U256::from_be_slice(word))),
+        AbiType::Address => {
+            if word[..12].iter().any(|&b| b != 0) {
+                return Err(AbiError::InvalidAddress(index));
+            }
+            Ok(address_to_checksum_hex_string(Address::from_slice(&word[12..])))
+        }
+        AbiType::Bool => match U256::from_be_slice(word) {
+            v if v.is_zero() => Ok("false".to_string()),
+            v if v == U256::from(1) => Ok("true".to_string()),
+            _ => Err(AbiError::InvalidBool(index)),
+        },
+        AbiType::Bytes32 => Ok(bytes_to_hex_string(word)),
+        AbiType::Bytes | AbiType::String => Err(AbiError::Unsupported(index)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(value: u64) -> [u8; 32] {
+        U256::from(value).to_be_bytes::<32>()
+    }
+
+    fn transfer_call(to: [u8; 20], amount: u64) -> Vec<u8> {
+        let mut data = vec![0xa9, 0x05, 0x9c, 0xbb];
+        data.extend_from_slice(&[0u8; 12]);
+        data.extend_from_slice(&to);
+        data.extend_from_slice(&word(amount));
+        data
+    }
+
+    #[test]
+    fn test_decode_transfer_call() {
+        let to = [0x52, 0x90, 0x8e, 0x08, 0x4f, 0x3d, 0x7d, 0xe1, 0xb3, 0x9a,
+                  0x96, 0x30, 0x02, 0x64, 0xbd, 0x2a, 0x47, 0x9e, 0x9c, 0x8f];
+        let data = transfer_call(to, 1_000_000);
+        let args = decode_static_args(&data, &[AbiType::Address, AbiType::Uint]).unwrap();
+        assert_eq!(args[0], address_to_checksum_hex_string(Address::from_slice(&to)));
+        assert_eq!(args[1], "1000000");
+    }
+
+    #[test]
+    fn test_decode_bool_and_bytes32() {
+        let mut data = vec![0u8; 4];
+        data.extend_from_slice(&word(1));
+        data.extend_from_slice(&word(0));
+        data.extend_from_slice(&[0xab; 32]);
+        let args = decode_static_args(&data, &[AbiType::Bool, AbiType::Bool, AbiType::Bytes32]).unwrap();
+        assert_eq!(args, vec!["true".to_string(), "false".to_string(), format!("0x{}", "ab".repeat(32))]);
+    }
+
+    #[test]
+    fn test_wrong_length_payload() {
+        let data = transfer_call([0; 20], 1);
+        assert_eq!(
+            decode_static_args(&data[..67], &[AbiType::Address, AbiType::Uint]),
+            Err(AbiError::InvalidLength { expected: 68, found: 67 })
+        );
+        assert_eq!(
+            decode_static_args(&data, &[AbiType::Address]),
+            Err(AbiError::InvalidLength { expected: 36, found: 68 })
+        );
+        assert_eq!(decode_static_args(&[0xa9], &[]), Err(AbiError::InvalidLength { expected: 4, found: 1 }));
+        assert_eq!(decode_static_args(&data[..4], &[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_dirty_words_are_rejected() {
+        let mut data = vec![0u8; 4];
+        let mut dirty = word(1);
+        dirty[0] = 0x80;
+        data.extend_from_slice(&dirty);
+        assert_eq!(decode_static_args(&data, &[AbiType::Bool]), Err(AbiError::InvalidBool(0)));
+        assert_eq!(decode_static_args(&data, &[AbiType::Address]), Err(AbiError::InvalidAddress(0)));
+        assert_eq!(decode_static_args(&data, &[AbiType::Uint]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_dynamic_types_unsupported() {
+        let data = vec![0u8; 4 + 64];
+        assert_eq!(
+            decode_static_args(&data, &[AbiType::Uint, AbiType::String]),
+            Err(AbiError::Unsupported(1))
+        );
+        assert_eq!(
+            decode_static_args(&data, &[AbiType::Bytes, AbiType::Uint]),
+            Err(AbiError::Unsupported(0))
+        );
+    }
+}
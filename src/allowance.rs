@@ -0,0 +1,95 @@
+//! Display of ERC-20 approval amounts, recognizing "infinite" allowances.
+//!
+//! Wallets approve `type(uint256).max` to mean "no limit", and some
+//! protocols treat anything at or above 2^255 the same way so that the
+//! allowance never visibly decreases. Such values render as "Unlimited"
+//! instead of a 78-digit number.
+
+use alloy_primitives::U256;
+
+use crate::format_units;
+
+/// Rendering of an allowance treated as infinite
+const UNLIMITED: &str = "Unlimited";
+
+/// Multiple of total supply above which an allowance can never be spent
+const SUPPLY_MULTIPLE: u64 = 10;
+
+/// The 2^255 threshold used by protocols that never decrement large allowances
+pub const HALF_MAX_ALLOWANCE: U256 = U256::from_limbs([0, 0, 0, 1 << 63]);
+
+/// Formats an allowance as "<amount> <symbol>", or "Unlimited" for
+/// `U256::MAX`.
+pub fn format_allowance(value: U256, decimals: u8, symbol: &str) -> String {
+    format_allowance_with_threshold(value, decimals, symbol, U256::MAX)
+}
+
+/// Like `format_allowance`, but renders every value at or above `threshold`
+/// as "Unlimited", e.g. `HALF_MAX_ALLOWANCE`.
+pub fn format_allowance_with_threshold(value: U256, decimals: u8, symbol: &str, threshold: U256) -> String {
+    if value >= threshold {
+        return UNLIMITED.to_string();
+    }
+    format!("{} {}", format_units(value, decimals), symbol)
+}
+
+/// Returns whether an allowance is unlimited in practice.
+///
+/// An allowance more than 10× the token's total supply can never be
+/// exhausted, even allowing for supply growth, so it is shown as unlimited.
+/// `U256::MAX` is always unlimited; with a zero supply every nonzero
+/// allowance is. If 10× the supply does not fit in a `U256`, only
+/// `U256::MAX` qualifies.
+pub fn is_effectively_unlimited(value: U256, total_supply: U256) -> bool {
+    if value == U256::MAX {
+        return true;
+    }
+    match total_supply.checked_mul(U256::from(SUPPLY_MULTIPLE)) {
+        Some(limit) => value > limit,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_allowance_max() {
+        assert_eq!(format_allowance(U256::MAX, 18, "DAI"), "Unlimited");
+        assert_eq!(
+            format_allowance(U256::MAX - U256::from(1), 0, "DAI"),
+            format!("{} DAI", crate::to_string(U256::MAX - U256::from(1)))
+        );
+    }
+
+    #[test]
+    fn test_format_allowance_half_max_convention() {
+        assert_eq!(HALF_MAX_ALLOWANCE, U256::from(1) << 255);
+        let below = HALF_MAX_ALLOWANCE - U256::from(1);
+        assert_eq!(format_allowance_with_threshold(HALF_MAX_ALLOWANCE, 18, "USDC", HALF_MAX_ALLOWANCE), "Unlimited");
+        assert_eq!(format_allowance_with_threshold(U256::MAX, 18, "USDC", HALF_MAX_ALLOWANCE), "Unlimited");
+        assert_ne!(format_allowance_with_threshold(below, 18, "USDC", HALF_MAX_ALLOWANCE), "Unlimited");
+        assert_ne!(format_allowance(HALF_MAX_ALLOWANCE, 18, "USDC"), "Unlimited");
+    }
+
+    #[test]
+    fn test_format_allowance_normal() {
+        assert_eq!(format_allowance(U256::from(1_500_000u64), 6, "USDC"), "1.5 USDC");
+        assert_eq!(format_allowance(U256::ZERO, 18, "WETH"), "0 WETH");
+    }
+
+    #[test]
+    fn test_is_effectively_unlimited() {
+        let supply = U256::from(1_000_000u64);
+        assert!(!is_effectively_unlimited(U256::from(10_000_000u64), supply));
+        assert!(is_effectively_unlimited(U256::from(10_000_001u64), supply));
+        assert!(!is_effectively_unlimited(supply, supply));
+        assert!(is_effectively_unlimited(U256::MAX, supply));
+        assert!(is_effectively_unlimited(U256::from(1), U256::ZERO));
+        assert!(!is_effectively_unlimited(U256::ZERO, U256::ZERO));
+        // 10× supply overflows: only MAX counts
+        assert!(!is_effectively_unlimited(U256::MAX - U256::from(1), U256::MAX / U256::from(2)));
+        assert!(is_effectively_unlimited(U256::MAX, U256::MAX));
+    }
+}
@@ -0,0 +1,126 @@
+//! Line wrapping and PEM-style armor for long payloads in logs.
+
+use crate::encoding::{base64_decode, base64_encode, Base64Error};
+
+/// Column width of armored base64 lines, as in PEM
+const ARMOR_WIDTH: usize = 64;
+
+/// Error types for wrapping and armor parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArmorError {
+    /// Line width of zero was requested
+    ZeroWidth,
+    /// No "-----BEGIN label-----" line was found
+    MissingBegin,
+    /// No "-----END label-----" line matching the BEGIN label was found
+    MissingEnd,
+    /// Armored body is not valid base64
+    InvalidBase64(Base64Error),
+}
+
+/// Inserts `\n` after every `width` characters, with no trailing newline.
+///
+/// Counts characters rather than bytes, so multibyte text is never split
+/// inside a character.
+pub fn wrap_fixed(s: &str, width: usize) -> Result<String, ArmorError> {
+    if width == 0 {
+        return Err(ArmorError::ZeroWidth);
+    }
+    let mut result = String::with_capacity(s.len() + s.len() / width);
+    for (i, c) in s.chars().enumerate() {
+        if i > 0 && i % width == 0 {
+            result.push('\n');
+        }
+        result.push(c);
+    }
+    Ok(result)
+}
+
+/// Armors `data` as PEM-style text: a `-----BEGIN label-----` line, the
+/// base64 encoding wrapped at 64 columns, and `-----END label-----`.
+pub fn armor(label: &str, data: &[u8]) -> String {
+    let body = wrap_fixed(&base64_encode(data), ARMOR_WIDTH).expect("armor width is nonzero");
+    let mut result = format!("-----BEGIN {}-----\n", label);
+    if !body.is_empty() {
+        result.push_str(&body);
+        result.push('\n');
+    }
+    result.push_str(&format!("-----END {}-----\n", label));
+    result
+}
+
+/// Parses the first armored block in `s`, returning its label and data.
+///
+/// Text before the BEGIN line and after the END line is ignored, as is
+/// whitespace around each line, so a block pasted into an email or log
+/// can be extracted as-is.
+pub fn unarmor(s: &str) -> Result<(String, Vec<u8>), ArmorError> {
+    let mut lines = s.lines().map(str::trim);
+    let label = lines
+        .by_ref()
+        .find_map(|line| line.strip_prefix("-----BEGIN ")?.strip_suffix("-----"))
+        .ok_or(ArmorError::MissingBegin)?;
+
+    let end = format!("-----END {}-----", label);
+    let mut body = String::new();
+    for line in lines {
+        if line == end {
+            let data = base64_decode(&body).map_err(ArmorError::InvalidBase64)?;
+            return Ok((label.to_string(), data));
+        }
+        body.push_str(line);
+    }
+    Err(ArmorError::MissingEnd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_fixed() {
+        assert_eq!(wrap_fixed("abcdef", 2).unwrap(), "ab\ncd\nef");
+        assert_eq!(wrap_fixed("abcdefg", 3).unwrap(), "abc\ndef\ng");
+        assert_eq!(wrap_fixed("abc", 3).unwrap(), "abc");
+        assert_eq!(wrap_fixed("", 4).unwrap(), "");
+        assert_eq!(wrap_fixed("héllo", 2).unwrap(), "hé\nll\no");
+        assert_eq!(wrap_fixed("abc", 0), Err(ArmorError::ZeroWidth));
+    }
+
+    #[test]
+    fn test_armor_layout() {
+        let armored = armor("SIGNED PAYLOAD", &[0xab; 48]);
+        let lines: Vec<&str> = armored.lines().collect();
+        assert_eq!(lines[0], "-----BEGIN SIGNED PAYLOAD-----");
+        // 48 bytes encode to exactly one 64-column line
+        assert_eq!(lines[1].len(), 64);
+        assert_eq!(lines[2], "-----END SIGNED PAYLOAD-----");
+        assert_eq!(lines.len(), 3);
+        assert_eq!(armor("EMPTY", &[]), "-----BEGIN EMPTY-----\n-----END EMPTY-----\n");
+    }
+
+    #[test]
+    fn test_armor_round_trip() {
+        let data: Vec<u8> = (0..=255).collect();
+        let armored = armor("DATA", &data);
+        assert_eq!(armored.lines().nth(1).unwrap().len(), 64);
+        assert_eq!(unarmor(&armored).unwrap(), ("DATA".to_string(), data));
+        assert_eq!(unarmor(&armor("EMPTY", &[])).unwrap(), ("EMPTY".to_string(), vec![]));
+    }
+
+    #[test]
+    fn test_unarmor_with_surrounding_text() {
+        let input = format!("Hi, here is the payload:\n\n  {}  \nThanks!\n", armor("MSG", b"hello"));
+        assert_eq!(unarmor(&input).unwrap(), ("MSG".to_string(), b"hello".to_vec()));
+    }
+
+    #[test]
+    fn test_unarmor_errors() {
+        assert_eq!(unarmor("no armor here"), Err(ArmorError::MissingBegin));
+        assert_eq!(unarmor("-----BEGIN A-----\naGVsbG8=\n-----END B-----"), Err(ArmorError::MissingEnd));
+        assert_eq!(
+            unarmor("-----BEGIN A-----\naGVsbG8\n-----END A-----"),
+            Err(ArmorError::InvalidBase64(Base64Error::InvalidLength(7)))
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! Exponential backoff delays for keeper retry state.
+
+use alloy_primitives::{U256, U512};
+
+use crate::format_duration;
+use crate::time::timestamp_label;
+
+/// Fractional bits of the fixed-point factor used when exact powers
+/// overflow
+const FRAC_BITS: usize = 128;
+
+/// Describes the delay before retry `attempt`, e.g.
+/// "attempt 5: retry in 16m (capped)".
+///
+/// The delay is `base_seconds * (factor_num / factor_den)^attempt`, rounded
+/// down and capped at `max_seconds`, so attempt 0 waits `base_seconds`. See
+/// `backoff_delay`.
+pub fn format_backoff(base_seconds: U256, factor_num: u32, factor_den: u32, attempt: u32, max_seconds: U256) -> String {
+    let (delay, capped) = backoff_delay(base_seconds, factor_num, factor_den, attempt, max_seconds);
+    describe_delay(attempt, delay, capped)
+}
+
+/// Describes the delay before retry `attempt` like `format_backoff`, followed
+/// by the time of the retry after `now` as ISO-8601, e.g.
+/// "attempt 2: retry in 4m, at 2024-06-01T00:04:00Z".
+pub fn format_backoff_at(
+    base_seconds: U256,
+    factor_num: u32,
+    factor_den: u32,
+    attempt: u32,
+    max_seconds: U256,
+    now: U256,
+) -> String {
+    let (delay, capped) = backoff_delay(base_seconds, factor_num, factor_den, attempt, max_seconds);
+    let mut result = describe_delay(attempt, delay, capped);
+    result.push_str(", at ");
+    result.push_str(&timestamp_label(now.saturating_add(delay)));
+    result
+}
+
+/// Returns the delay before retry `attempt` and whether `max_seconds` capped
+/// it.
+///
+/// The delay is `base_seconds * (factor_num / factor_den)^attempt` rounded
+/// down, computed exactly while `base_seconds * factor_num^attempt` and
+/// `factor_den^attempt` fit in 512 bits. Past that the factor is raised as a
+/// 128-bit binary fraction. That is exact for whole-number factors such as
+/// 2; otherwise every truncation loses under 2^-128, and the delay may be
+/// low by up to `(2 * attempt + 32) * 2^-128` times the larger of
+/// `base_seconds` and the exact delay, plus one second of final rounding.
+/// For a base of 2^200 and a factor of 1.000001 at attempt 30 that is
+/// about 2^78 seconds.
+///
+/// Growing powers stop as soon as they exceed the cap, so no attempt count
+/// overflows. A zero `factor_den` is an unbounded factor: every attempt after
+/// the first waits `max_seconds`.
+pub fn backoff_delay(
+    base_seconds: U256,
+    factor_num: u32,
+    factor_den: u32,
+    attempt: u32,
+    max_seconds: U256,
+) -> (U256, bool) {
+    let delay = if attempt == 0 {
+        Some(U512::from(base_seconds))
+    } else if factor_den == 0 {
+        None
+    } else {
+        exact_delay(base_seconds, factor_num, factor_den, attempt)
+            .or_else(|| fixed_point_delay(base_seconds, factor_num, factor_den, attempt))
+    };
+    match delay {
+        Some(delay) if delay <= U512::from(max_seconds) => (U256::from(delay), false),
+        _ => (max_seconds, true),
+    }
+}
+
+fn describe_delay(attempt: u32, delay: U256, capped: bool) -> String {
+    let mut result = format!("attempt {}: retry in {}", attempt, format_duration(delay));
+    if capped {
+        result.push_str(" (capped)");
+    }
+    result
+}
+
+/// `floor(base * num^attempt / den^attempt)`, or `None` if an operand
+/// overflows
+fn exact_delay(base: U256, num: u32, den: u32, attempt: u32) -> Option<U512> {
+    let exponent = U512::from(attempt);
+    let numerator = U512::from(num).checked_pow(exponent)?.checked_mul(U512::from(base))?;
+    let denominator = U512::from(den).checked_pow(exponent)?;
+    Some(numerator / denominator)
+}
+
+/// `base * (num / den)^attempt` by squaring a fixed-point factor, or `None`
+/// if it exceeds every 256-bit delay
+fn fixed_point_delay(base: U256, num: u32, den: u32, attempt: u32) -> Option<U512> {
+    if base.is_zero() {
+        return Some(U512::ZERO);
+    }
+    // Any power at or above this bound makes the delay exceed U256::MAX for a
+    // non-zero base, and only growing factors reach it
+    let bound = U512::from(1) << (256 + FRAC_BITS);
+    let mul = |a: U512, b: U512| a.checked_mul(b).map(|product| product >> FRAC_BITS).filter(|&p| p < bound);
+
+    let mut power = (U512::from(num) << FRAC_BITS) / U512::from(den);
+    let mut factor = U512::from(1) << FRAC_BITS;
+    let mut exponent = attempt;
+    loop {
+        if exponent & 1 == 1 {
+            factor = mul(factor, power)?;
+        }
+        exponent >>= 1;
+        if exponent == 0 {
+            break;
+        }
+        power = mul(power, power)?;
+    }
+    U512::from(base).checked_mul(factor).map(|delay| delay >> FRAC_BITS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(value: u64) -> U256 {
+        U256::from(value)
+    }
+
+    const HOUR: u64 = 3_600;
+
+    #[test]
+    fn test_doubling() {
+        assert_eq!(format_backoff(secs(60), 2, 1, 1, secs(HOUR)), "attempt 1: retry in 2m");
+        assert_eq!(format_backoff(secs(60), 2, 1, 4, secs(HOUR)), "attempt 4: retry in 16m");
+        for attempt in 0..6 {
+            assert_eq!(backoff_delay(secs(60), 2, 1, attempt, secs(HOUR)), (secs(60 << attempt), false));
+        }
+    }
+
+    #[test]
+    fn test_fractional_factor() {
+        assert_eq!(backoff_delay(secs(100), 3, 2, 1, secs(HOUR)), (secs(150), false));
+        assert_eq!(backoff_delay(secs(100), 3, 2, 2, secs(HOUR)), (secs(225), false));
+        // 337.5 rounds down
+        assert_eq!(backoff_delay(secs(100), 3, 2, 3, secs(HOUR)), (secs(337), false));
+        assert_eq!(backoff_delay(secs(9), 5, 3, 2, secs(HOUR)), (secs(25), false));
+        // Shrinking factors work too
+        assert_eq!(backoff_delay(secs(100), 1, 2, 3, secs(HOUR)), (secs(12), false));
+        assert_eq!(format_backoff(secs(100), 1, 2, 10, secs(HOUR)), "attempt 10: retry in 0s");
+    }
+
+    #[test]
+    fn test_cap() {
+        assert_eq!(format_backoff(secs(60), 2, 1, 5, secs(HOUR)), "attempt 5: retry in 32m");
+        assert_eq!(format_backoff(secs(60), 2, 1, 6, secs(HOUR)), "attempt 6: retry in 1h (capped)");
+        assert_eq!(backoff_delay(secs(60), 2, 1, 6, secs(3_840)), (secs(3_840), false));
+        assert_eq!(backoff_delay(secs(60), 2, 1, 6, secs(3_839)), (secs(3_839), true));
+        assert_eq!(backoff_delay(secs(100), 3, 2, 8, secs(HOUR)), (secs(2_562), false));
+        assert_eq!(backoff_delay(secs(100), 3, 2, 9, secs(HOUR)), (secs(HOUR), true));
+    }
+
+    #[test]
+    fn test_attempt_zero() {
+        assert_eq!(format_backoff(secs(30), 2, 1, 0, secs(HOUR)), "attempt 0: retry in 30s");
+        assert_eq!(format_backoff(secs(30), 0, 0, 0, secs(HOUR)), "attempt 0: retry in 30s");
+        assert_eq!(format_backoff(secs(2 * HOUR), 2, 1, 0, secs(HOUR)), "attempt 0: retry in 1h (capped)");
+    }
+
+    #[test]
+    fn test_huge_attempts() {
+        assert_eq!(backoff_delay(secs(60), 2, 1, u32::MAX, secs(HOUR)), (secs(HOUR), true));
+        assert_eq!(backoff_delay(U256::MAX, 2, 1, 1, U256::MAX), (U256::MAX, true));
+        assert_eq!(backoff_delay(secs(1), 3, 2, 600, U256::MAX), (U256::MAX, true));
+        assert_eq!(backoff_delay(secs(1), 2, 1, 255, U256::MAX), (U256::from(1) << 255, false));
+        assert_eq!(backoff_delay(U256::MAX, 1, 2, 256, U256::MAX), (U256::ZERO, false));
+        assert_eq!(backoff_delay(U256::MAX, 1, 2, u32::MAX, U256::MAX), (U256::ZERO, false));
+        assert_eq!(backoff_delay(secs(60), 1, 1, u32::MAX, secs(HOUR)), (secs(60), false));
+        assert_eq!(backoff_delay(U256::ZERO, 2, 1, u32::MAX, secs(HOUR)), (U256::ZERO, false));
+        assert_eq!(backoff_delay(secs(60), 2, 0, 1, secs(HOUR)), (secs(HOUR), true));
+    }
+
+    #[test]
+    fn test_fixed_point_error_bound() {
+        let base = U256::from(1) << 200;
+        let delay = |attempt| backoff_delay(base, 1_000_001, 1_000_000, attempt, U256::MAX);
+        // Last attempt computed exactly: floor(2^200 * 1.000001^15)
+        let exact: U256 = "1606962148498383386202292813801085622620929663931862936876139".parse().unwrap();
+        assert_eq!(delay(15), (exact, false));
+        // First attempt past 512 bits, against floor(2^200 * 1.000001^16)
+        let exact: U256 = "1606963755460531884585679016093899423706552284861526868739076".parse().unwrap();
+        let (approx, capped) = delay(16);
+        assert!(!capped && approx <= exact);
+        assert!(exact - approx <= ((exact * U256::from(2 * 16 + 32)) >> 128) + U256::from(1));
+        assert!(exact - approx > U256::from(1) << 64);
+    }
+
+    #[test]
+    fn test_next_try_time() {
+        let now = secs(1_717_200_000);
+        assert_eq!(
+            format_backoff_at(secs(60), 2, 1, 2, secs(HOUR), now),
+            "attempt 2: retry in 4m, at 2024-06-01T00:04:00Z"
+        );
+        assert_eq!(
+            format_backoff_at(secs(60), 2, 1, 9, secs(HOUR), now),
+            "attempt 9: retry in 1h (capped), at 2024-06-01T01:00:00Z"
+        );
+    }
+}
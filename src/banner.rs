@@ -0,0 +1,144 @@
+//! Large five-line ASCII art for numbers and hex strings in terminal
+//! tooling.
+
+use crate::t9::CharsetError;
+
+/// Rows in every glyph
+const GLYPH_HEIGHT: usize = 5;
+
+/// Blank columns between glyphs
+const KERNING: &str = " ";
+
+/// Fill used by the bitmaps below, replaced according to the font
+const BITMAP_FILL: char = '#';
+
+/// Character used for the set pixels of a banner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BannerFont {
+    /// `#`, safe for any terminal or log
+    Hash,
+    /// `█` (U+2588), denser where Unicode is available
+    Block,
+}
+
+impl BannerFont {
+    fn fill(self) -> char {
+        match self {
+            BannerFont::Hash => '#',
+            BannerFont::Block => '█',
+        }
+    }
+}
+
+/// Renders `s` as five lines of ASCII art, glyphs three columns wide and
+/// separated by one blank column, e.g. "0x2a" as
+///
+/// ```text
+/// ###     ###  # 
+/// # # # #   # # #
+/// # #  #  ### ###
+/// # # # # #   # #
+/// ###     ### # #
+/// ```
+///
+/// Supported are the digits, hex letters `a`–`f` (either case, drawn as
+/// capitals), a lowercase-height `x` (either case), space, `-`, `.` and
+/// `:`. Lines are not trimmed, so every line has the same width.
+pub fn to_banner(s: &str, font: BannerFont) -> Result<String, CharsetError> {
+    if s.is_empty() {
+        return Err(CharsetError::Empty);
+    }
+    let glyphs = s
+        .char_indices()
+        .map(|(index, c)| glyph(c).ok_or(CharsetError::InvalidChar { index, found: c }))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let fill = font.fill();
+    let lines: Vec<String> = (0..GLYPH_HEIGHT)
+        .map(|row| {
+            glyphs
+                .iter()
+                .map(|glyph| glyph[row].replace(BITMAP_FILL, fill.encode_utf8(&mut [0; 4])))
+                .collect::<Vec<_>>()
+                .join(KERNING)
+        })
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+fn glyph(c: char) -> Option<[&'static str; GLYPH_HEIGHT]> {
+    let rows = match c.to_ascii_lowercase() {
+        '0' => ["###", "# #", "# #", "# #", "###"],
+        '1' => [" # ", "## ", " # ", " # ", "###"],
+        '2' => ["###", "  #", "###", "#  ", "###"],
+        '3' => ["###", "  #", "###", "  #", "###"],
+        '4' => ["# #", "# #", "###", "  #", "  #"],
+        '5' => ["###", "#  ", "###", "  #", "###"],
+        '6' => ["###", "#  ", "###", "# #", "###"],
+        '7' => ["###", "  #", "  #", "  #", "  #"],
+        '8' => ["###", "# #", "###", "# #", "###"],
+        '9' => ["###", "# #", "###", "  #", "###"],
+        'a' => [" # ", "# #", "###", "# #", "# #"],
+        'b' => ["## ", "# #", "## ", "# #", "## "],
+        'c' => [" ##", "#  ", "#  ", "#  ", " ##"],
+        'd' => ["## ", "# #", "# #", "# #", "## "],
+        'e' => ["###", "#  ", "## ", "#  ", "###"],
+        'f' => ["###", "#  ", "## ", "#  ", "#  "],
+        'x' => ["   ", "# #", " # ", "# #", "   "],
+        ' ' => ["   ", "   ", "   ", "   ", "   "],
+        '-' => ["   ", "   ", "###", "   ", "   "],
+        '.' => ["   ", "   ", "   ", "   ", " # "],
+        ':' => ["   ", " # ", "   ", " # ", "   "],
+        _ => return None,
+    };
+    Some(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_id() {
+        let expected = [
+            "###     ###  # ",
+            "# # # #   # # #",
+            "# #  #  ### ###",
+            "# # # # #   # #",
+            "###     ### # #",
+        ]
+        .join("\n");
+        assert_eq!(to_banner("0x2a", BannerFont::Hash).unwrap(), expected);
+        assert_eq!(to_banner("0X2A", BannerFont::Hash).unwrap(), expected);
+        assert_eq!(to_banner("0x2a", BannerFont::Block).unwrap(), expected.replace('#', "█"));
+    }
+
+    #[test]
+    fn test_full_charset() {
+        let banner = to_banner("0123456789abcdefx -.:", BannerFont::Hash).unwrap();
+        let expected = [
+            "###  #  ### ### # # ### ### ### ### ###  #  ##   ## ##  ### ###                    ",
+            "# # ##    #   # # # #   #     # # # # # # # # # #   # # #   #   # #              # ",
+            "# #  #  ### ### ### ### ###   # ### ### ### ##  #   # # ##  ##   #      ###        ",
+            "# #  #  #     #   #   # # #   # # #   # # # # # #   # # #   #   # #              # ",
+            "### ### ### ###   # ### ###   # ### ### # # ##   ## ##  ### #                #     ",
+        ]
+        .join("\n");
+        assert_eq!(banner, expected);
+        assert!(banner.lines().all(|line| line.len() == 21 * 4 - 1));
+    }
+
+    #[test]
+    fn test_unsupported_char() {
+        assert_eq!(
+            to_banner("0x2g", BannerFont::Hash),
+            Err(CharsetError::InvalidChar { index: 3, found: 'g' })
+        );
+        assert_eq!(
+            to_banner("é1", BannerFont::Block),
+            Err(CharsetError::InvalidChar { index: 0, found: 'é' })
+        );
+        assert_eq!(to_banner("1é", BannerFont::Hash), Err(CharsetError::InvalidChar { index: 1, found: 'é' }));
+        assert_eq!(to_banner("", BannerFont::Hash), Err(CharsetError::Empty));
+    }
+}
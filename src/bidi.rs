@@ -0,0 +1,95 @@
+//! Isolation of right-to-left user text inside left-to-right strings.
+
+/// First strong isolate: lays out the following text as its own paragraph
+const FSI: char = '\u{2068}';
+
+/// Pop directional isolate: ends the isolate opened by `FSI`
+const PDI: char = '\u{2069}';
+
+/// Ranges of right-to-left characters, sorted by start:
+/// - U+0590–08FF: Hebrew, Arabic, Syriac, Arabic Supplement, Thaana, NKo,
+///   Samaritan, Mandaic and the Arabic Extended blocks
+/// - U+200F, 202B, 202E, 2067: the right-to-left mark, embedding, override
+///   and isolate controls
+/// - U+FB1D–FDFF, FE70–FEFE: Hebrew and Arabic presentation forms
+/// - U+10800–10FFF, 1E800–1EFFF: right-to-left scripts outside the BMP,
+///   e.g. Phoenician, Adlam and the Arabic mathematical symbols
+const RTL_RANGES: [(u32, u32); 9] = [
+    (0x0590, 0x08FF),
+    (0x200F, 0x200F),
+    (0x202B, 0x202B),
+    (0x202E, 0x202E),
+    (0x2067, 0x2067),
+    (0xFB1D, 0xFDFF),
+    (0xFE70, 0xFEFE),
+    (0x10800, 0x10FFF),
+    (0x1E800, 0x1EFFF),
+];
+
+/// How `msgf_with` treats `Arg::Str` arguments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BidiMode {
+    /// Insert text verbatim
+    #[default]
+    Passthrough,
+    /// Pass text through `isolate_bidi`
+    Isolate,
+}
+
+/// Wraps `s` in FSI/PDI (U+2068/U+2069) if it contains right-to-left
+/// characters, and returns it unchanged otherwise.
+///
+/// Inside an isolate, right-to-left text is reordered on its own, so a name
+/// like "علي" interpolated into "sent 5 to {0}." cannot pull the neighbouring
+/// number or period into its run. Text without RTL characters is left alone
+/// so plain ASCII output stays byte-for-byte the same.
+pub fn isolate_bidi(s: &str) -> String {
+    if !s.chars().any(is_rtl) {
+        return s.to_string();
+    }
+    let mut result = String::with_capacity(s.len() + FSI.len_utf8() + PDI.len_utf8());
+    result.push(FSI);
+    result.push_str(s);
+    result.push(PDI);
+    result
+}
+
+fn is_rtl(c: char) -> bool {
+    let code = c as u32;
+    let index = RTL_RANGES.partition_point(|&(_, end)| end < code);
+    matches!(RTL_RANGES.get(index), Some(&(start, _)) if start <= code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranges_sorted() {
+        for pair in RTL_RANGES.windows(2) {
+            assert!(pair[0].0 <= pair[0].1 && pair[0].1 < pair[1].0, "{:x?}", pair);
+        }
+    }
+
+    #[test]
+    fn test_ascii_untouched() {
+        assert_eq!(isolate_bidi("alice.eth"), "alice.eth");
+        assert_eq!(isolate_bidi(""), "");
+        assert_eq!(isolate_bidi("流動性 café"), "流動性 café");
+    }
+
+    #[test]
+    fn test_arabic_and_hebrew_wrapped() {
+        assert_eq!(isolate_bidi("علي"), "\u{2068}علي\u{2069}");
+        assert_eq!(isolate_bidi("שלום"), "\u{2068}שלום\u{2069}");
+        assert_eq!(isolate_bidi("\u{FEFB}"), "\u{2068}\u{FEFB}\u{2069}");
+    }
+
+    #[test]
+    fn test_mixed_text_wrapped() {
+        assert_eq!(isolate_bidi("vault #3 علي"), "\u{2068}vault #3 علي\u{2069}");
+        // An override control alone is enough to reorder what follows
+        assert_eq!(isolate_bidi("a\u{202E}b"), "\u{2068}a\u{202E}b\u{2069}");
+        assert_eq!(isolate_bidi("\u{FEFF}x"), "\u{FEFF}x");
+    }
+}
@@ -0,0 +1,65 @@
+//! Braille-pattern rendering of binary data for comparing hashes by eye.
+
+use alloy_primitives::U256;
+
+/// First code point of the Unicode Braille Patterns block, the blank cell
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/// Renders each byte as one Braille cell, e.g. `[0xde, 0xad]` as "⣞⢭".
+///
+/// The mapping is Unicode's own and will not change: bit 0 (least
+/// significant) to bit 7 raise dots 1 to 8, so the cell is U+2800 plus the
+/// byte. The dots are laid out as
+///
+/// ```text
+/// 1 4
+/// 2 5
+/// 3 6
+/// 7 8
+/// ```
+///
+/// so a zero byte is a blank cell and 0xff a full one. A 32-byte hash
+/// becomes 32 glyphs.
+pub fn to_braille(data: &[u8]) -> String {
+    data.iter()
+        .map(|&byte| char::from_u32(BRAILLE_BLANK + u32::from(byte)).expect("Braille block is assigned"))
+        .collect()
+}
+
+/// Renders the 32 big-endian bytes of `value` with `to_braille`, leading
+/// zero bytes included so every value is 32 glyphs.
+pub fn u256_to_braille(value: U256) -> String {
+    to_braille(&value.to_be_bytes::<32>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blank_and_full_cells() {
+        assert_eq!(to_braille(&[0; 4]), "\u{2800}".repeat(4));
+        assert_eq!(to_braille(&[0xff; 3]), "⣿⣿⣿");
+        assert_eq!(to_braille(&[]), "");
+    }
+
+    #[test]
+    fn test_known_pattern() {
+        // 0x01 raises dot 1 (top left), 0x80 dot 8 (bottom right),
+        // 0x47 dots 1, 2, 3 and 7 (the left column)
+        assert_eq!(to_braille(&[0x01, 0x80, 0x47, 0xb8]), "⠁⢀⡇⢸");
+        assert_eq!(to_braille(&[0xde, 0xad, 0xbe, 0xef]), "⣞⢭⢾⣯");
+    }
+
+    #[test]
+    fn test_one_glyph_per_byte() {
+        for len in [1, 7, 20, 32, 100] {
+            assert_eq!(to_braille(&vec![0x5a; len]).chars().count(), len);
+        }
+        assert_eq!(u256_to_braille(U256::ZERO), "\u{2800}".repeat(32));
+        assert_eq!(u256_to_braille(U256::MAX), "⣿".repeat(32));
+        let one = u256_to_braille(U256::from(0x1234));
+        assert_eq!(one.chars().count(), 32);
+        assert!(one.ends_with("⠒⠴"));
+    }
+}
@@ -0,0 +1,93 @@
+//! Build identifiers embedded in contracts as a single `U256`.
+//!
+//! Layout, from the most significant bit:
+//!
+//! | bits    | content                                              |
+//! |---------|------------------------------------------------------|
+//! | 255–96  | commit hash prefix, left-aligned and zero-padded     |
+//! | 95–64   | reserved, zero                                       |
+//! | 63–0    | build time as a Unix timestamp                       |
+
+use alloy_primitives::U256;
+
+use crate::bytes_to_hex_string;
+use crate::time::timestamp_label;
+
+/// Bytes of commit hash the layout can hold
+const COMMIT_BYTES: usize = 20;
+
+/// Commit bytes shown in the short form, i.e. 8 hex characters
+const SHORT_COMMIT_BYTES: usize = 4;
+
+/// Error types for packing build info
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildInfoError {
+    /// Commit prefix is shorter than the 4 bytes shown or longer than 20
+    InvalidCommitLength(usize),
+}
+
+/// Packs a commit hash prefix of 4 to 20 bytes and a Unix timestamp into
+/// the build info layout.
+pub fn pack_build_info(commit_prefix: &[u8], timestamp: u64) -> Result<U256, BuildInfoError> {
+    if !(SHORT_COMMIT_BYTES..=COMMIT_BYTES).contains(&commit_prefix.len()) {
+        return Err(BuildInfoError::InvalidCommitLength(commit_prefix.len()));
+    }
+    let mut bytes = [0u8; 32];
+    bytes[..commit_prefix.len()].copy_from_slice(commit_prefix);
+    bytes[24..].copy_from_slice(&timestamp.to_be_bytes());
+    Ok(U256::from_be_bytes(bytes))
+}
+
+/// Formats packed build info as "build abcdef12 @ 2024-06-01T10:00:00Z",
+/// showing the first 8 hex characters of the commit.
+pub fn format_build_info(packed: U256) -> String {
+    let bytes = packed.to_be_bytes::<32>();
+    let commit = bytes_to_hex_string(&bytes[..SHORT_COMMIT_BYTES]);
+    let timestamp = u64::from_be_bytes(bytes[24..].try_into().expect("slice is 8 bytes"));
+    format!("build {} @ {}", &commit[2..], timestamp_label(U256::from(timestamp)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMIT: [u8; 8] = [0xab, 0xcd, 0xef, 0x12, 0x34, 0x56, 0x78, 0x9a];
+
+    #[test]
+    fn test_round_trip() {
+        let packed = pack_build_info(&COMMIT, 1_717_236_000).unwrap();
+        assert_eq!(format_build_info(packed), "build abcdef12 @ 2024-06-01T10:00:00Z");
+        let full = pack_build_info(&[0x01; 20], 1_717_236_000).unwrap();
+        assert_eq!(format_build_info(full), "build 01010101 @ 2024-06-01T10:00:00Z");
+    }
+
+    #[test]
+    fn test_layout() {
+        let packed = pack_build_info(&COMMIT, 0x1234).unwrap();
+        assert_eq!(packed >> 192usize, U256::from(0xabcdef123456789au64));
+        assert_eq!(packed & U256::from(u64::MAX), U256::from(0x1234));
+        // Reserved bits stay zero
+        assert!(((packed >> 64usize) & U256::from(u32::MAX)).is_zero());
+    }
+
+    #[test]
+    fn test_zero_timestamp() {
+        let packed = pack_build_info(&COMMIT, 0).unwrap();
+        assert_eq!(format_build_info(packed), "build abcdef12 @ 1970-01-01T00:00:00Z");
+        assert_eq!(format_build_info(U256::ZERO), "build 00000000 @ 1970-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_far_future_timestamp() {
+        let packed = pack_build_info(&COMMIT, u64::MAX).unwrap();
+        assert_eq!(format_build_info(packed), format!("build abcdef12 @ timestamp {}", u64::MAX));
+    }
+
+    #[test]
+    fn test_commit_length_validation() {
+        assert_eq!(pack_build_info(&COMMIT[..3], 0), Err(BuildInfoError::InvalidCommitLength(3)));
+        assert_eq!(pack_build_info(&[], 0), Err(BuildInfoError::InvalidCommitLength(0)));
+        assert_eq!(pack_build_info(&[0; 21], 0), Err(BuildInfoError::InvalidCommitLength(21)));
+        assert!(pack_build_info(&COMMIT[..4], 0).is_ok());
+    }
+}
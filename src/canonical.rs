@@ -0,0 +1,135 @@
+//! Canonical hex strings for use as map keys.
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+
+use alloy_primitives::U256;
+
+use crate::parse::{parse_digits, ParseError};
+use crate::to_string_radix;
+
+/// Hex string with exactly one representation per numeric value.
+///
+/// The canonical form is "0x" followed by lowercase digits with no leading
+/// zeros ("0x0" for zero), so `"0x0F"` and `"0xf"` produce equal keys.
+///
+/// `Ord` is numeric, which differs from `str` ordering. `Borrow<str>` lookups
+/// are therefore only sound in hash-based collections; look up a `BTreeMap`
+/// with a `CanonicalHex` key instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CanonicalHex(String);
+
+impl CanonicalHex {
+    /// Builds the canonical hex key for `value`.
+    pub fn from_u256(value: U256) -> Self {
+        let mut s = String::from("0x");
+        s.push_str(&to_string_radix(value, 16));
+        Self(s)
+    }
+
+    /// Parses a "0x"/"0X"-prefixed hex string of either case, with or
+    /// without leading zeros, into its canonical form.
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let digits = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .ok_or(ParseError::MissingHexPrefix)?;
+        parse_digits(digits, 2, 16).map(Self::from_u256)
+    }
+
+    /// Returns the canonical string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the numeric value of the key.
+    pub fn to_u256(&self) -> U256 {
+        parse_digits(&self.0[2..], 2, 16).expect("canonical hex is always valid")
+    }
+}
+
+impl Ord for CanonicalHex {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // No leading zeros, so a longer string is always a larger number
+        self.0
+            .len()
+            .cmp(&other.0.len())
+            .then_with(|| self.0.cmp(&other.0))
+    }
+}
+
+impl PartialOrd for CanonicalHex {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Borrow<str> for CanonicalHex {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for CanonicalHex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[test]
+    fn test_canonical_form() {
+        assert_eq!(CanonicalHex::from_u256(U256::ZERO).as_str(), "0x0");
+        assert_eq!(CanonicalHex::from_u256(U256::from(15)).as_str(), "0xf");
+        assert_eq!(CanonicalHex::from_u256(U256::from(0x100)).to_string(), "0x100");
+        assert_eq!(CanonicalHex::from_u256(U256::MAX).to_u256(), U256::MAX);
+    }
+
+    #[test]
+    fn test_equal_across_formats() {
+        let expected = CanonicalHex::from_u256(U256::from(15));
+        for input in ["0xf", "0xF", "0x0F", "0X000f", "0x0000000f"] {
+            assert_eq!(CanonicalHex::parse(input).unwrap(), expected, "{}", input);
+        }
+        assert_eq!(CanonicalHex::parse("0x00").unwrap().as_str(), "0x0");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(CanonicalHex::parse("f"), Err(ParseError::MissingHexPrefix));
+        assert_eq!(CanonicalHex::parse("0x"), Err(ParseError::Empty));
+        assert_eq!(
+            CanonicalHex::parse("0xfz"),
+            Err(ParseError::InvalidDigit { index: 3, found: 'z' })
+        );
+        assert_eq!(
+            CanonicalHex::parse(&format!("0x1{}", "0".repeat(64))),
+            Err(ParseError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_btreemap_orders_numerically() {
+        let mut map = BTreeMap::new();
+        for value in [0x100u64, 0x2, 0xff, 0x10, 0x0, 0x1f] {
+            map.insert(CanonicalHex::from_u256(U256::from(value)), value);
+        }
+        let values: Vec<u64> = map.values().copied().collect();
+        assert_eq!(values, vec![0x0, 0x2, 0x10, 0x1f, 0xff, 0x100]);
+        // "0x2" > "0x10" lexicographically but not numerically
+        assert!(CanonicalHex::parse("0x2").unwrap() < CanonicalHex::parse("0x10").unwrap());
+    }
+
+    #[test]
+    fn test_borrow_lookup_with_str() {
+        let mut map = HashMap::new();
+        map.insert(CanonicalHex::parse("0x0F").unwrap(), "fifteen");
+        assert_eq!(map.get("0xf"), Some(&"fifteen"));
+        assert_eq!(map.get("0x0F"), None);
+    }
+}
@@ -0,0 +1,330 @@
+//! Text helpers for rendering on-chain charts.
+
+use alloy_primitives::{U256, U512};
+
+use crate::units::split_decimal;
+use crate::width::{pad_right, text_width, WidthMode};
+use crate::{magnitude, pow10, to_string};
+
+/// Suffixes for tick labels, one per power of 1000
+const TICK_SUFFIXES: [&str; 5] = ["", "K", "M", "B", "T"];
+
+/// Block characters for sparklines, lowest to highest
+const SPARK_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Single-byte fallback levels for sparklines, lowest to highest
+const SPARK_ASCII: [char; 4] = ['_', '-', '=', '#'];
+
+/// Renders a series as a Unicode block sparkline, one character per value.
+///
+/// Values are scaled between the series min and max. A flat series renders
+/// mid-height blocks, a single value renders one full block and an empty
+/// series renders an empty string.
+pub fn sparkline(values: &[U256]) -> String {
+    render_sparkline(values, &SPARK_BLOCKS)
+}
+
+/// Renders a series like `sparkline`, using the ASCII levels `_-=#`.
+pub fn sparkline_ascii(values: &[U256]) -> String {
+    render_sparkline(values, &SPARK_ASCII)
+}
+
+fn render_sparkline(values: &[U256], levels: &[char]) -> String {
+    let top = levels.len() - 1;
+    let (min, max) = match (values.iter().min(), values.iter().max()) {
+        (Some(&min), Some(&max)) => (min, max),
+        _ => return String::new(),
+    };
+    if values.len() == 1 {
+        return levels[top].to_string();
+    }
+    if min == max {
+        return values.iter().map(|_| levels[top / 2]).collect();
+    }
+
+    values
+        .iter()
+        .map(|&value| levels[scale_to_level(value - min, max - min, top)])
+        .collect()
+}
+
+/// Maps `offset` in `0..=range` to the nearest level in `0..=top`.
+///
+/// Uses a 512-bit intermediate so ranges spanning all of U256 cannot overflow.
+fn scale_to_level(offset: U256, range: U256, top: usize) -> usize {
+    let offset = U512::from(offset);
+    let range = U512::from(range);
+    let scaled = offset * U512::from(2 * top) + range;
+    (scaled / (range * U512::from(2))).to::<usize>()
+}
+
+/// Renders a bar of `width_chars` cells filled in proportion to `value / max`.
+///
+/// The filled cell count rounds half up and is clamped to the full width
+/// when `value > max`. A zero `max` renders an empty bar for a zero value.
+pub fn bar(value: U256, max: U256, width_chars: usize) -> String {
+    render_bar(value, max, width_chars, '█', '░')
+}
+
+/// Renders a bar like `bar`, using `#` for filled and `.` for unfilled cells.
+pub fn bar_ascii(value: U256, max: U256, width_chars: usize) -> String {
+    render_bar(value, max, width_chars, '#', '.')
+}
+
+/// Renders one line per entry: the label right-padded to the longest label,
+/// a `bar` scaled against the largest count, then the count itself.
+///
+/// Labels are padded by character count so multibyte labels stay aligned.
+pub fn labeled_bars(entries: &[(&str, U256)], width: usize) -> String {
+    labeled_bars_with(entries, width, WidthMode::Chars)
+}
+
+/// Renders bars like `labeled_bars`, measuring labels according to `mode`.
+pub fn labeled_bars_with(entries: &[(&str, U256)], width: usize, mode: WidthMode) -> String {
+    let label_width = entries.iter().map(|(label, _)| text_width(label, mode)).max().unwrap_or(0);
+    let max = entries.iter().map(|&(_, count)| count).max().unwrap_or(U256::ZERO);
+
+    entries
+        .iter()
+        .map(|&(label, count)| {
+            let label = pad_right(label, label_width, mode);
+            format!("{} {} {}", label, bar(count, max, width), to_string(count))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_bar(value: U256, max: U256, width: usize, filled: char, unfilled: char) -> String {
+    let cells = if value >= max {
+        if value.is_zero() { 0 } else { width }
+    } else {
+        // Round half up: floor((value * width * 2 + max) / (max * 2))
+        let scaled = U512::from(value) * U512::from(2 * width) + U512::from(max);
+        (scaled / (U512::from(max) * U512::from(2))).to::<usize>()
+    };
+
+    let mut result = String::with_capacity(width * filled.len_utf8());
+    result.extend(core::iter::repeat_n(filled, cells));
+    result.extend(core::iter::repeat_n(unfilled, width - cells));
+    result
+}
+
+/// Generates compact axis labels for "nice" ticks between `min` and `max`.
+///
+/// Values are interpreted as fixed-point numbers with `decimals` decimals.
+/// The step is the smallest of 1, 2, 2.5 or 5 × 10^k that yields at most
+/// `target_count` ticks; ticks are the multiples of the step within the range.
+///
+/// Degenerate inputs: `target_count == 0` or `max < min` returns no labels,
+/// and `target_count == 1` or `min == max` returns the single label for `min`.
+pub fn tick_labels(min: U256, max: U256, target_count: usize, decimals: u8) -> Vec<String> {
+    if target_count == 0 || max < min {
+        return Vec::new();
+    }
+    if target_count == 1 || min == max {
+        return vec![format_tick(min, decimals)];
+    }
+
+    let intervals = U256::from(target_count - 1);
+    let range = max - min;
+    let mut raw_step = range / intervals;
+    if !(range % intervals).is_zero() || raw_step.is_zero() {
+        raw_step += U256::from(1);
+    }
+    let step = nice_step(raw_step);
+
+    let mut labels = Vec::new();
+    let mut tick = match round_up_to_multiple(min, step) {
+        Some(tick) => tick,
+        None => return labels,
+    };
+    while tick <= max {
+        labels.push(format_tick(tick, decimals));
+        match tick.checked_add(step) {
+            Some(next) => tick = next,
+            None => break,
+        }
+    }
+    labels
+}
+
+/// Returns the smallest 1, 2, 2.5 or 5 × 10^k step that is at least `raw`.
+fn nice_step(raw: U256) -> U256 {
+    let exp = magnitude(raw) as usize;
+    let base = pow10(exp).expect("magnitude is at most 77");
+    // Near the top of U256 the larger candidates overflow and are skipped
+    let mut candidates = vec![base];
+    candidates.extend(base.checked_mul(U256::from(2)));
+    if exp > 0 {
+        candidates.extend((base / U256::from(2)).checked_mul(U256::from(5)));
+    }
+    candidates.extend(base.checked_mul(U256::from(5)));
+    candidates.extend(pow10(exp + 1));
+    candidates.into_iter().find(|&c| c >= raw).unwrap_or(raw)
+}
+
+/// Formats a fixed-point tick value exactly, scaling by thousands with a suffix.
+fn format_tick(value: U256, decimals: u8) -> String {
+    let (mut integer, mut fraction) = split_decimal(value, decimals);
+
+    let index = ((integer.len() - 1) / 3).min(TICK_SUFFIXES.len() - 1);
+    if index > 0 {
+        let split = integer.len() - 3 * index;
+        fraction.insert_str(0, &integer[split..]);
+        integer.truncate(split);
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{}{}", integer, TICK_SUFFIXES[index])
+    } else {
+        format!("{}.{}{}", integer, fraction, TICK_SUFFIXES[index])
+    }
+}
+
+/// Rounds `value` up to the next multiple of `step`, or None on overflow.
+fn round_up_to_multiple(value: U256, step: U256) -> Option<U256> {
+    let remainder = value % step;
+    if remainder.is_zero() {
+        Some(value)
+    } else {
+        value.checked_add(step - remainder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(min: u64, max: u64, count: usize, decimals: u8) -> Vec<String> {
+        tick_labels(U256::from(min), U256::from(max), count, decimals)
+    }
+
+    #[test]
+    fn test_million_range_five_ticks() {
+        assert_eq!(labels(0, 1_000_000, 5, 0), vec!["0", "250K", "500K", "750K", "1M"]);
+    }
+
+    #[test]
+    fn test_nice_steps() {
+        assert_eq!(labels(0, 100, 6, 0), vec!["0", "20", "40", "60", "80", "100"]);
+        assert_eq!(labels(0, 10, 3, 0), vec!["0", "5", "10"]);
+        assert_eq!(labels(3, 17, 4, 0), vec!["5", "10", "15"]);
+        assert_eq!(labels(0, 1_250_000, 6, 0), vec!["0", "250K", "500K", "750K", "1M", "1.25M"]);
+    }
+
+    #[test]
+    fn test_token_decimals() {
+        let one = 1_000_000_000_000_000_000u64;
+        assert_eq!(labels(0, one, 5, 18), vec!["0", "0.25", "0.5", "0.75", "1"]);
+        let whole = U256::from(one);
+        let ticks = tick_labels(U256::ZERO, whole * U256::from(5000), 6, 18);
+        assert_eq!(ticks, vec!["0", "1K", "2K", "3K", "4K", "5K"]);
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert!(labels(0, 100, 0, 0).is_empty());
+        assert!(labels(100, 0, 5, 0).is_empty());
+        assert_eq!(labels(0, 100, 1, 0), vec!["0"]);
+        assert_eq!(labels(42, 42, 5, 0), vec!["42"]);
+        assert_eq!(labels(1, 2, 10, 0), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn test_full_range_does_not_overflow() {
+        let ticks = tick_labels(U256::ZERO, U256::MAX, 3, 0);
+        assert_eq!(ticks.len(), 2);
+        assert_eq!(ticks[0], "0");
+        // A raw step above 10^77 has no larger nice step that fits
+        let ticks = tick_labels(U256::ZERO, U256::MAX, 2, 0);
+        assert_eq!(ticks.len(), 2);
+        assert_eq!(ticks[0], "0");
+        assert_eq!(ticks[1], format_tick(U256::MAX, 0));
+    }
+
+    fn series(values: &[u64]) -> Vec<U256> {
+        values.iter().map(|&v| U256::from(v)).collect()
+    }
+
+    #[test]
+    fn test_sparkline_monotonic() {
+        assert_eq!(sparkline(&series(&[1, 2, 3, 4, 5, 6, 7, 8])), "▁▂▃▄▅▆▇█");
+        assert_eq!(sparkline(&series(&[80, 10, 45])), "█▁▅");
+        assert_eq!(sparkline_ascii(&series(&[0, 1, 2, 3])), "_-=#");
+    }
+
+    #[test]
+    fn test_sparkline_flat_and_trivial() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline_ascii(&[]), "");
+        assert_eq!(sparkline(&series(&[5])), "█");
+        assert_eq!(sparkline_ascii(&series(&[5])), "#");
+        assert_eq!(sparkline(&series(&[7, 7, 7])), "▄▄▄");
+        assert_eq!(sparkline_ascii(&series(&[7, 7])), "--");
+    }
+
+    #[test]
+    fn test_sparkline_two_points() {
+        assert_eq!(sparkline(&series(&[0, 10])), "▁█");
+        assert_eq!(sparkline(&series(&[10, 0])), "█▁");
+    }
+
+    #[test]
+    fn test_sparkline_full_range() {
+        let values = [U256::ZERO, U256::MAX / U256::from(2), U256::MAX, U256::ZERO];
+        assert_eq!(sparkline(&values), "▁▄█▁");
+        assert_eq!(sparkline_ascii(&values), "_-#_");
+    }
+
+    #[test]
+    fn test_bar_bounds() {
+        assert_eq!(bar(U256::ZERO, U256::from(100), 10), "░░░░░░░░░░");
+        assert_eq!(bar(U256::from(100), U256::from(100), 10), "██████████");
+        assert_eq!(bar_ascii(U256::from(70), U256::from(100), 10), "#######...");
+        assert_eq!(bar(U256::ZERO, U256::ZERO, 4), "░░░░");
+        assert_eq!(bar(U256::from(1), U256::from(1), 0), "");
+    }
+
+    #[test]
+    fn test_bar_rounds_half_up() {
+        // 1/4 of 2 cells is exactly half a cell
+        assert_eq!(bar_ascii(U256::from(1), U256::from(4), 2), "#.");
+        assert_eq!(bar_ascii(U256::from(1), U256::from(2), 3), "##.");
+        assert_eq!(bar_ascii(U256::from(49), U256::from(100), 1), ".");
+        assert_eq!(bar_ascii(U256::from(50), U256::from(100), 1), "#");
+        assert_eq!(bar_ascii(U256::MAX / U256::from(2), U256::MAX, 2), "#.");
+    }
+
+    #[test]
+    fn test_bar_clamps() {
+        assert_eq!(bar_ascii(U256::from(500), U256::from(100), 5), "#####");
+        assert_eq!(bar(U256::from(1), U256::ZERO, 3), "███");
+        assert_eq!(bar_ascii(U256::MAX, U256::from(1), 4), "####");
+    }
+
+    #[test]
+    fn test_labeled_bars_alignment() {
+        let chart = labeled_bars(
+            &[("For", U256::from(60)), ("Against", U256::from(30)), ("Ñé", U256::from(0))],
+            6,
+        );
+        let expected = "For     ██████ 60\nAgainst ███░░░ 30\nÑé      ░░░░░░ 0";
+        assert_eq!(chart, expected);
+        assert_eq!(labeled_bars(&[], 6), "");
+    }
+
+    #[test]
+    fn test_labeled_bars_display_width() {
+        let chart = labeled_bars_with(&[("賛成", U256::from(2)), ("No", U256::from(1))], 2, WidthMode::Display);
+        assert_eq!(chart, "賛成 ██ 2\nNo   █░ 1");
+    }
+
+    #[test]
+    fn test_format_tick() {
+        assert_eq!(format_tick(U256::from(1_500), 0), "1.5K");
+        assert_eq!(format_tick(U256::from(15), 1), "1.5");
+        assert_eq!(format_tick(U256::from(5), 3), "0.005");
+        assert_eq!(format_tick(U256::from(2_000_000_000_000_000u64), 0), "2000T");
+    }
+}
@@ -0,0 +1,127 @@
+//! EIP-1191 chain-aware address checksums.
+//!
+//! EIP-1191 mixes the chain id into the EIP-55 hash so a checksummed
+//! address from one chain fails validation on another. Only a few chains
+//! (notably RSK, ids 30 and 31) use it; wallets and most tooling ignore
+//! it, so EIP-55 (`address_to_checksum_hex_string`, or `None` here) stays
+//! the default everywhere else in this crate.
+
+use alloy_primitives::{keccak256, Address, U256};
+
+use crate::id::parse_address;
+use crate::{address_to_checksum_hex_string, address_to_hex_string, to_string};
+
+/// Formats `addr` with an EIP-1191 checksum for `chain_id`, or a plain
+/// EIP-55 checksum for `None`.
+///
+/// Per EIP-1191 the hashed string is the chain id in decimal followed by
+/// the "0x"-prefixed lowercase address, e.g. "300x5aae…".
+pub fn to_checksum_address_chain(addr: Address, chain_id: Option<u64>) -> String {
+    let chain_id = match chain_id {
+        Some(chain_id) => chain_id,
+        None => return address_to_checksum_hex_string(addr),
+    };
+
+    let mut buffer = address_to_hex_string(addr).into_bytes();
+    let mut preimage = to_string(U256::from(chain_id)).into_bytes();
+    preimage.extend_from_slice(&buffer);
+    let hash = keccak256(&preimage);
+    for i in 0..40 {
+        let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0xf };
+        if nibble > 7 && buffer[2 + i].is_ascii_lowercase() {
+            buffer[2 + i] ^= 0x20;
+        }
+    }
+    String::from_utf8(buffer).expect("hex digits are ASCII")
+}
+
+/// Returns true if `s` is a "0x"-prefixed address whose letter case is
+/// exactly its checksum for `chain_id` (EIP-55 for `None`).
+///
+/// All-lowercase input fails unless that happens to be the checksum.
+pub fn verify_checksum_chain(s: &str, chain_id: Option<u64>) -> bool {
+    match parse_address(s, 0) {
+        Ok(addr) => to_checksum_address_chain(addr, chain_id) == s,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Addresses from the EIP-1191 test vectors
+    const RSK_MAINNET: [&str; 9] = [
+        "0x27b1FdB04752BBc536007A920D24ACB045561c26",
+        "0x3599689E6292B81B2D85451025146515070129Bb",
+        "0x42712D45473476B98452f434E72461577d686318",
+        "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD",
+        "0x8617E340b3D01Fa5f11f306f4090fd50E238070D",
+        "0x88021160c5C792225E4E5452585947470010289d",
+        "0xD1220A0Cf47c7B9BE7a2e6ba89F429762E7B9adB",
+        "0xDe709F2102306220921060314715629080e2FB77",
+        "0xFb6916095cA1Df60bb79ce92cE3EA74c37c5d359",
+    ];
+
+    const RSK_TESTNET: [&str; 9] = [
+        "0x27B1FdB04752BbC536007a920D24acB045561C26",
+        "0x3599689e6292b81b2D85451025146515070129Bb",
+        "0x42712D45473476B98452F434E72461577D686318",
+        "0x5aAeb6053F3e94c9b9A09F33669435E7EF1BEaEd",
+        "0x8617e340b3D01fa5F11f306F4090Fd50e238070d",
+        "0x88021160c5C792225E4E5452585947470010289d",
+        "0xd1220a0CF47c7B9Be7A2E6Ba89f429762E7b9adB",
+        "0xDE709F2102306220921060314715629080e2Fb77",
+        "0xFb6916095CA1dF60bb79CE92ce3Ea74C37c5D359",
+    ];
+
+    const EIP55: [&str; 4] = [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+
+    fn parse(s: &str) -> Address {
+        parse_address(s, 0).unwrap()
+    }
+
+    #[test]
+    fn test_eip1191_vectors() {
+        for (mainnet, testnet) in RSK_MAINNET.iter().zip(RSK_TESTNET) {
+            let addr = parse(mainnet);
+            assert_eq!(to_checksum_address_chain(addr, Some(30)), *mainnet);
+            assert_eq!(to_checksum_address_chain(addr, Some(31)), testnet);
+            assert!(verify_checksum_chain(mainnet, Some(30)));
+            assert!(verify_checksum_chain(testnet, Some(31)));
+        }
+    }
+
+    #[test]
+    fn test_none_is_eip55() {
+        for expected in EIP55 {
+            let addr = parse(expected);
+            assert_eq!(to_checksum_address_chain(addr, None), expected);
+            assert_eq!(to_checksum_address_chain(addr, None), address_to_checksum_hex_string(addr));
+            assert!(verify_checksum_chain(expected, None));
+        }
+    }
+
+    #[test]
+    fn test_checksums_do_not_cross_chains() {
+        assert!(!verify_checksum_chain(RSK_MAINNET[0], Some(31)));
+        assert!(!verify_checksum_chain(RSK_MAINNET[0], None));
+        assert!(!verify_checksum_chain(EIP55[0], Some(30)));
+        // Chain 1 under EIP-1191 is not EIP-55
+        assert!(!verify_checksum_chain(EIP55[0], Some(1)));
+    }
+
+    #[test]
+    fn test_verify_rejects_malformed() {
+        assert!(!verify_checksum_chain(&EIP55[0].to_lowercase(), None));
+        assert!(!verify_checksum_chain(&EIP55[0][2..], None));
+        assert!(!verify_checksum_chain(&EIP55[0][..41], None));
+        assert!(!verify_checksum_chain("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg", None));
+        assert!(!verify_checksum_chain("", Some(30)));
+    }
+}
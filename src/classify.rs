@@ -0,0 +1,111 @@
+//! Value classification predicates used to choose between display styles.
+
+use alloy_primitives::U256;
+
+use crate::{log10, pow10};
+
+/// Returns true if the value is a single non-zero digit followed by one or
+/// more zeros, e.g. 5_000_000 or 10 (but not 0, 7 or 2_500).
+pub fn is_round_number(value: U256) -> bool {
+    if value < U256::from(10) {
+        return false;
+    }
+    let scale = pow10(magnitude(value) as usize).expect("magnitude is at most 77");
+    (value % scale).is_zero()
+}
+
+/// Returns the decimal order of magnitude: digit count minus one (0 for zero).
+pub fn magnitude(value: U256) -> u8 {
+    log10(value) as u8
+}
+
+/// Returns true if the value is 10^k for some k >= 0.
+pub fn is_power_of_ten(value: U256) -> bool {
+    !value.is_zero() && pow10(magnitude(value) as usize) == Some(value)
+}
+
+/// Returns true if the value is 2^k for some k >= 0.
+pub fn is_power_of_two(value: U256) -> bool {
+    value.count_ones() == 1
+}
+
+/// Returns the power of ten closest to the value, rounding ties down.
+///
+/// Zero maps to 1. Values past 10^77 map to 10^77, the largest power of ten
+/// that fits in a U256.
+pub fn nearest_power_of_ten(value: U256) -> U256 {
+    if value.is_zero() {
+        return U256::from(1);
+    }
+    let exp = magnitude(value) as usize;
+    let lower = pow10(exp).expect("magnitude is at most 77");
+    match pow10(exp + 1) {
+        Some(upper) if upper - value < value - lower => upper,
+        _ => lower,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_magnitude() {
+        assert_eq!(magnitude(U256::ZERO), 0);
+        assert_eq!(magnitude(U256::from(9)), 0);
+        assert_eq!(magnitude(U256::from(999)), 2);
+        assert_eq!(magnitude(U256::from(1000)), 3);
+        assert_eq!(magnitude(U256::MAX), 77);
+    }
+
+    #[test]
+    fn test_is_round_number() {
+        assert!(is_round_number(U256::from(5_000_000)));
+        assert!(is_round_number(U256::from(10)));
+        assert!(is_round_number(U256::from(1000)));
+        assert!(!is_round_number(U256::ZERO));
+        assert!(!is_round_number(U256::from(7)));
+        assert!(!is_round_number(U256::from(999)));
+        assert!(!is_round_number(U256::from(2_500)));
+        assert!(!is_round_number(U256::MAX));
+        assert!(is_round_number(pow10(77).unwrap()));
+    }
+
+    #[test]
+    fn test_is_power_of_ten() {
+        assert!(is_power_of_ten(U256::from(1)));
+        assert!(is_power_of_ten(U256::from(1000)));
+        assert!(!is_power_of_ten(U256::ZERO));
+        assert!(!is_power_of_ten(U256::from(999)));
+        assert!(!is_power_of_ten(U256::from(1001)));
+        assert!(!is_power_of_ten(U256::from(5_000)));
+        assert!(is_power_of_ten(pow10(77).unwrap()));
+        assert!(!is_power_of_ten(U256::MAX));
+    }
+
+    #[test]
+    fn test_is_power_of_two() {
+        assert!(!is_power_of_two(U256::ZERO));
+        assert!(is_power_of_two(U256::from(1)));
+        for k in [1usize, 8, 63, 64, 128, 255] {
+            let p = U256::from(1) << k;
+            assert!(is_power_of_two(p), "2^{}", k);
+            assert!(!is_power_of_two(p + U256::from(1)));
+        }
+        assert!(!is_power_of_two(U256::from(6)));
+        assert!(!is_power_of_two(U256::MAX));
+    }
+
+    #[test]
+    fn test_nearest_power_of_ten() {
+        assert_eq!(nearest_power_of_ten(U256::ZERO), U256::from(1));
+        assert_eq!(nearest_power_of_ten(U256::from(4)), U256::from(1));
+        assert_eq!(nearest_power_of_ten(U256::from(6)), U256::from(10));
+        assert_eq!(nearest_power_of_ten(U256::from(551)), U256::from(1000));
+        assert_eq!(nearest_power_of_ten(U256::from(999)), U256::from(1000));
+        assert_eq!(nearest_power_of_ten(U256::from(1000)), U256::from(1000));
+        // 550 is equidistant from 100 and 1000 and rounds down
+        assert_eq!(nearest_power_of_ten(U256::from(550)), U256::from(100));
+        assert_eq!(nearest_power_of_ten(U256::MAX), pow10(77).unwrap());
+    }
+}
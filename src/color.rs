@@ -0,0 +1,158 @@
+//! Colors and gradients for on-chain generative SVG art.
+
+use alloy_primitives::{B256, U512};
+
+use crate::escape::escape_html_attr;
+use crate::units::format_hundredths;
+
+/// Fixed-point denominator for `hsl_to_rgb`: saturation and lightness
+/// percentages (100 × 100) times the 60 degrees of a hue sector
+const HSL_SCALE: u32 = 100 * 100 * 60;
+
+/// Converts an HSL color to RGB using integer arithmetic only.
+///
+/// `hue` is in degrees and taken modulo 360; `saturation` and `lightness`
+/// are percentages, clamped to 100. Channels are rounded half up, so
+/// `hsl_to_rgb(0, 0, 50)` is the CSS gray `[128, 128, 128]`.
+pub fn hsl_to_rgb(hue: u16, saturation: u8, lightness: u8) -> [u8; 3] {
+    let hue = u32::from(hue % 360);
+    let saturation = u32::from(saturation.min(100));
+    let lightness = u32::from(lightness.min(100));
+
+    // Chroma in units of 1/10000, then everything in units of 1/HSL_SCALE
+    let chroma = (100 - (2 * lightness).abs_diff(100)) * saturation;
+    let c = chroma * 60;
+    let x = chroma * (60 - (hue % 120).abs_diff(60));
+    let m = lightness * 100 * 60 - chroma * 30;
+    let (r, g, b) = match hue / 60 {
+        0 => (c, x, 0),
+        1 => (x, c, 0),
+        2 => (0, c, x),
+        3 => (0, x, c),
+        4 => (x, 0, c),
+        _ => (c, 0, x),
+    };
+    let channel = |v: u32| (((v + m) * 255 + HSL_SCALE / 2) / HSL_SCALE) as u8;
+    [channel(r), channel(g), channel(b)]
+}
+
+/// Formats RGB channels as a CSS hex color, e.g. "#336699".
+pub fn format_hex_color(rgb: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2])
+}
+
+/// Derives `count` colors that belong together from `seed`, as "#rrggbb".
+///
+/// The seed fixes a base hue (bytes 0–1, modulo 360), a saturation of
+/// 55–84% (byte 2) and a lightness of 45–64% (byte 3). The colors share
+/// saturation and lightness and have hues evenly spaced around the wheel
+/// starting from the base, so any count gives a balanced palette.
+pub fn palette_from_seed(seed: B256, count: usize) -> Vec<String> {
+    let base = u16::from_be_bytes([seed[0], seed[1]]) % 360;
+    let saturation = 55 + seed[2] % 30;
+    let lightness = 45 + seed[3] % 20;
+    (0..count)
+        .map(|i| {
+            let offset = (360 * i / count) as u16;
+            format_hex_color(hsl_to_rgb(base + offset, saturation, lightness))
+        })
+        .collect()
+}
+
+/// Renders an SVG `<linearGradient>` with `colors` as evenly spaced stops
+/// from 0% to 100%, left to right.
+///
+/// The id and colors are escaped for attribute values. A single color gets
+/// one stop at 0%, which SVG renders as a solid fill.
+pub fn linear_gradient_svg(colors: &[&str], id: &str) -> String {
+    let mut result = format!("<linearGradient id=\"{}\">", escape_html_attr(id));
+    let last = colors.len().saturating_sub(1).max(1);
+    for (i, color) in colors.iter().enumerate() {
+        let offset = format_hundredths(U512::from(i * 100), U512::from(last));
+        result.push_str(&format!(
+            "<stop offset=\"{}%\" stop-color=\"{}\"/>",
+            offset,
+            escape_html_attr(color)
+        ));
+    }
+    result.push_str("</linearGradient>");
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::keccak256;
+
+    #[test]
+    fn test_hsl_spot_checks() {
+        assert_eq!(hsl_to_rgb(0, 100, 50), [255, 0, 0]);
+        assert_eq!(hsl_to_rgb(120, 100, 25), [0, 128, 0]);
+        assert_eq!(hsl_to_rgb(240, 100, 50), [0, 0, 255]);
+        assert_eq!(hsl_to_rgb(30, 100, 50), [255, 128, 0]);
+        assert_eq!(hsl_to_rgb(210, 50, 40), [51, 102, 153]);
+        assert_eq!(hsl_to_rgb(300, 100, 25), [128, 0, 128]);
+        assert_eq!(hsl_to_rgb(0, 0, 50), [128, 128, 128]);
+        assert_eq!(hsl_to_rgb(0, 0, 100), [255, 255, 255]);
+        assert_eq!(hsl_to_rgb(77, 100, 0), [0, 0, 0]);
+        // Hue wraps and out-of-range percentages clamp
+        assert_eq!(hsl_to_rgb(360, 100, 50), hsl_to_rgb(0, 100, 50));
+        assert_eq!(hsl_to_rgb(60, 200, 50), [255, 255, 0]);
+    }
+
+    #[test]
+    fn test_format_hex_color() {
+        assert_eq!(format_hex_color([51, 102, 153]), "#336699");
+        assert_eq!(format_hex_color([0, 10, 255]), "#000aff");
+    }
+
+    #[test]
+    fn test_pinned_palette() {
+        // Base hue 0x0102 % 360 = 258, saturation 55 + 3 = 58%, lightness 45 + 4 = 49%;
+        // values agree with Python's colorsys.hls_to_rgb
+        let mut bytes = [0u8; 32];
+        bytes[..4].copy_from_slice(&[1, 2, 3, 4]);
+        let palette = palette_from_seed(B256::from(bytes), 3);
+        assert_eq!(palette, ["#6034c5", "#c56034", "#34c560"]);
+        assert_eq!(palette[0], format_hex_color(hsl_to_rgb(258, 58, 49)));
+    }
+
+    #[test]
+    fn test_palette_counts() {
+        let seed = keccak256(b"token 1");
+        let one = palette_from_seed(seed, 1);
+        assert_eq!(one.len(), 1);
+        let sixteen = palette_from_seed(seed, 16);
+        assert_eq!(sixteen.len(), 16);
+        assert_eq!(sixteen[0], one[0]);
+        assert!(sixteen.iter().all(|c| c.len() == 7 && c.starts_with('#')));
+        // Evenly spaced hues are distinct
+        let mut unique = sixteen.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 16);
+        assert!(palette_from_seed(seed, 0).is_empty());
+    }
+
+    #[test]
+    fn test_gradient_fragment() {
+        assert_eq!(
+            linear_gradient_svg(&["#ff0000", "#00ff00", "#0000ff"], "bg"),
+            "<linearGradient id=\"bg\">\
+             <stop offset=\"0%\" stop-color=\"#ff0000\"/>\
+             <stop offset=\"50%\" stop-color=\"#00ff00\"/>\
+             <stop offset=\"100%\" stop-color=\"#0000ff\"/>\
+             </linearGradient>"
+        );
+        assert_eq!(
+            linear_gradient_svg(&["a", "b", "c", "d"], "g"),
+            "<linearGradient id=\"g\"><stop offset=\"0%\" stop-color=\"a\"/><stop offset=\"33.33%\" stop-color=\"b\"/>\
+             <stop offset=\"66.67%\" stop-color=\"c\"/><stop offset=\"100%\" stop-color=\"d\"/></linearGradient>"
+        );
+        assert_eq!(
+            linear_gradient_svg(&["#fff"], "x\"y"),
+            "<linearGradient id=\"x&quot;y\"><stop offset=\"0%\" stop-color=\"#fff\"/></linearGradient>"
+        );
+        assert_eq!(linear_gradient_svg(&[], "e"), "<linearGradient id=\"e\"></linearGradient>");
+    }
+}
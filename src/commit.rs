@@ -0,0 +1,185 @@
+//! Commit-reveal commitments hashed from formatted values as they are
+//! produced, without building the preimage string.
+
+use alloy_primitives::{Keccak256, B256, U256};
+
+use crate::{display_safe, to_hex_string, to_string};
+
+/// Incremental keccak256 state, so `CommitmentBuilder` can use a hasher
+/// other than alloy's, e.g. a host-provided one.
+pub trait Keccak {
+    /// Absorbs `bytes`.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the hash of everything absorbed.
+    fn finalize(self) -> B256;
+}
+
+impl Keccak for Keccak256 {
+    fn update(&mut self, bytes: &[u8]) {
+        Keccak256::update(self, bytes);
+    }
+
+    fn finalize(self) -> B256 {
+        Keccak256::finalize(self)
+    }
+}
+
+/// Hashes a commitment preimage such as "42:0x2a:salt" piece by piece.
+///
+/// Each `push_*` formats its value as the crate's converters do and feeds
+/// the UTF-8 bytes straight into the hasher, so `finish` equals
+/// `keccak256` of the concatenated string. Only an optional preview, capped
+/// by `preimage_preview`, is kept for logging.
+#[derive(Debug, Clone)]
+pub struct CommitmentBuilder<K: Keccak = Keccak256> {
+    hasher: K,
+    preview: String,
+    preview_limit: usize,
+    chars: usize,
+}
+
+impl CommitmentBuilder {
+    /// Creates a builder hashing with alloy's keccak256.
+    pub fn new() -> Self {
+        Self::with_hasher(Keccak256::new())
+    }
+}
+
+impl Default for CommitmentBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Keccak> CommitmentBuilder<K> {
+    /// Creates a builder feeding `hasher`.
+    pub fn with_hasher(hasher: K) -> Self {
+        Self { hasher, preview: String::new(), preview_limit: 0, chars: 0 }
+    }
+
+    /// Records the first `max_len` characters of the preimage for
+    /// `preview`.
+    ///
+    /// Panics if anything has been pushed already, since the preview must
+    /// start at the first character of the preimage.
+    pub fn preimage_preview(mut self, max_len: usize) -> Self {
+        assert!(self.chars == 0, "preimage_preview must be set before pushing");
+        self.preview_limit = max_len;
+        self
+    }
+
+    /// Appends `value` in decimal, as by `to_string`.
+    pub fn push_dec(self, value: U256) -> Self {
+        self.push_str(&to_string(value))
+    }
+
+    /// Appends `value` in hex, as by `to_hex_string`.
+    pub fn push_hex(self, value: U256) -> Self {
+        self.push_str(&to_hex_string(value))
+    }
+
+    /// Appends a separator character.
+    pub fn push_sep(self, separator: char) -> Self {
+        self.push_str(separator.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Appends `s` verbatim.
+    pub fn push_str(mut self, s: &str) -> Self {
+        self.hasher.update(s.as_bytes());
+        let room = self.preview_limit.saturating_sub(self.chars);
+        if room > 0 {
+            self.preview.extend(s.chars().take(room));
+        }
+        self.chars += s.chars().count();
+        self
+    }
+
+    /// Returns the recorded start of the preimage, escaped with
+    /// `display_safe` and followed by "… (+N more)" if it was cut.
+    pub fn preview(&self) -> String {
+        let recorded = self.preview.chars().count();
+        let mut result = display_safe(&self.preview, recorded);
+        if self.chars > recorded {
+            result.push_str("… (+");
+            result.push_str(&to_string(U256::from(self.chars - recorded)));
+            result.push_str(" more)");
+        }
+        result
+    }
+
+    /// Returns keccak256 of the preimage.
+    pub fn finish(self) -> B256 {
+        self.hasher.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::keccak256;
+
+    fn builder() -> CommitmentBuilder {
+        CommitmentBuilder::new()
+            .push_dec(U256::from(42))
+            .push_sep(':')
+            .push_hex(U256::from(0xbeef))
+            .push_sep(':')
+            .push_str("salt-✓")
+    }
+
+    #[test]
+    fn test_hash_matches_concatenated_string() {
+        assert_eq!(builder().finish(), keccak256("42:0xbeef:salt-✓"));
+    }
+
+    #[test]
+    fn test_preview_truncation() {
+        let cut = CommitmentBuilder::new()
+            .preimage_preview(10)
+            .push_dec(U256::from(42))
+            .push_sep(':')
+            .push_hex(U256::from(0xbeef))
+            .push_str("\nsalt");
+        assert_eq!(cut.preview(), "42:0xbeef\\x0A… (+4 more)");
+        assert_eq!(cut.finish(), keccak256("42:0xbeef\nsalt"));
+
+        let full = CommitmentBuilder::new().preimage_preview(64).push_str("short");
+        assert_eq!(full.preview(), "short");
+        // Without a preview limit nothing is recorded
+        assert_eq!(builder().preview(), "… (+16 more)");
+    }
+
+    #[test]
+    #[should_panic(expected = "preimage_preview must be set before pushing")]
+    fn test_preview_after_push_panics() {
+        let _ = CommitmentBuilder::new().push_str("42:").preimage_preview(10);
+    }
+
+    #[test]
+    fn test_empty_builder() {
+        assert_eq!(CommitmentBuilder::new().finish(), keccak256(b""));
+        assert_eq!(CommitmentBuilder::new().preview(), "");
+    }
+
+    /// Hasher that records what it is fed, to check the pluggable path
+    #[derive(Default)]
+    struct Recording(Vec<u8>);
+
+    impl Keccak for Recording {
+        fn update(&mut self, bytes: &[u8]) {
+            self.0.extend_from_slice(bytes);
+        }
+
+        fn finalize(self) -> B256 {
+            keccak256(&self.0)
+        }
+    }
+
+    #[test]
+    fn test_custom_hasher() {
+        let custom = CommitmentBuilder::with_hasher(Recording::default()).push_dec(U256::from(7)).push_sep('|');
+        assert_eq!(custom.hasher.0, b"7|");
+        assert_eq!(custom.finish(), keccak256(b"7|"));
+    }
+}
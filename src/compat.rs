@@ -0,0 +1,336 @@
+//! Verification against OpenZeppelin's `Strings.sol`.
+//!
+//! The `reference` functions below are line-by-line ports of the Solidity
+//! algorithms, kept independent of the crate's own converters so that a
+//! migration can check both sides agree. `compare_to_solidity` runs a value
+//! through each pair and reports where the outputs differ; some formatters
+//! differ on purpose, and those are listed in `INTENTIONAL_DIFFERENCES`.
+
+use core::fmt;
+
+use alloy_primitives::{keccak256, Address, I256, U256};
+
+use crate::{
+    address_to_checksum_hex_string, address_to_hex_string, to_hex_string, to_hex_string_with_length, to_string,
+    to_string_signed, CanonicalHex, U256Fmt,
+};
+
+/// Formatters whose output deliberately differs from `Strings.toHexString`,
+/// with the reason
+pub const INTENTIONAL_DIFFERENCES: &[(&str, &str)] = &[
+    (
+        "U256Fmt::hex",
+        "minimal nibbles by default: \"0x0\" for zero and \"0xf\" for 15, where OZ pads to whole bytes \
+         (\"0x00\", \"0x0f\"); the `oz-compat` feature switches to whole bytes",
+    ),
+    (
+        "CanonicalHex::from_u256",
+        "canonical map keys use minimal nibbles, so zero is \"0x0\" rather than OZ's \"0x00\"",
+    ),
+];
+
+/// Rendering of a reverted `toHexString(uint256, uint256)` call
+const HEX_LENGTH_REVERT: &str = "revert StringsInsufficientHexLength";
+
+/// One formatter whose output differs from the Solidity reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Crate function, e.g. "to_hex_string"
+    pub function: &'static str,
+    /// Solidity function it is compared with, e.g. "toHexString(uint256)"
+    pub solidity_function: &'static str,
+    /// Output of this crate
+    pub ours: String,
+    /// Output of the Solidity algorithm
+    pub solidity: String,
+    /// Whether the difference is listed in `INTENTIONAL_DIFFERENCES`
+    pub intentional: bool,
+}
+
+/// Result of `compare_to_solidity` for one value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatReport {
+    /// Value that was compared
+    pub value: U256,
+    /// Formatters whose output differed, in comparison order
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl CompatReport {
+    /// Returns true if every difference is an intentional one.
+    pub fn is_compatible(&self) -> bool {
+        self.unexpected().next().is_none()
+    }
+
+    /// Returns the differences not listed in `INTENTIONAL_DIFFERENCES`.
+    pub fn unexpected(&self) -> impl Iterator<Item = &Mismatch> {
+        self.mismatches.iter().filter(|mismatch| !mismatch.intentional)
+    }
+}
+
+impl fmt::Display for CompatReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", to_hex_string(self.value))?;
+        if self.mismatches.is_empty() {
+            return write!(f, "matches Solidity");
+        }
+        write!(f, "{} difference(s)", self.mismatches.len())?;
+        for mismatch in &self.mismatches {
+            let kind = if mismatch.intentional { "intentional" } else { "UNEXPECTED" };
+            write!(
+                f,
+                "\n  {} vs {} ({})\n    - solidity: {}\n    + crate:    {}",
+                mismatch.function, mismatch.solidity_function, kind, mismatch.solidity, mismatch.ours
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats `value` with each crate converter and its `Strings.sol`
+/// counterpart and reports the outputs that differ.
+///
+/// `value` is also read as the raw bits of an `int256` for
+/// `toStringSigned`, and truncated to its low 160 bits for the address
+/// functions. Fixed-length hex is compared at 32 bytes and at one byte too
+/// short, where both sides must fail.
+pub fn compare_to_solidity(value: U256) -> CompatReport {
+    let signed = I256::from_raw(value);
+    let addr = Address::from_slice(&value.to_be_bytes::<32>()[12..]);
+    let short = reference::log256(value);
+    let fixed = |length: usize| {
+        to_hex_string_with_length(value, length).unwrap_or_else(|_| HEX_LENGTH_REVERT.to_string())
+    };
+    let reference_fixed = |length: usize| {
+        reference::to_hex_string_fixed(value, length).unwrap_or_else(|| HEX_LENGTH_REVERT.to_string())
+    };
+
+    let pairs = [
+        ("to_string", "toString(uint256)", to_string(value), reference::to_string(value)),
+        ("to_string_signed", "toStringSigned(int256)", to_string_signed(signed), reference::to_string_signed(signed)),
+        ("to_hex_string", "toHexString(uint256)", to_hex_string(value), reference::to_hex_string(value)),
+        ("to_hex_string_with_length", "toHexString(uint256,uint256)", fixed(32), reference_fixed(32)),
+        ("to_hex_string_with_length", "toHexString(uint256,uint256)", fixed(short), reference_fixed(short)),
+        ("address_to_hex_string", "toHexString(address)", address_to_hex_string(addr), reference::address_to_hex_string(addr)),
+        (
+            "address_to_checksum_hex_string",
+            "toChecksumHexString(address)",
+            address_to_checksum_hex_string(addr),
+            reference::address_to_checksum_hex_string(addr),
+        ),
+        ("U256Fmt::hex", "toHexString(uint256)", U256Fmt::of(value).hex().to_string(), reference::to_hex_string(value)),
+        (
+            "CanonicalHex::from_u256",
+            "toHexString(uint256)",
+            CanonicalHex::from_u256(value).as_str().to_string(),
+            reference::to_hex_string(value),
+        ),
+    ];
+
+    let mismatches = pairs
+        .into_iter()
+        .filter(|(_, _, ours, solidity)| ours != solidity)
+        .map(|(function, solidity_function, ours, solidity)| Mismatch {
+            function,
+            solidity_function,
+            ours,
+            solidity,
+            intentional: INTENTIONAL_DIFFERENCES.iter().any(|&(name, _)| name == function),
+        })
+        .collect();
+    CompatReport { value, mismatches }
+}
+
+/// Panics with a readable diff if `value` formats differently from
+/// `Strings.sol` in any way not listed in `INTENTIONAL_DIFFERENCES`.
+///
+/// Intended for property tests of code migrated from Solidity.
+pub fn assert_oz_compatible(value: U256) {
+    let report = compare_to_solidity(value);
+    assert!(report.is_compatible(), "output differs from OpenZeppelin Strings.sol\n{}", report);
+}
+
+/// Ports of the `Strings.sol` and `Math.sol` algorithms
+mod reference {
+    use super::*;
+
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    /// `Math.log10`: repeated division by decreasing powers of ten
+    fn log10(mut value: U256) -> usize {
+        let mut result = 0;
+        for exp in [64, 32, 16, 8, 4, 2, 1] {
+            let power = U256::from(10).pow(U256::from(exp));
+            if value >= power {
+                value /= power;
+                result += exp;
+            }
+        }
+        result
+    }
+
+    /// `Math.log256`: floor of the base-256 logarithm, 0 for 0
+    pub(super) fn log256(mut value: U256) -> usize {
+        let mut result = 0;
+        for bits in [128usize, 64, 32, 16, 8] {
+            if !(value >> bits).is_zero() {
+                value >>= bits;
+                result += bits / 8;
+            }
+        }
+        result
+    }
+
+    /// `toString(uint256)`
+    pub(super) fn to_string(mut value: U256) -> String {
+        let length = log10(value) + 1;
+        let mut buffer = vec![0u8; length];
+        for i in (0..length).rev() {
+            buffer[i] = HEX_DIGITS[(value % U256::from(10)).to::<usize>()];
+            value /= U256::from(10);
+        }
+        String::from_utf8(buffer).expect("digits are ASCII")
+    }
+
+    /// `toStringSigned(int256)`
+    pub(super) fn to_string_signed(value: I256) -> String {
+        let sign = if value.is_negative() { "-" } else { "" };
+        format!("{}{}", sign, to_string(value.unsigned_abs()))
+    }
+
+    /// `toHexString(uint256)`
+    pub(super) fn to_hex_string(value: U256) -> String {
+        to_hex_string_fixed(value, log256(value) + 1).expect("length is always sufficient")
+    }
+
+    /// `toHexString(uint256, uint256)`, with `None` for the revert
+    pub(super) fn to_hex_string_fixed(value: U256, length: usize) -> Option<String> {
+        let mut local_value = value;
+        let mut buffer = vec![0u8; 2 * length + 2];
+        buffer[0] = b'0';
+        buffer[1] = b'x';
+        for i in (2..2 * length + 2).rev() {
+            buffer[i] = HEX_DIGITS[(local_value & U256::from(0xf)).to::<usize>()];
+            local_value >>= 4;
+        }
+        if !local_value.is_zero() {
+            return None;
+        }
+        Some(String::from_utf8(buffer).expect("digits are ASCII"))
+    }
+
+    /// `toHexString(address)`
+    pub(super) fn address_to_hex_string(addr: Address) -> String {
+        to_hex_string_fixed(U256::from_be_slice(addr.as_slice()), 20).expect("address fits in 20 bytes")
+    }
+
+    /// `toChecksumHexString(address)`
+    pub(super) fn address_to_checksum_hex_string(addr: Address) -> String {
+        let mut buffer = address_to_hex_string(addr).into_bytes();
+        let mut hash_value = U256::from_be_slice(keccak256(&buffer[2..]).as_slice()) >> 96;
+        for i in (2..42).rev() {
+            if (hash_value & U256::from(0xf)) > U256::from(7) && buffer[i] > 96 {
+                buffer[i] ^= 0x20;
+            }
+            hash_value >>= 4;
+        }
+        String::from_utf8(buffer).expect("digits are ASCII")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// splitmix64, so the sample is reproducible without a rand dependency
+    fn next(state: &mut u64) -> u64 {
+        *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Random value with a random bit length, so small values are common
+    fn sample(state: &mut u64) -> U256 {
+        let limbs = [next(state), next(state), next(state), next(state)];
+        U256::from_limbs(limbs) >> (next(state) % 256) as usize
+    }
+
+    #[test]
+    fn test_random_sample_is_compatible() {
+        let mut state = 0x5eed;
+        for _ in 0..1_000 {
+            assert_oz_compatible(sample(&mut state));
+        }
+    }
+
+    #[test]
+    fn test_edge_values_are_compatible() {
+        let min_int = U256::from(1) << 255;
+        for value in [U256::ZERO, U256::from(1), U256::from(15), U256::from(255), U256::from(256), min_int, U256::MAX] {
+            assert_oz_compatible(value);
+        }
+    }
+
+    #[test]
+    fn test_known_intentional_differences() {
+        // With `oz-compat`, U256Fmt pads hex to whole bytes and matches
+        #[cfg(not(feature = "oz-compat"))]
+        let expected = ["U256Fmt::hex", "CanonicalHex::from_u256"];
+        #[cfg(feature = "oz-compat")]
+        let expected = ["CanonicalHex::from_u256"];
+
+        let report = compare_to_solidity(U256::ZERO);
+        let functions: Vec<&str> = report.mismatches.iter().map(|mismatch| mismatch.function).collect();
+        assert_eq!(functions, expected);
+        assert_eq!(report.mismatches[0].ours, "0x0");
+        assert_eq!(report.mismatches[0].solidity, "0x00");
+        assert!(report.mismatches.iter().all(|mismatch| mismatch.intentional));
+
+        // Whole bytes need no padding, so both agree with Solidity
+        assert!(compare_to_solidity(U256::from(0xab)).mismatches.is_empty());
+        assert_eq!(compare_to_solidity(U256::from(0xabc)).mismatches.len(), expected.len());
+    }
+
+    #[test]
+    fn test_report_display() {
+        assert_eq!(compare_to_solidity(U256::from(0xab)).to_string(), "0xab: matches Solidity");
+        #[cfg(not(feature = "oz-compat"))]
+        assert_eq!(
+            compare_to_solidity(U256::from(0xf)).to_string(),
+            "0x0f: 2 difference(s)\n  \
+             U256Fmt::hex vs toHexString(uint256) (intentional)\n    \
+             - solidity: 0x0f\n    \
+             + crate:    0xf\n  \
+             CanonicalHex::from_u256 vs toHexString(uint256) (intentional)\n    \
+             - solidity: 0x0f\n    \
+             + crate:    0xf"
+        );
+    }
+
+    #[test]
+    fn test_unexpected_difference_is_incompatible() {
+        let report = CompatReport {
+            value: U256::from(1),
+            mismatches: vec![Mismatch {
+                function: "to_string",
+                solidity_function: "toString(uint256)",
+                ours: "01".to_string(),
+                solidity: "1".to_string(),
+                intentional: false,
+            }],
+        };
+        assert!(!report.is_compatible());
+        assert!(report.to_string().contains("to_string vs toString(uint256) (UNEXPECTED)"));
+    }
+
+    #[test]
+    fn test_reference_matches_solidity_vectors() {
+        assert_eq!(reference::to_string(U256::ZERO), "0");
+        assert_eq!(reference::to_string(U256::from(10).pow(U256::from(64))).len(), 65);
+        assert_eq!(reference::to_hex_string(U256::ZERO), "0x00");
+        assert_eq!(reference::to_hex_string_fixed(U256::from(0x1234), 1), None);
+        assert_eq!(reference::to_hex_string_fixed(U256::ZERO, 0).as_deref(), Some("0x"));
+    }
+}
@@ -0,0 +1,119 @@
+//! Content-addressed filenames and fanned-out storage paths.
+
+use alloy_primitives::B256;
+
+use crate::bytes_to_hex_string;
+
+/// Hash bytes used in a filename, i.e. 16 hex characters
+const FILENAME_HASH_BYTES: usize = 8;
+
+/// Longest accepted file extension
+const MAX_EXTENSION_LEN: usize = 8;
+
+/// Error types for content-addressed filenames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilenameError {
+    /// Extension was empty
+    EmptyExtension,
+    /// Extension is longer than 8 characters
+    ExtensionTooLong(usize),
+    /// Extension character at `index` is not lowercase ASCII alphanumeric
+    InvalidExtensionChar { index: usize, found: char },
+    /// More directory levels requested than the hash has bytes
+    TooManyLevels(usize),
+}
+
+/// Names a file after its content hash, e.g. "abcdef0123456789.json".
+///
+/// The name is the first 16 hex characters of `hash`. The extension must be
+/// 1 to 8 lowercase ASCII letters or digits.
+pub fn content_filename(hash: B256, extension: &str) -> Result<String, FilenameError> {
+    validate_extension(extension)?;
+    let name = bytes_to_hex_string(&hash.as_slice()[..FILENAME_HASH_BYTES]);
+    Ok(format!("{}.{}", &name[2..], extension))
+}
+
+/// Places `content_filename` under `prefix_levels` directories, each named
+/// after the next byte of the hash, e.g. "ab/cd/abcdef0123456789.json" for
+/// two levels. At most 32 levels are possible.
+pub fn content_path(prefix_levels: usize, hash: B256, extension: &str) -> Result<String, FilenameError> {
+    if prefix_levels > hash.as_slice().len() {
+        return Err(FilenameError::TooManyLevels(prefix_levels));
+    }
+    let filename = content_filename(hash, extension)?;
+    let mut path = String::with_capacity(3 * prefix_levels + filename.len());
+    for &byte in &hash.as_slice()[..prefix_levels] {
+        path.push_str(&bytes_to_hex_string(&[byte])[2..]);
+        path.push('/');
+    }
+    path.push_str(&filename);
+    Ok(path)
+}
+
+fn validate_extension(extension: &str) -> Result<(), FilenameError> {
+    if extension.is_empty() {
+        return Err(FilenameError::EmptyExtension);
+    }
+    if let Some((index, found)) = extension
+        .char_indices()
+        .find(|(_, c)| !(c.is_ascii_lowercase() || c.is_ascii_digit()))
+    {
+        return Err(FilenameError::InvalidExtensionChar { index, found });
+    }
+    if extension.len() > MAX_EXTENSION_LEN {
+        return Err(FilenameError::ExtensionTooLong(extension.len()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash() -> B256 {
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = 0xab + i as u8;
+        }
+        B256::from(bytes)
+    }
+
+    #[test]
+    fn test_level_zero() {
+        assert_eq!(content_filename(hash(), "json").unwrap(), "abacadaeafb0b1b2.json");
+        assert_eq!(content_path(0, hash(), "json").unwrap(), "abacadaeafb0b1b2.json");
+    }
+
+    #[test]
+    fn test_fan_out_levels() {
+        assert_eq!(content_path(2, hash(), "json").unwrap(), "ab/ac/abacadaeafb0b1b2.json");
+        assert_eq!(content_path(1, hash(), "png").unwrap(), "ab/abacadaeafb0b1b2.png");
+        let deepest = content_path(32, hash(), "svg").unwrap();
+        assert_eq!(deepest.matches('/').count(), 32);
+        assert!(deepest.ends_with("ca/abacadaeafb0b1b2.svg"));
+        assert_eq!(content_path(33, hash(), "svg"), Err(FilenameError::TooManyLevels(33)));
+    }
+
+    #[test]
+    fn test_extension_validation() {
+        assert_eq!(content_filename(hash(), ""), Err(FilenameError::EmptyExtension));
+        assert_eq!(
+            content_filename(hash(), "JSON"),
+            Err(FilenameError::InvalidExtensionChar { index: 0, found: 'J' })
+        );
+        assert_eq!(
+            content_filename(hash(), "tar.gz"),
+            Err(FilenameError::InvalidExtensionChar { index: 3, found: '.' })
+        );
+        assert_eq!(content_filename(hash(), "verylongx"), Err(FilenameError::ExtensionTooLong(9)));
+        assert!(content_filename(hash(), "mp4").is_ok());
+        assert!(content_filename(hash(), "12345678").is_ok());
+    }
+
+    #[test]
+    fn test_deterministic() {
+        assert_eq!(content_path(2, hash(), "json"), content_path(2, hash(), "json"));
+        assert_ne!(content_filename(hash(), "json"), content_filename(B256::ZERO, "json"));
+        assert_eq!(content_filename(B256::ZERO, "bin").unwrap(), "0000000000000000.bin");
+    }
+}
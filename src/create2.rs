@@ -0,0 +1,86 @@
+//! Counterfactual CREATE2 addresses and a preview for users to verify.
+
+use alloy_primitives::{keccak256, Address, B256};
+
+use crate::{address_to_checksum_hex_string, bytes_to_hex_string};
+
+/// Computes the EIP-1014 address
+/// `keccak256(0xff ‖ deployer ‖ salt ‖ init_code_hash)[12..]`.
+pub fn create2_address(deployer: Address, salt: B256, init_code_hash: B256) -> Address {
+    let mut preimage = [0u8; 85];
+    preimage[0] = 0xff;
+    preimage[1..21].copy_from_slice(deployer.as_slice());
+    preimage[21..53].copy_from_slice(salt.as_slice());
+    preimage[53..].copy_from_slice(init_code_hash.as_slice());
+    Address::from_slice(&keccak256(preimage).as_slice()[12..])
+}
+
+/// Summarizes a CREATE2 deployment, one "label: value" line each for the
+/// checksummed predicted address, the deployer, and the salt and init code
+/// hash in full hex.
+pub fn format_create2_preview(deployer: Address, salt: B256, init_code_hash: B256) -> String {
+    format!(
+        "predicted address: {}\ndeployer: {}\nsalt: {}\ninit code hash: {}",
+        address_to_checksum_hex_string(create2_address(deployer, salt, init_code_hash)),
+        address_to_checksum_hex_string(deployer),
+        bytes_to_hex_string(salt.as_slice()),
+        bytes_to_hex_string(init_code_hash.as_slice())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(suffix: &[u8]) -> Address {
+        let mut bytes = [0u8; 20];
+        bytes[20 - suffix.len()..].copy_from_slice(suffix);
+        Address::from(bytes)
+    }
+
+    fn predicted(deployer: Address, salt: B256, init_code: &[u8]) -> String {
+        address_to_checksum_hex_string(create2_address(deployer, salt, keccak256(init_code)))
+    }
+
+    #[test]
+    fn test_eip1014_examples() {
+        let deadbeef = {
+            let mut bytes = [0u8; 20];
+            bytes[..4].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+            Address::from(bytes)
+        };
+        let mut feed = [0u8; 32];
+        feed[12..14].copy_from_slice(&[0xfe, 0xed]);
+        let mut cafebabe = [0u8; 32];
+        cafebabe[28..].copy_from_slice(&[0xca, 0xfe, 0xba, 0xbe]);
+
+        assert_eq!(predicted(Address::ZERO, B256::ZERO, &[0x00]), "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38");
+        assert_eq!(predicted(deadbeef, B256::ZERO, &[0x00]), "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3");
+        assert_eq!(predicted(deadbeef, B256::from(feed), &[0x00]), "0xD04116cDd17beBE565EB2422F2497E06cC1C9833");
+        assert_eq!(
+            predicted(Address::ZERO, B256::ZERO, &[0xde, 0xad, 0xbe, 0xef]),
+            "0x70f2b2914A2a4b783FaEFb75f459A580616Fcb5e"
+        );
+        assert_eq!(
+            predicted(address(&[0xde, 0xad, 0xbe, 0xef]), B256::from(cafebabe), &[0xde, 0xad, 0xbe, 0xef]),
+            "0x60f3f640a8508fC6a86d45DF051962668E1e8AC7"
+        );
+        assert_eq!(predicted(Address::ZERO, B256::ZERO, &[]), "0xE33C0C7F7df4809055C3ebA6c09CFe4BaF1BD9e0");
+    }
+
+    #[test]
+    fn test_format_create2_preview() {
+        let init_code_hash = keccak256([0x00]);
+        assert_eq!(
+            format_create2_preview(Address::ZERO, B256::ZERO, init_code_hash),
+            format!(
+                "predicted address: 0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38\n\
+                 deployer: 0x0000000000000000000000000000000000000000\n\
+                 salt: 0x{}\n\
+                 init code hash: {}",
+                "00".repeat(32),
+                bytes_to_hex_string(init_code_hash.as_slice())
+            )
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Comparison of secret-bearing values without data-dependent early exit.
+
+use core::hint::black_box;
+
+/// Returns true if `a` and `b` are equal, examining every byte regardless
+/// of where the first difference is.
+///
+/// Lengths are compared first and unequal lengths return immediately, so
+/// the length itself is not hidden. Otherwise every byte pair is XORed into
+/// an accumulator behind `black_box`, which stops the optimizer from
+/// turning the loop back into an early-exit `memcmp`. `black_box` is a
+/// best-effort hint rather than a guarantee, but it is the tool `core`
+/// offers.
+///
+/// In a Stylus contract this keeps the ink/gas spent independent of how
+/// long the matching prefix is, so a caller cannot probe a stored value
+/// byte by byte through gas usage. It does not make anything secret:
+/// contract storage and calldata are public, so a stored preimage can be
+/// read directly and a revealed value is visible in the mempool before it
+/// is mined. Use it for defence in depth, e.g. comparing against a value
+/// that is only ever stored as a hash.
+pub fn eq_constant_time(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    black_box(diff_bits(a, b)) == 0
+}
+
+/// `eq_constant_time` over the UTF-8 bytes of two strings.
+pub fn eq_str_constant_time(a: &str, b: &str) -> bool {
+    eq_constant_time(a.as_bytes(), b.as_bytes())
+}
+
+/// ORs together the XOR of every byte pair; zero only if all pairs match
+fn diff_bits(a: &[u8], b: &[u8]) -> u8 {
+    let mut acc = 0u8;
+    for (x, y) in a.iter().zip(b) {
+        acc |= black_box(x ^ y);
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equality() {
+        assert!(eq_constant_time(b"", b""));
+        assert!(eq_constant_time(b"reveal:42:salt", b"reveal:42:salt"));
+        assert!(!eq_constant_time(b"reveal:42:salt", b"reveal:43:salt"));
+        assert!(!eq_constant_time(b"abc", b"abcd"));
+        assert!(!eq_constant_time(b"", b"a"));
+        assert!(eq_str_constant_time("café", "café"));
+        assert!(!eq_str_constant_time("café", "cafe"));
+    }
+
+    #[test]
+    fn test_difference_detected_at_every_position() {
+        let secret: Vec<u8> = (0..64).collect();
+        for i in 0..secret.len() {
+            let mut guess = secret.clone();
+            guess[i] ^= 0x01;
+            assert!(!eq_constant_time(&secret, &guess), "missed difference at {}", i);
+        }
+    }
+
+    #[test]
+    fn test_loop_does_not_exit_early() {
+        // With an early exit only the first difference would be accumulated
+        let a = [0u8; 32];
+        let mut b = [0u8; 32];
+        b[0] = 0x01;
+        b[15] = 0x10;
+        b[31] = 0x80;
+        assert_eq!(diff_bits(&a, &b), 0x91);
+        for (i, bit) in (0..8).map(|bit| (bit * 4, 1u8 << bit)) {
+            let mut c = [0u8; 32];
+            c[0] = 0x01;
+            c[i] |= bit;
+            assert_eq!(diff_bits(&a, &c), 0x01 | bit, "byte {} not examined", i);
+        }
+    }
+}
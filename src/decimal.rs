@@ -0,0 +1,282 @@
+//! Arithmetic directly on decimal digit strings, for counters that can
+//! outgrow `U256`.
+//!
+//! Operands are validated like `parse_decimal`: ASCII digits only, leading
+//! zeros accepted, and error indices relative to the offending operand.
+//! Results are canonical, without leading zeros and "0" for zero.
+
+use core::cmp::Ordering;
+
+use crate::parse::ParseError;
+
+/// Error types for decimal string arithmetic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathError {
+    /// An operand is not a decimal string
+    Parse(ParseError),
+    /// Result would be negative
+    Underflow,
+    /// Divisor is zero
+    DivisionByZero,
+}
+
+impl From<ParseError> for MathError {
+    fn from(error: ParseError) -> Self {
+        MathError::Parse(error)
+    }
+}
+
+/// Compares two decimal strings by numeric value, of any length.
+pub fn compare_decimal_strings(a: &str, b: &str) -> Result<Ordering, ParseError> {
+    let a = significant_digits(a)?;
+    let b = significant_digits(b)?;
+    Ok(a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+}
+
+/// Adds two decimal strings of any length.
+pub fn add_decimal_strings(a: &str, b: &str) -> Result<String, ParseError> {
+    let a = significant_digits(a)?.as_bytes();
+    let b = significant_digits(b)?.as_bytes();
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+
+    // Digits are produced least significant first and reversed at the end
+    let mut digits = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0;
+    for i in 0..long.len() {
+        let x = long[long.len() - 1 - i] - b'0';
+        let y = if i < short.len() { short[short.len() - 1 - i] - b'0' } else { 0 };
+        let sum = x + y + carry;
+        digits.push(b'0' + sum % 10);
+        carry = sum / 10;
+    }
+    if carry > 0 {
+        digits.push(b'0' + carry);
+    }
+    Ok(finish(digits))
+}
+
+/// Subtracts `b` from `a`, failing with `MathError::Underflow` if `b > a`.
+pub fn sub_decimal_strings(a: &str, b: &str) -> Result<String, MathError> {
+    if compare_decimal_strings(a, b)? == Ordering::Less {
+        return Err(MathError::Underflow);
+    }
+    let a = significant_digits(a)?.as_bytes();
+    let b = significant_digits(b)?.as_bytes();
+
+    let mut digits = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for i in 0..a.len() {
+        let x = a[a.len() - 1 - i] - b'0';
+        let y = if i < b.len() { b[b.len() - 1 - i] - b'0' } else { 0 } + borrow;
+        if x >= y {
+            digits.push(b'0' + x - y);
+            borrow = 0;
+        } else {
+            digits.push(b'0' + x + 10 - y);
+            borrow = 1;
+        }
+    }
+    Ok(finish(digits))
+}
+
+/// Multiplies a decimal string of any length by `m`.
+///
+/// Multiplying by zero gives "0" whatever the operand's spelling.
+pub fn mul_decimal_string(a: &str, m: u64) -> Result<String, ParseError> {
+    let a = significant_digits(a)?.as_bytes();
+    let m = u128::from(m);
+
+    // Each step is below 10 * m + m, which fits in u128
+    let mut digits = Vec::with_capacity(a.len() + 20);
+    let mut carry = 0u128;
+    for &digit in a.iter().rev() {
+        let product = u128::from(digit - b'0') * m + carry;
+        digits.push(b'0' + (product % 10) as u8);
+        carry = product / 10;
+    }
+    while carry > 0 {
+        digits.push(b'0' + (carry % 10) as u8);
+        carry /= 10;
+    }
+    Ok(finish(digits))
+}
+
+/// Divides a decimal string of any length by `d`, returning the quotient
+/// and the remainder.
+pub fn div_decimal_string(a: &str, d: u64) -> Result<(String, u64), MathError> {
+    if d == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let a = significant_digits(a)?.as_bytes();
+    let d = u128::from(d);
+
+    // Long division, most significant digit first; the remainder stays below `d`
+    let mut quotient = Vec::with_capacity(a.len());
+    let mut remainder = 0u128;
+    for &digit in a {
+        let current = remainder * 10 + u128::from(digit - b'0');
+        quotient.push(b'0' + (current / d) as u8);
+        remainder = current % d;
+    }
+    quotient.reverse();
+    Ok((finish(quotient), remainder as u64))
+}
+
+/// Validates `s` and returns it without leading zeros, "" for zero
+fn significant_digits(s: &str) -> Result<&str, ParseError> {
+    if s.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    if let Some((index, found)) = s.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(ParseError::InvalidDigit { index, found });
+    }
+    Ok(s.trim_start_matches('0'))
+}
+
+/// Turns least-significant-first digits into a canonical string
+fn finish(mut digits: Vec<u8>) -> String {
+    while digits.last() == Some(&b'0') {
+        digits.pop();
+    }
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.reverse();
+    String::from_utf8(digits).expect("ASCII digits")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_string;
+    use alloy_primitives::U256;
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(compare_decimal_strings("10", "9"), Ok(Ordering::Greater));
+        assert_eq!(compare_decimal_strings("0009", "9"), Ok(Ordering::Equal));
+        assert_eq!(compare_decimal_strings("123", "124"), Ok(Ordering::Less));
+        assert_eq!(compare_decimal_strings("0", "000"), Ok(Ordering::Equal));
+        assert_eq!(compare_decimal_strings("1", "1x"), Err(ParseError::InvalidDigit { index: 1, found: 'x' }));
+    }
+
+    #[test]
+    fn test_add_carries_across_full_length() {
+        assert_eq!(add_decimal_strings("999999999", "1").unwrap(), "1000000000");
+        assert_eq!(add_decimal_strings("1", "999999999").unwrap(), "1000000000");
+        assert_eq!(add_decimal_strings("555", "445").unwrap(), "1000");
+        assert_eq!(add_decimal_strings("12", "34").unwrap(), "46");
+    }
+
+    #[test]
+    fn test_hundred_digit_operands() {
+        let nines = "9".repeat(100);
+        let expected = format!("1{}8", "9".repeat(99));
+        assert_eq!(add_decimal_strings(&nines, &nines).unwrap(), expected);
+        assert_eq!(sub_decimal_strings(&expected, &nines).unwrap(), nines);
+        let one_then_zeros = format!("1{}", "0".repeat(100));
+        assert_eq!(sub_decimal_strings(&one_then_zeros, "1").unwrap(), nines);
+        assert_eq!(add_decimal_strings(&nines, "1").unwrap(), one_then_zeros);
+    }
+
+    #[test]
+    fn test_zero() {
+        assert_eq!(add_decimal_strings("0", "0").unwrap(), "0");
+        assert_eq!(add_decimal_strings("000", "0042").unwrap(), "42");
+        assert_eq!(sub_decimal_strings("42", "0").unwrap(), "42");
+        assert_eq!(sub_decimal_strings("0", "0").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_self_subtraction_is_zero() {
+        for a in ["0", "7", "1000", &"123456789".repeat(12)] {
+            assert_eq!(sub_decimal_strings(a, a).unwrap(), "0");
+        }
+        assert_eq!(sub_decimal_strings("0100", "100").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_underflow_and_invalid_operands() {
+        assert_eq!(sub_decimal_strings("9", "10"), Err(MathError::Underflow));
+        assert_eq!(sub_decimal_strings("0", "1"), Err(MathError::Underflow));
+        assert_eq!(sub_decimal_strings("", "1"), Err(MathError::Parse(ParseError::Empty)));
+        assert_eq!(
+            sub_decimal_strings("10", "-1"),
+            Err(MathError::Parse(ParseError::InvalidDigit { index: 0, found: '-' }))
+        );
+        assert_eq!(add_decimal_strings("1 ", "1"), Err(ParseError::InvalidDigit { index: 1, found: ' ' }));
+        assert_eq!(add_decimal_strings("1", ""), Err(ParseError::Empty));
+    }
+
+    #[test]
+    fn test_mul_carries() {
+        assert_eq!(mul_decimal_string("999", 9).unwrap(), "8991");
+        assert_eq!(mul_decimal_string("12345", 10).unwrap(), "123450");
+        assert_eq!(mul_decimal_string("5", 1).unwrap(), "5");
+        assert_eq!(
+            mul_decimal_string("99", u64::MAX).unwrap(),
+            (99u128 * u128::from(u64::MAX)).to_string()
+        );
+        assert_eq!(
+            mul_decimal_string(&"9".repeat(30), u64::MAX).unwrap(),
+            sub_decimal_strings(&format!("{}{}", u64::MAX, "0".repeat(30)), &u64::MAX.to_string()).unwrap()
+        );
+        assert_eq!(mul_decimal_string("1x", 2), Err(ParseError::InvalidDigit { index: 1, found: 'x' }));
+    }
+
+    #[test]
+    fn test_div_with_remainder() {
+        assert_eq!(div_decimal_string("100", 7).unwrap(), ("14".to_string(), 2));
+        assert_eq!(div_decimal_string("6", 7).unwrap(), ("0".to_string(), 6));
+        assert_eq!(div_decimal_string("18446744073709551615", u64::MAX).unwrap(), ("1".to_string(), 0));
+        assert_eq!(div_decimal_string("1", 0), Err(MathError::DivisionByZero));
+        assert_eq!(div_decimal_string("", 3), Err(MathError::Parse(ParseError::Empty)));
+    }
+
+    #[test]
+    fn test_div_then_mul_restores_hundred_digits() {
+        let a = "1234567890".repeat(10);
+        for d in [1, 3, 7, 1_000_000_007, u64::MAX - 58] {
+            let (q, r) = div_decimal_string(&a, d).unwrap();
+            assert!(r < d);
+            let restored = add_decimal_strings(&mul_decimal_string(&q, d).unwrap(), &r.to_string()).unwrap();
+            assert_eq!(restored, a, "divisor {}", d);
+        }
+        assert_eq!(mul_decimal_string(&a, 1000).unwrap().len(), 103);
+    }
+
+    #[test]
+    fn test_mul_div_canonical_zero() {
+        assert_eq!(mul_decimal_string("12345", 0).unwrap(), "0");
+        assert_eq!(mul_decimal_string("000", 5).unwrap(), "0");
+        assert_eq!(mul_decimal_string("0", 0).unwrap(), "0");
+        assert_eq!(div_decimal_string("0000", 9).unwrap(), ("0".to_string(), 0));
+        assert_eq!(div_decimal_string("0042", 42).unwrap(), ("1".to_string(), 0));
+    }
+
+    #[test]
+    fn test_consistent_with_u256() {
+        let values = [
+            U256::ZERO,
+            U256::from(1),
+            U256::from(987_654_321u64),
+            U256::from(u64::MAX),
+            U256::MAX >> 1,
+            U256::MAX - U256::from(12345),
+        ];
+        for &a in &values {
+            for &b in &values {
+                if let Some(sum) = a.checked_add(b) {
+                    assert_eq!(add_decimal_strings(&to_string(a), &to_string(b)).unwrap(), to_string(sum));
+                }
+                match a.checked_sub(b) {
+                    Some(diff) => assert_eq!(sub_decimal_strings(&to_string(a), &to_string(b)).unwrap(), to_string(diff)),
+                    None => assert_eq!(sub_decimal_strings(&to_string(a), &to_string(b)), Err(MathError::Underflow)),
+                }
+            }
+        }
+        // Beyond U256
+        let max = to_string(U256::MAX);
+        assert_eq!(add_decimal_strings(&max, "1").unwrap(), "115792089237316195423570985008687907853269984665640564039457584007913129639936");
+    }
+}
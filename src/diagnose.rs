@@ -0,0 +1,148 @@
+//! Precise diagnostics for mistyped hex input such as pasted addresses.
+//!
+//! Unlike the parsers, which stop at the first error, `suggest_hex_fix`
+//! classifies what is wrong in terms a user can act on. It never guesses a
+//! correction.
+
+use alloy_primitives::Address;
+
+use crate::address_to_checksum_hex_string;
+
+/// Number of hex digits in an address
+const ADDRESS_DIGITS: usize = 40;
+
+/// Outcome of `suggest_hex_fix`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexDiagnosis {
+    /// Input is well-formed
+    Ok,
+    /// Input does not start with "0x" or "0X"
+    MissingPrefix,
+    /// Character at `index` (a byte offset into the input) is not a hex digit
+    InvalidChar { index: usize, found: char },
+    /// Input has the wrong number of hex digits
+    BadLength { expected: usize, found: usize },
+    /// Mixed-case address whose capitalization is not its EIP-55 checksum
+    ChecksumMismatch,
+}
+
+impl core::fmt::Display for HexDiagnosis {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            HexDiagnosis::Ok => write!(f, "valid hex"),
+            HexDiagnosis::MissingPrefix => write!(f, "missing 0x prefix"),
+            HexDiagnosis::InvalidChar { index, found } => {
+                write!(f, "character {:?} at position {} is not valid hex", found, index)
+            }
+            HexDiagnosis::BadLength { expected, found } if found < expected => {
+                write!(f, "expected {} hex digits, found {} ({} too few)", expected, found, expected - found)
+            }
+            HexDiagnosis::BadLength { expected, found } => {
+                write!(f, "expected {} hex digits, found {} ({} too many)", expected, found, found - expected)
+            }
+            HexDiagnosis::ChecksumMismatch => write!(f, "mixed-case address fails its EIP-55 checksum"),
+        }
+    }
+}
+
+/// Diagnoses `input` as "0x" followed by `expected_len` hex digits.
+///
+/// When several problems are present, the first in this order is reported:
+/// missing prefix, the first invalid character, wrong length, and, for
+/// 40-digit inputs with both upper- and lowercase letters, a failed EIP-55
+/// checksum. All-lowercase and all-uppercase addresses carry no checksum.
+pub fn suggest_hex_fix(input: &str, expected_len: usize) -> HexDiagnosis {
+    let Some(digits) = input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")) else {
+        return HexDiagnosis::MissingPrefix;
+    };
+    if let Some((offset, found)) = digits.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return HexDiagnosis::InvalidChar { index: offset + 2, found };
+    }
+    if digits.len() != expected_len {
+        return HexDiagnosis::BadLength { expected: expected_len, found: digits.len() };
+    }
+
+    let mixed_case = digits.bytes().any(|b| b.is_ascii_uppercase()) && digits.bytes().any(|b| b.is_ascii_lowercase());
+    if expected_len == ADDRESS_DIGITS && mixed_case {
+        let bytes: Vec<u8> = digits
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| (hex_value(pair[0]) << 4) | hex_value(pair[1]))
+            .collect();
+        let checksummed = address_to_checksum_hex_string(Address::from_slice(&bytes));
+        if checksummed[2..] != *digits {
+            return HexDiagnosis::ChecksumMismatch;
+        }
+    }
+    HexDiagnosis::Ok
+}
+
+fn hex_value(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// EIP-55 test vector
+    const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    #[test]
+    fn test_clean_input() {
+        assert_eq!(suggest_hex_fix(CHECKSUMMED, 40), HexDiagnosis::Ok);
+        assert_eq!(suggest_hex_fix(&CHECKSUMMED.to_lowercase(), 40), HexDiagnosis::Ok);
+        assert_eq!(suggest_hex_fix(&format!("0X{}", CHECKSUMMED[2..].to_uppercase()), 40), HexDiagnosis::Ok);
+        assert_eq!(suggest_hex_fix("0xAbCd", 4), HexDiagnosis::Ok);
+    }
+
+    #[test]
+    fn test_missing_prefix() {
+        assert_eq!(suggest_hex_fix(&CHECKSUMMED[2..], 40), HexDiagnosis::MissingPrefix);
+        assert_eq!(HexDiagnosis::MissingPrefix.to_string(), "missing 0x prefix");
+    }
+
+    #[test]
+    fn test_invalid_char() {
+        let diagnosis = suggest_hex_fix("0x5aAebg053F3E94C9b9A09f33669435E7Ef1BeAed", 40);
+        assert_eq!(diagnosis, HexDiagnosis::InvalidChar { index: 7, found: 'g' });
+        assert_eq!(diagnosis.to_string(), "character 'g' at position 7 is not valid hex");
+        assert_eq!(suggest_hex_fix("0x12é4", 4), HexDiagnosis::InvalidChar { index: 4, found: 'é' });
+    }
+
+    #[test]
+    fn test_bad_length() {
+        let diagnosis = suggest_hex_fix(&CHECKSUMMED[..41], 40);
+        assert_eq!(diagnosis, HexDiagnosis::BadLength { expected: 40, found: 39 });
+        assert_eq!(diagnosis.to_string(), "expected 40 hex digits, found 39 (1 too few)");
+        let long = format!("{}00", CHECKSUMMED);
+        assert_eq!(suggest_hex_fix(&long, 40).to_string(), "expected 40 hex digits, found 42 (2 too many)");
+    }
+
+    #[test]
+    fn test_checksum_mismatch() {
+        let wrong_case = CHECKSUMMED.replace("5aA", "5AA");
+        assert_eq!(suggest_hex_fix(&wrong_case, 40), HexDiagnosis::ChecksumMismatch);
+        assert_eq!(
+            HexDiagnosis::ChecksumMismatch.to_string(),
+            "mixed-case address fails its EIP-55 checksum"
+        );
+    }
+
+    #[test]
+    fn test_priority_of_multiple_problems() {
+        // Invalid character beats wrong length and wrong checksum
+        assert_eq!(suggest_hex_fix("0xZZ", 40), HexDiagnosis::InvalidChar { index: 2, found: 'Z' });
+        // Missing prefix beats everything
+        assert_eq!(suggest_hex_fix("zz", 40), HexDiagnosis::MissingPrefix);
+        // Wrong length beats checksum
+        assert_eq!(
+            suggest_hex_fix("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAe", 40),
+            HexDiagnosis::BadLength { expected: 40, found: 39 }
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Line diffs of two multi-line strings for readable comparisons.
+
+/// Default number of output lines kept by `simple_diff`
+pub const DEFAULT_MAX_DIFF_LINES: usize = 100;
+
+/// Marker printed after a last line that has no trailing newline
+const NO_NEWLINE_MARKER: &str = "\\ No newline at end of file";
+
+/// Diffs `a` against `b` line by line, keeping at most
+/// `DEFAULT_MAX_DIFF_LINES` output lines. See `simple_diff_capped`.
+pub fn simple_diff(a: &str, b: &str) -> String {
+    simple_diff_capped(a, b, DEFAULT_MAX_DIFF_LINES)
+}
+
+/// Diffs `a` against `b` line by line in a unified-diff-like format:
+/// unchanged lines start with a space, removed lines with `-` and added
+/// lines with `+`. Identical inputs produce an empty string.
+///
+/// Lines are matched by their longest common subsequence. Where both sides
+/// change, removals come before additions, so the output is deterministic.
+/// Every line is shown, without hunks; past `max_lines` lines the output is
+/// cut off with a "... N more lines" marker.
+///
+/// When both inputs are non-empty and only one ends with a newline, the
+/// last lines differ and the unterminated one is followed by
+/// `\ No newline at end of file`, as in unified diff. The marker is not
+/// counted against `max_lines`.
+pub fn simple_diff_capped(a: &str, b: &str, max_lines: usize) -> String {
+    if a == b {
+        return String::new();
+    }
+    let mark = !a.is_empty() && !b.is_empty() && a.ends_with('\n') != b.ends_with('\n');
+    let a = split_lines(a, mark && !a.ends_with('\n'));
+    let b = split_lines(b, mark && !b.ends_with('\n'));
+
+    // lcs[i][j] is the LCS length of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut output = Vec::with_capacity(a.len().max(b.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            output.push(diff_line(' ', a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            output.push(diff_line('-', a[i]));
+            i += 1;
+        } else {
+            output.push(diff_line('+', b[j]));
+            j += 1;
+        }
+    }
+
+    if output.len() > max_lines {
+        let hidden = output.len() - max_lines;
+        output.truncate(max_lines);
+        output.push(format!("... {} more lines", hidden));
+    }
+    output.join("\n")
+}
+
+/// Splits `s` into lines, flagging the last one when `mark_last` is set
+fn split_lines(s: &str, mark_last: bool) -> Vec<(&str, bool)> {
+    let mut lines: Vec<(&str, bool)> = s.lines().map(|line| (line, false)).collect();
+    if let Some(last) = lines.last_mut() {
+        last.1 = mark_last;
+    }
+    lines
+}
+
+/// Formats one output line, followed by the no-newline marker if flagged
+fn diff_line(prefix: char, (line, unterminated): (&str, bool)) -> String {
+    if unterminated {
+        format!("{}{}\n{}", prefix, line, NO_NEWLINE_MARKER)
+    } else {
+        format!("{}{}", prefix, line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_inputs() {
+        assert_eq!(simple_diff("", ""), "");
+        assert_eq!(simple_diff("a\nb", "a\nb"), "");
+    }
+
+    #[test]
+    fn test_one_line_change() {
+        assert_eq!(simple_diff("a\nb\nc", "a\nx\nc"), " a\n-b\n+x\n c");
+    }
+
+    #[test]
+    fn test_insertion_at_start_and_end() {
+        assert_eq!(simple_diff("b\nc", "a\nb\nc"), "+a\n b\n c");
+        assert_eq!(simple_diff("a\nb", "a\nb\nc"), " a\n b\n+c");
+        assert_eq!(simple_diff("a\nb\nc", "b"), "-a\n b\n-c");
+    }
+
+    #[test]
+    fn test_disjoint_inputs() {
+        assert_eq!(simple_diff("a\nb", "x\ny"), "-a\n-b\n+x\n+y");
+        assert_eq!(simple_diff("", "x"), "+x");
+    }
+
+    #[test]
+    fn test_truncation() {
+        let a: String = (0..10).map(|i| format!("{}\n", i)).collect();
+        assert_eq!(simple_diff_capped(&a, "", 3), "-0\n-1\n-2\n... 7 more lines");
+        assert_eq!(simple_diff_capped("a", "b", 2), "-a\n+b");
+    }
+
+    #[test]
+    fn test_trailing_newline_only() {
+        assert_eq!(
+            simple_diff("a\nb", "a\nb\n"),
+            " a\n-b\n\\ No newline at end of file\n+b"
+        );
+        assert_eq!(simple_diff("x\n", "x"), "-x\n+x\n\\ No newline at end of file");
+        assert_eq!(
+            simple_diff_capped("a\nb", "a\nc\n", 2),
+            " a\n-b\n\\ No newline at end of file\n... 1 more lines"
+        );
+    }
+}
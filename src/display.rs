@@ -0,0 +1,197 @@
+//! Bounded, printable rendering of untrusted text for revert messages.
+
+use alloy_primitives::U256;
+
+use crate::grapheme::{grapheme_count, grapheme_len, ZERO_WIDTH_JOINER};
+use crate::{bytes_to_hex_string, to_string};
+
+/// Uppercase hex digits for `\xNN` escapes
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// What `display_safe_with` counts when deciding where to cut
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TruncateMode {
+    /// Characters, which may split an emoji sequence
+    #[default]
+    Chars,
+    /// Grapheme clusters as segmented by `truncate_graphemes`
+    Graphemes,
+}
+
+/// Renders untrusted text for display, keeping at most `max_chars` of its
+/// characters.
+///
+/// - control characters (C0, DEL and C1) become `\xNN`, and a backslash
+///   becomes `\\`, so escapes in the output are unambiguous
+/// - characters that can disguise text — bidi overrides and isolates,
+///   zero-width characters, line and paragraph separators, the BOM and
+///   noncharacters — become U+FFFD
+/// - longer input is cut on a character boundary and followed by
+///   "… (+N more)", N being the number of characters omitted
+pub fn display_safe(s: &str, max_chars: usize) -> String {
+    let mut result = String::with_capacity(s.len().min(max_chars.saturating_mul(4)));
+    let mut chars = s.chars();
+    for c in chars.by_ref().take(max_chars) {
+        push_safe(&mut result, c);
+    }
+    let omitted = chars.count();
+    if omitted > 0 {
+        result.push_str("… (+");
+        result.push_str(&to_string(U256::from(omitted)));
+        result.push_str(" more)");
+    }
+    result
+}
+
+/// Renders untrusted text like `display_safe`, counting and cutting
+/// according to `mode`.
+///
+/// With `TruncateMode::Graphemes` the limit and the "(+N more)" count are
+/// in grapheme clusters, and a zero width joiner inside an emoji sequence
+/// is kept rather than replaced, so the sequence still renders as one
+/// glyph. A joiner at the end of a cluster is replaced as usual.
+pub fn display_safe_with(s: &str, max_chars: usize, mode: TruncateMode) -> String {
+    if mode == TruncateMode::Chars {
+        return display_safe(s, max_chars);
+    }
+    let mut result = String::with_capacity(s.len().min(max_chars.saturating_mul(4)));
+    let mut rest = s;
+    for _ in 0..max_chars {
+        if rest.is_empty() {
+            break;
+        }
+        let (cluster, tail) = rest.split_at(grapheme_len(rest));
+        let mut chars = cluster.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == ZERO_WIDTH_JOINER && chars.peek().is_some() {
+                result.push(c);
+            } else {
+                push_safe(&mut result, c);
+            }
+        }
+        rest = tail;
+    }
+    let omitted = grapheme_count(rest);
+    if omitted > 0 {
+        result.push_str("… (+");
+        result.push_str(&to_string(U256::from(omitted)));
+        result.push_str(" more)");
+    }
+    result
+}
+
+/// Renders untrusted bytes for display: as `display_safe` text if they are
+/// valid UTF-8, otherwise as "0x" hex of at most `max_chars` bytes followed
+/// by "… (+N more bytes)" when cut.
+pub fn display_safe_bytes(bytes: &[u8], max_chars: usize) -> String {
+    if let Ok(s) = core::str::from_utf8(bytes) {
+        return display_safe(s, max_chars);
+    }
+    let shown = bytes.len().min(max_chars);
+    let mut result = bytes_to_hex_string(&bytes[..shown]);
+    if shown < bytes.len() {
+        result.push_str("… (+");
+        result.push_str(&to_string(U256::from(bytes.len() - shown)));
+        result.push_str(" more bytes)");
+    }
+    result
+}
+
+fn push_safe(out: &mut String, c: char) {
+    if c.is_control() {
+        let code = c as usize;
+        out.push_str("\\x");
+        out.push(HEX_UPPER[code >> 4] as char);
+        out.push(HEX_UPPER[code & 0xf] as char);
+    } else if c == '\\' {
+        out.push_str("\\\\");
+    } else if is_deceptive(c) {
+        out.push(char::REPLACEMENT_CHARACTER);
+    } else {
+        out.push(c);
+    }
+}
+
+/// Invisible or direction-changing characters that make displayed text
+/// differ from its content
+fn is_deceptive(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200B}'..='\u{200F}'
+            | '\u{2028}'..='\u{202E}'
+            | '\u{2060}'..='\u{2069}'
+            | '\u{FEFF}'
+            | '\u{FFF9}'..='\u{FFFB}'
+            | '\u{FDD0}'..='\u{FDEF}'
+    ) || (c as u32 & 0xfffe) == 0xfffe
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_string_passes_through() {
+        assert_eq!(display_safe("transfer to alice.eth", 64), "transfer to alice.eth");
+        assert_eq!(display_safe("naïve – ok", 64), "naïve – ok");
+        assert_eq!(display_safe("", 0), "");
+    }
+
+    #[test]
+    fn test_control_characters_are_escaped() {
+        assert_eq!(display_safe("a\0b\x1b[31mc", 64), "a\\x00b\\x1B[31mc");
+        assert_eq!(display_safe("line\nbreak\u{7f}\u{85}", 64), "line\\x0Abreak\\x7F\\x85");
+        // A literal backslash cannot be mistaken for an escape
+        assert_eq!(display_safe("\\x00", 64), "\\\\x00");
+    }
+
+    #[test]
+    fn test_deceptive_characters_are_replaced() {
+        assert_eq!(display_safe("abc\u{202E}fed", 64), "abc\u{FFFD}fed");
+        assert_eq!(display_safe("a\u{200B}b\u{FEFF}c\u{2028}", 64), "a\u{FFFD}b\u{FFFD}c\u{FFFD}");
+        assert_eq!(display_safe("\u{FFFF}\u{1FFFE}", 64), "\u{FFFD}\u{FFFD}");
+    }
+
+    #[test]
+    fn test_long_string_is_truncated() {
+        let long = "ab".repeat(5_000);
+        let shown = display_safe(&long, 32);
+        assert_eq!(shown, format!("{}… (+9968 more)", "ab".repeat(16)));
+        // Truncation counts characters, never splitting one
+        assert_eq!(display_safe("ééé", 2), "éé… (+1 more)");
+        assert_eq!(display_safe("abc", 3), "abc");
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let long = "ab".repeat(5_000);
+        assert_eq!(display_safe(&long, usize::MAX), long);
+        assert_eq!(display_safe("a\nb", usize::MAX), "a\\x0Ab");
+        let family = "\u{1F468}\u{200D}\u{1F469}";
+        assert_eq!(display_safe_with(family, usize::MAX, TruncateMode::Graphemes), family);
+    }
+
+    #[test]
+    fn test_grapheme_mode() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let name = format!("{}{}x", family, family);
+        assert_eq!(display_safe_with(&name, 1, TruncateMode::Graphemes), format!("{}… (+2 more)", family));
+        assert_eq!(display_safe_with(&name, 3, TruncateMode::Graphemes), name);
+        // By characters the sequence is split and its joiners replaced
+        assert_eq!(
+            display_safe_with(&name, 2, TruncateMode::Chars),
+            "\u{1F468}\u{FFFD}… (+9 more)"
+        );
+        // Joiners outside emoji sequences are still replaced
+        assert_eq!(display_safe_with("a\u{200D}", 5, TruncateMode::Graphemes), "a\u{FFFD}");
+        assert_eq!(display_safe_with("a\u{202E}b\n", 5, TruncateMode::Graphemes), "a\u{FFFD}b\\x0A");
+        assert_eq!(display_safe_with("flags: \u{1F1FA}\u{1F1F8}", 8, TruncateMode::Graphemes), "flags: \u{1F1FA}\u{1F1F8}");
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(display_safe_bytes(b"ok\0", 16), "ok\\x00");
+        assert_eq!(display_safe_bytes(&[0xff, 0xfe, 0x00], 16), "0xfffe00");
+        assert_eq!(display_safe_bytes(&[0xc3; 10], 4), "0xc3c3c3c3… (+6 more bytes)");
+    }
+}
@@ -0,0 +1,120 @@
+//! `Debug`-like dumps of contract state without deriving anything.
+
+use alloy_primitives::{Address, B256, U256};
+
+use crate::fluent::group_digits;
+use crate::{abbreviate_address, bytes_to_hex_string, to_string, to_string_radix};
+
+/// Builder for a one-line rendering like
+/// "PoolState { reserve0: 1,234 (0x4d2), token0: 0xAb58…cF12, paused: false }".
+///
+/// Each field type is shown the way it is usually read: numbers in grouped
+/// decimal with their hex alongside, addresses abbreviated with their
+/// checksum, hashes by their first two bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateDump {
+    name: String,
+    fields: Vec<String>,
+}
+
+impl StateDump {
+    /// Starts a dump of a struct called `name`.
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), fields: Vec::new() }
+    }
+
+    /// Adds a number as grouped decimal with unpadded hex, e.g. "1,234 (0x4d2)".
+    pub fn field_u256(self, name: &str, value: U256) -> Self {
+        let decimal = group_digits(&to_string(value), 3, ',');
+        let rendered = format!("{} (0x{})", decimal, to_string_radix(value, 16));
+        self.field(name, rendered)
+    }
+
+    /// Adds an address as its abbreviated checksum form, e.g. "0xAb58…cF12".
+    pub fn field_address(self, name: &str, value: Address) -> Self {
+        self.field(name, abbreviate_address(value))
+    }
+
+    /// Adds a 32-byte hash as its first two bytes, e.g. "0x9f86…".
+    pub fn field_b256(self, name: &str, value: B256) -> Self {
+        let rendered = format!("{}…", bytes_to_hex_string(&value[..2]));
+        self.field(name, rendered)
+    }
+
+    /// Adds a boolean.
+    pub fn field_bool(self, name: &str, value: bool) -> Self {
+        self.field(name, value.to_string())
+    }
+
+    /// Adds another dump inline, e.g. "fee: FeeConfig { bps: 30 (0x1e) }".
+    pub fn field_nested(self, name: &str, value: StateDump) -> Self {
+        self.field(name, value.finish())
+    }
+
+    /// Renders the dump; one without fields is just its name, like `Debug`.
+    pub fn finish(self) -> String {
+        if self.fields.is_empty() {
+            return self.name;
+        }
+        format!("{} {{ {} }}", self.name, self.fields.join(", "))
+    }
+
+    fn field(mut self, name: &str, rendered: String) -> Self {
+        self.fields.push(format!("{}: {}", name, rendered));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::keccak256;
+
+    fn token() -> Address {
+        crate::id::parse_address("0xAb5801a7D398351b8bE11C439e05C5B3259aeC9B", 0).unwrap()
+    }
+
+    #[test]
+    fn test_flat_struct() {
+        let dump = StateDump::new("PoolState")
+            .field_u256("reserve0", U256::from(1234))
+            .field_address("token0", token())
+            .field_b256("lastHash", keccak256(b"test"))
+            .field_bool("paused", false)
+            .finish();
+        assert_eq!(
+            dump,
+            "PoolState { reserve0: 1,234 (0x4d2), token0: 0xAb58…eC9B, lastHash: 0x9c22…, paused: false }"
+        );
+    }
+
+    #[test]
+    fn test_nested_struct() {
+        let fees = StateDump::new("FeeConfig")
+            .field_u256("bps", U256::from(30))
+            .field_bool("enabled", true);
+        let dump = StateDump::new("Pool")
+            .field_nested("fees", fees)
+            .field_u256("liquidity", U256::from(10).pow(U256::from(18)))
+            .finish();
+        assert_eq!(
+            dump,
+            "Pool { fees: FeeConfig { bps: 30 (0x1e), enabled: true }, \
+             liquidity: 1,000,000,000,000,000,000 (0xde0b6b3a7640000) }"
+        );
+    }
+
+    #[test]
+    fn test_empty_and_zero_values() {
+        assert_eq!(StateDump::new("Empty").finish(), "Empty");
+        assert_eq!(
+            StateDump::new("Zeroed")
+                .field_u256("n", U256::ZERO)
+                .field_address("a", Address::ZERO)
+                .field_b256("h", B256::ZERO)
+                .field_nested("inner", StateDump::new("Inner"))
+                .finish(),
+            "Zeroed { n: 0 (0x0), a: 0x0000…0000, h: 0x0000…, inner: Inner }"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! EIP-191 `personal_sign` messages: prefix, hash and a readable preview.
+
+use alloy_primitives::{keccak256, B256, U256};
+
+use crate::{bytes_to_hex_string, to_string};
+
+/// Version 0x45 (`E`) preamble, followed by the decimal message length
+const PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+/// Builds `"\x19Ethereum Signed Message:\n" ‖ len ‖ message`, where `len`
+/// is the message's byte length in decimal.
+pub fn eip191_prefix(message: &[u8]) -> Vec<u8> {
+    let length = to_string(U256::from(message.len()));
+    let mut result = Vec::with_capacity(PREFIX.len() + length.len() + message.len());
+    result.extend_from_slice(PREFIX);
+    result.extend_from_slice(length.as_bytes());
+    result.extend_from_slice(message);
+    result
+}
+
+/// Hashes a message as `personal_sign` does, i.e. keccak256 of
+/// `eip191_prefix(message)`.
+pub fn eip191_hash(message: &[u8]) -> B256 {
+    keccak256(eip191_prefix(message))
+}
+
+/// Shows what a `personal_sign` signature covers, one line each for the
+/// escaped prefix, the byte length, the message and the hash.
+///
+/// UTF-8 messages are shown quoted with control characters and quotes
+/// escaped, so a message cannot spoof extra lines; anything else is shown
+/// as hex.
+pub fn format_eip191_preview(message: &[u8]) -> String {
+    let rendered = match core::str::from_utf8(message) {
+        Ok(text) => format!("{:?}", text),
+        Err(_) => bytes_to_hex_string(message),
+    };
+    format!(
+        "prefix: \"\\x19Ethereum Signed Message:\\n\"\nlength: {}\nmessage: {}\nhash: {}",
+        message.len(),
+        rendered,
+        bytes_to_hex_string(eip191_hash(message).as_slice())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eip191_prefix() {
+        assert_eq!(eip191_prefix(b"hello"), b"\x19Ethereum Signed Message:\n5hello".to_vec());
+        assert_eq!(eip191_prefix(&[0u8; 120])[PREFIX.len()..PREFIX.len() + 3], *b"120");
+    }
+
+    #[test]
+    fn test_eip191_hash_matches_wallets() {
+        // hashMessage("Hello World") as computed by ethers.js and MetaMask
+        assert_eq!(
+            bytes_to_hex_string(eip191_hash(b"Hello World").as_slice()),
+            "0xa1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+        );
+    }
+
+    #[test]
+    fn test_empty_message() {
+        assert_eq!(eip191_prefix(b""), b"\x19Ethereum Signed Message:\n0".to_vec());
+        let preview = format_eip191_preview(b"");
+        assert!(preview.contains("\nlength: 0\nmessage: \"\"\n"));
+    }
+
+    #[test]
+    fn test_format_eip191_preview() {
+        assert_eq!(
+            format_eip191_preview(b"Hello World"),
+            "prefix: \"\\x19Ethereum Signed Message:\\n\"\n\
+             length: 11\n\
+             message: \"Hello World\"\n\
+             hash: 0xa1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+        );
+        // Line breaks in the message stay escaped
+        assert!(format_eip191_preview(b"a\nb").contains("message: \"a\\nb\"\n"));
+    }
+
+    #[test]
+    fn test_non_utf8_message() {
+        let preview = format_eip191_preview(&[0xff, 0x00, 0x42]);
+        assert!(preview.contains("\nlength: 3\nmessage: 0xff0042\n"));
+    }
+}
@@ -0,0 +1,259 @@
+//! Byte-to-text encodings used when embedding content in URIs.
+
+use core::fmt;
+
+/// Standard base64 alphabet (RFC 4648)
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// URL- and filename-safe base64 alphabet (RFC 4648 section 5)
+const BASE64_URL_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Error types for base64 decoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base64Error {
+    /// Input length is not a multiple of 4
+    InvalidLength(usize),
+    /// Character at `index` is outside the alphabet or misplaced padding
+    InvalidCharacter { index: usize, found: char },
+}
+
+/// Encodes bytes as padded standard base64.
+pub fn base64_encode(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(base64_encoded_len(bytes.len()));
+    for chunk in bytes.chunks(3) {
+        push_quantum(&mut result, chunk, BASE64_ALPHABET, true).expect("writing to a String cannot fail");
+    }
+    result
+}
+
+/// Writes the base64 of one to three bytes, padded to four characters
+/// with `=` if `pad` is set.
+fn push_quantum<W: fmt::Write>(out: &mut W, chunk: &[u8], alphabet: &[u8; 64], pad: bool) -> fmt::Result {
+    let b0 = chunk[0] as u32;
+    let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
+    let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
+    let triple = (b0 << 16) | (b1 << 8) | b2;
+
+    out.write_char(alphabet[(triple >> 18) as usize & 0x3f] as char)?;
+    out.write_char(alphabet[(triple >> 12) as usize & 0x3f] as char)?;
+    if chunk.len() > 1 {
+        out.write_char(alphabet[(triple >> 6) as usize & 0x3f] as char)?;
+    } else if pad {
+        out.write_char('=')?;
+    }
+    if chunk.len() > 2 {
+        out.write_char(alphabet[triple as usize & 0x3f] as char)?;
+    } else if pad {
+        out.write_char('=')?;
+    }
+    Ok(())
+}
+
+/// Incremental base64 encoder writing into any `fmt::Write`, such as a
+/// `&mut String`.
+///
+/// Input may be fed in chunks of any size; up to two bytes are carried
+/// between calls, so the output is identical to encoding the concatenated
+/// input at once. Nothing is buffered beyond that carry.
+///
+/// ```
+/// use strings_utils_stylus::{base64_encode, Base64Encoder};
+/// let mut out = String::new();
+/// let mut encoder = Base64Encoder::new(&mut out);
+/// encoder.update(b"<svg").unwrap();
+/// encoder.update(b"/>").unwrap();
+/// encoder.finalize().unwrap();
+/// assert_eq!(out, base64_encode(b"<svg/>"));
+/// ```
+#[derive(Debug)]
+pub struct Base64Encoder<W: fmt::Write> {
+    out: W,
+    alphabet: &'static [u8; 64],
+    pad: bool,
+    carry: [u8; 3],
+    carried: usize,
+}
+
+impl<W: fmt::Write> Base64Encoder<W> {
+    /// Creates an encoder producing padded standard base64, like
+    /// `base64_encode`.
+    pub fn new(out: W) -> Self {
+        Self { out, alphabet: BASE64_ALPHABET, pad: true, carry: [0; 3], carried: 0 }
+    }
+
+    /// Creates an encoder producing unpadded URL-safe base64, which uses
+    /// `-` and `_` in place of `+` and `/`.
+    pub fn url_safe(out: W) -> Self {
+        Self { out, alphabet: BASE64_URL_ALPHABET, pad: false, carry: [0; 3], carried: 0 }
+    }
+
+    /// Encodes `chunk`, writing every complete 3-byte group.
+    pub fn update(&mut self, mut chunk: &[u8]) -> fmt::Result {
+        if self.carried > 0 {
+            let take = (3 - self.carried).min(chunk.len());
+            self.carry[self.carried..self.carried + take].copy_from_slice(&chunk[..take]);
+            self.carried += take;
+            chunk = &chunk[take..];
+            if self.carried < 3 {
+                return Ok(());
+            }
+            push_quantum(&mut self.out, &self.carry, self.alphabet, self.pad)?;
+            self.carried = 0;
+        }
+
+        let whole = chunk.len() - chunk.len() % 3;
+        for group in chunk[..whole].chunks(3) {
+            push_quantum(&mut self.out, group, self.alphabet, self.pad)?;
+        }
+        let rest = &chunk[whole..];
+        self.carry[..rest.len()].copy_from_slice(rest);
+        self.carried = rest.len();
+        Ok(())
+    }
+
+    /// Writes the final partial group, with padding if enabled, and
+    /// returns the writer.
+    pub fn finalize(mut self) -> Result<W, fmt::Error> {
+        if self.carried > 0 {
+            push_quantum(&mut self.out, &self.carry[..self.carried], self.alphabet, self.pad)?;
+        }
+        Ok(self.out)
+    }
+}
+
+/// Returns the length of the padded base64 encoding of `len` bytes.
+pub fn base64_encoded_len(len: usize) -> usize {
+    len.div_ceil(3) * 4
+}
+
+/// Decodes padded standard base64, the inverse of `base64_encode`.
+///
+/// Input must be a multiple of 4 characters with `=` only as the final one
+/// or two; whitespace is not skipped. Unused bits of the last character are
+/// ignored.
+pub fn base64_decode(s: &str) -> Result<Vec<u8>, Base64Error> {
+    let bytes = s.as_bytes();
+    if bytes.len() % 4 != 0 {
+        return Err(Base64Error::InvalidLength(bytes.len()));
+    }
+    let padding = bytes.iter().rev().take(2).take_while(|&&b| b == b'=').count();
+    let data_len = bytes.len() - padding;
+
+    let mut result = Vec::with_capacity(bytes.len() / 4 * 3);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for (index, &b) in bytes[..data_len].iter().enumerate() {
+        let value = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => {
+                let found = s[index..].chars().next().unwrap_or_default();
+                return Err(Base64Error::InvalidCharacter { index, found });
+            }
+        };
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode_rfc_vectors() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foob"), "Zm9vYg==");
+        assert_eq!(base64_encode(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(&[0xfb, 0xff]), "+/8=");
+    }
+
+    #[test]
+    fn test_base64_decode_round_trip() {
+        for input in [&b""[..], b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar", &[0xfb, 0xff]] {
+            assert_eq!(base64_decode(&base64_encode(input)).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn test_base64_decode_errors() {
+        assert_eq!(base64_decode("Zg="), Err(Base64Error::InvalidLength(3)));
+        assert_eq!(base64_decode("Z=g="), Err(Base64Error::InvalidCharacter { index: 1, found: '=' }));
+        assert_eq!(base64_decode("Zm9v\nYg=="), Err(Base64Error::InvalidLength(9)));
+        assert_eq!(base64_decode("Zm9vY é"), Err(Base64Error::InvalidCharacter { index: 5, found: ' ' }));
+        assert_eq!(base64_decode("Zm9vé=="), Err(Base64Error::InvalidCharacter { index: 4, found: 'é' }));
+    }
+
+    #[test]
+    fn test_base64_encoded_len() {
+        for len in 0..10 {
+            assert_eq!(base64_encoded_len(len), base64_encode(&vec![0u8; len]).len());
+        }
+    }
+
+    const INPUT: &[u8] = b"<svg xmlns='http://www.w3.org/2000/svg'><text>\xfb\xff streaming</text></svg>";
+
+    fn streamed(input: &[u8], chunk_size: usize) -> String {
+        let mut out = String::new();
+        let mut encoder = Base64Encoder::new(&mut out);
+        for chunk in input.chunks(chunk_size) {
+            encoder.update(chunk).unwrap();
+        }
+        encoder.finalize().unwrap();
+        out
+    }
+
+    #[test]
+    fn test_streaming_matches_one_shot() {
+        for len in 0..INPUT.len() {
+            for chunk_size in [1, 2, 3, 4, 7] {
+                assert_eq!(streamed(&INPUT[..len], chunk_size), base64_encode(&INPUT[..len]), "{} {}", len, chunk_size);
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_empty_chunks() {
+        let mut encoder = Base64Encoder::new(String::new());
+        for chunk in INPUT.chunks(5) {
+            encoder.update(&[]).unwrap();
+            encoder.update(chunk).unwrap();
+            encoder.update(&[]).unwrap();
+        }
+        assert_eq!(encoder.finalize().unwrap(), base64_encode(INPUT));
+    }
+
+    #[test]
+    fn test_streaming_empty_input() {
+        assert_eq!(Base64Encoder::new(String::new()).finalize().unwrap(), "");
+        assert_eq!(Base64Encoder::url_safe(String::new()).finalize().unwrap(), "");
+    }
+
+    #[test]
+    fn test_url_safe() {
+        let mut encoder = Base64Encoder::url_safe(String::new());
+        encoder.update(&[0xfb]).unwrap();
+        encoder.update(&[0xff, 0xbf]).unwrap();
+        encoder.update(b"f").unwrap();
+        assert_eq!(encoder.finalize().unwrap(), "-_-_Zg");
+
+        let expected = base64_encode(INPUT).replace('+', "-").replace('/', "_");
+        let mut encoder = Base64Encoder::url_safe(String::new());
+        for chunk in INPUT.chunks(4) {
+            encoder.update(chunk).unwrap();
+        }
+        assert_eq!(encoder.finalize().unwrap(), expected.trim_end_matches('='));
+    }
+}
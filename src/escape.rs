@@ -0,0 +1,84 @@
+//! Escaping for embedding untrusted text in HTML.
+//!
+//! Text content only needs `&`, `<` and `>` escaped. Attribute values also
+//! need both quote characters escaped, and newlines are written as numeric
+//! character references so they survive attribute-value normalization.
+//! Single quotes use `&#39;` rather than `&apos;`, which HTML4 lacks; XML and
+//! SVG accept both, so an XML/SVG escaper should share `escape_markup`.
+//!
+//! Escaping is not idempotent: already-escaped input such as `&amp;` is
+//! escaped again to `&amp;amp;`. Callers should escape raw text exactly once.
+
+/// Escapes text for use between HTML tags (`&`, `<`, `>`).
+pub fn escape_html_text(s: &str) -> String {
+    escape_markup(s, false)
+}
+
+/// Escapes text for use inside a quoted HTML attribute value.
+///
+/// In addition to `escape_html_text`, escapes `"` and `'` and encodes
+/// `\n`, `\r` and `\t` as numeric character references.
+pub fn escape_html_attr(s: &str) -> String {
+    escape_markup(s, true)
+}
+
+/// Shared escaping core; `attribute` selects the stricter attribute rules.
+fn escape_markup(s: &str, attribute: bool) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' if attribute => result.push_str("&quot;"),
+            '\'' if attribute => result.push_str("&#39;"),
+            '\n' if attribute => result.push_str("&#10;"),
+            '\r' if attribute => result.push_str("&#13;"),
+            '\t' if attribute => result.push_str("&#9;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPECIALS: &str = "a & b < c > d \"e\" 'f'";
+
+    #[test]
+    fn test_escape_html_text() {
+        assert_eq!(escape_html_text(SPECIALS), "a &amp; b &lt; c &gt; d \"e\" 'f'");
+        assert_eq!(escape_html_text("line\nbreak"), "line\nbreak");
+        assert_eq!(escape_html_text(""), "");
+    }
+
+    #[test]
+    fn test_escape_html_attr() {
+        assert_eq!(
+            escape_html_attr(SPECIALS),
+            "a &amp; b &lt; c &gt; d &quot;e&quot; &#39;f&#39;"
+        );
+        assert_eq!(escape_html_attr("a\r\nb\tc"), "a&#13;&#10;b&#9;c");
+    }
+
+    #[test]
+    fn test_already_escaped_is_escaped_again() {
+        assert_eq!(escape_html_text("&amp;"), "&amp;amp;");
+        assert_eq!(escape_html_attr("&quot;"), "&amp;quot;");
+    }
+
+    #[test]
+    fn test_attribute_template_cannot_break_out() {
+        let user = "x\" onload=\"alert(1)' data-a='\n<script>";
+        let escaped = escape_html_attr(user);
+        let html = format!("<iframe title=\"{}\" name='{}'></iframe>", escaped, escaped);
+        // The only quotes left are the template's own delimiters
+        assert_eq!(html.matches('"').count(), 2);
+        assert_eq!(html.matches('\'').count(), 2);
+        assert!(!html.contains('\n'));
+        assert_eq!(html.matches('<').count(), 2);
+        assert!(html.starts_with("<iframe title=\"x&quot; onload=&quot;alert(1)&#39; data-a=&#39;&#10;&lt;script&gt;\""));
+    }
+}
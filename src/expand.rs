@@ -0,0 +1,152 @@
+//! Shell-style `${NAME}` expansion for stored URI templates.
+//!
+//! Unlike `msgf`, variables are looked up by name through a resolver
+//! callback at read time and may carry a default. Expansion is a single
+//! pass: resolved values and defaults are inserted verbatim and never
+//! scanned for further `${...}`, so a variable cannot expand into itself.
+
+/// Maximum number of `${...}` substitutions in one template
+pub const MAX_EXPANSIONS: usize = 64;
+
+/// Error types for variable expansion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpandError {
+    /// Variable has no value and no default
+    Unresolved(String),
+    /// `${` without a closing `}` at this byte offset
+    Unterminated(usize),
+    /// Variable name at this byte offset is empty or not `[A-Za-z_][A-Za-z0-9_]*`
+    InvalidName(usize),
+    /// `$` at this byte offset is not followed by `{` or `$`
+    DanglingDollar(usize),
+    /// Template has more than `MAX_EXPANSIONS` substitutions
+    TooManyExpansions,
+}
+
+/// Expands `${NAME}` placeholders in `template` using `resolver`.
+///
+/// `${NAME:-default}` uses `default` when the resolver returns `None` or an
+/// empty string; the default runs to the first `}` and is literal text.
+/// `$$` produces a literal `$`, and any other `$` is an error. Resolved
+/// values are not expanded again (see the module docs), and templates with
+/// more than `MAX_EXPANSIONS` placeholders are rejected to bound the work
+/// done per call.
+pub fn expand_vars(template: &str, resolver: impl Fn(&str) -> Option<String>) -> Result<String, ExpandError> {
+    let mut result = String::with_capacity(template.len());
+    let mut expansions = 0;
+    let mut rest = template;
+
+    while let Some(dollar) = rest.find('$') {
+        let index = template.len() - rest.len() + dollar;
+        result.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        if let Some(tail) = after.strip_prefix('$') {
+            result.push('$');
+            rest = tail;
+            continue;
+        }
+        let body = after.strip_prefix('{').ok_or(ExpandError::DanglingDollar(index))?;
+        let close = body.find('}').ok_or(ExpandError::Unterminated(index))?;
+
+        expansions += 1;
+        if expansions > MAX_EXPANSIONS {
+            return Err(ExpandError::TooManyExpansions);
+        }
+
+        let (name, default) = match body[..close].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&body[..close], None),
+        };
+        if !is_valid_name(name) {
+            return Err(ExpandError::InvalidName(index + 2));
+        }
+        match (resolver(name), default) {
+            (Some(value), Some(default)) if value.is_empty() => result.push_str(default),
+            (Some(value), _) => result.push_str(&value),
+            (None, Some(default)) => result.push_str(default),
+            (None, None) => return Err(ExpandError::Unresolved(name.to_string())),
+        }
+        rest = &body[close + 1..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(name: &str) -> Option<String> {
+        match name {
+            "GATEWAY" => Some("https://ipfs.io".into()),
+            "CID" => Some("bafy123".into()),
+            "EMPTY" => Some(String::new()),
+            "LOOP" => Some("${LOOP}".into()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_simple_expansion() {
+        assert_eq!(expand_vars("${GATEWAY}/ipfs/${CID}/", vars).unwrap(), "https://ipfs.io/ipfs/bafy123/");
+        assert_eq!(expand_vars("no placeholders", vars).unwrap(), "no placeholders");
+        assert_eq!(expand_vars("", vars).unwrap(), "");
+    }
+
+    #[test]
+    fn test_defaults() {
+        // Default used when unset or empty
+        assert_eq!(expand_vars("${HOST:-https://dweb.link}/x", vars).unwrap(), "https://dweb.link/x");
+        assert_eq!(expand_vars("${EMPTY:-fallback}", vars).unwrap(), "fallback");
+        assert_eq!(expand_vars("${MISSING:-}", vars).unwrap(), "");
+        // Default unused when set
+        assert_eq!(expand_vars("${GATEWAY:-https://dweb.link}", vars).unwrap(), "https://ipfs.io");
+        // Empty without a default is still a value
+        assert_eq!(expand_vars("[${EMPTY}]", vars).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_unresolved() {
+        assert_eq!(expand_vars("${GATEWAY}/${TOKEN_ID}", vars), Err(ExpandError::Unresolved("TOKEN_ID".into())));
+    }
+
+    #[test]
+    fn test_dollar_escape() {
+        assert_eq!(expand_vars("cost: $$5 at ${CID}", vars).unwrap(), "cost: $5 at bafy123");
+        assert_eq!(expand_vars("$${CID}", vars).unwrap(), "${CID}");
+        assert_eq!(expand_vars("a $ b", vars), Err(ExpandError::DanglingDollar(2)));
+        assert_eq!(expand_vars("trailing $", vars), Err(ExpandError::DanglingDollar(9)));
+    }
+
+    #[test]
+    fn test_nested_expansion_disabled() {
+        // Values and defaults are inserted verbatim, so self-reference cannot loop
+        assert_eq!(expand_vars("${LOOP}", vars).unwrap(), "${LOOP}");
+        assert_eq!(expand_vars("${MISSING:-${CID}}", vars).unwrap(), "${CID}");
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert_eq!(expand_vars("x${CID", vars), Err(ExpandError::Unterminated(1)));
+        assert_eq!(expand_vars("${}", vars), Err(ExpandError::InvalidName(2)));
+        assert_eq!(expand_vars("ab${1X}", vars), Err(ExpandError::InvalidName(4)));
+        assert_eq!(expand_vars("${A-B}", vars), Err(ExpandError::InvalidName(2)));
+    }
+
+    #[test]
+    fn test_expansion_cap() {
+        let at_cap = "${CID}".repeat(MAX_EXPANSIONS);
+        assert_eq!(expand_vars(&at_cap, vars).unwrap(), "bafy123".repeat(MAX_EXPANSIONS));
+        let over = "${CID}".repeat(MAX_EXPANSIONS + 1);
+        assert_eq!(expand_vars(&over, vars), Err(ExpandError::TooManyExpansions));
+        // Escapes do not count
+        assert!(expand_vars(&"$$".repeat(MAX_EXPANSIONS + 1), vars).is_ok());
+    }
+}
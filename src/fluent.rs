@@ -0,0 +1,422 @@
+//! Chainable formatting builder covering the decimal and hex options in one place.
+
+use core::fmt;
+
+use alloy_primitives::U256;
+
+use crate::{to_string, to_string_radix};
+
+/// Suffixes used by compact notation, one per power of 1000
+const COMPACT_SUFFIXES: [&str; 4] = ["K", "M", "B", "T"];
+
+/// Error types for invalid option combinations in `U256Fmt`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatError {
+    /// Option only applies to hex output but decimal was selected
+    HexOnlyOption(&'static str),
+    /// Option only applies to decimal output but hex was selected
+    DecimalOnlyOption(&'static str),
+    /// Two options that cannot be combined were both set
+    ConflictingOptions(&'static str, &'static str),
+    /// Digit group size must be non-zero
+    InvalidGroupSize,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::HexOnlyOption(option) => {
+                write!(f, "`{}` only applies to hex output", option)
+            }
+            FormatError::DecimalOnlyOption(option) => {
+                write!(f, "`{}` only applies to decimal output", option)
+            }
+            FormatError::ConflictingOptions(a, b) => {
+                write!(f, "`{}` cannot be combined with `{}`", a, b)
+            }
+            FormatError::InvalidGroupSize => write!(f, "digit group size must be non-zero"),
+        }
+    }
+}
+
+/// Formats a U256 in compact notation with one truncated decimal place,
+/// e.g. "1.5M" for 1_500_000. Values below 1000 are rendered exactly and
+/// values of a thousand trillion or more switch to scientific notation,
+/// e.g. "1.1e77" for `U256::MAX`.
+pub fn to_compact_string(value: U256) -> String {
+    let thousand = U256::from(1000);
+    if value < thousand {
+        return to_string(value);
+    }
+    let digits = to_string(value);
+    if digits.len() > 3 * (COMPACT_SUFFIXES.len() + 1) {
+        let tenth = &digits[1..2];
+        return if tenth == "0" {
+            format!("{}e{}", &digits[..1], digits.len() - 1)
+        } else {
+            format!("{}.{}e{}", &digits[..1], tenth, digits.len() - 1)
+        };
+    }
+
+    // Pick the largest suffix whose scale does not exceed the value
+    let mut scale = thousand;
+    let mut index = 0;
+    while index + 1 < COMPACT_SUFFIXES.len() && value / scale >= thousand {
+        scale *= thousand;
+        index += 1;
+    }
+
+    let whole = value / scale;
+    let tenth = (value % scale) / (scale / U256::from(10));
+    if tenth.is_zero() {
+        format!("{}{}", to_string(whole), COMPACT_SUFFIXES[index])
+    } else {
+        format!("{}.{}{}", to_string(whole), to_string(tenth), COMPACT_SUFFIXES[index])
+    }
+}
+
+/// Unprefixed hex digits of `value`, padded to an even count for
+/// `HexPadding::WholeBytes`
+fn hex_digits(value: U256, padding: HexPadding, uppercase: bool) -> String {
+    let mut digits = to_string_radix(value, 16);
+    if padding == HexPadding::WholeBytes && digits.len() % 2 == 1 {
+        digits.insert(0, '0');
+    }
+    if uppercase {
+        digits.make_ascii_uppercase();
+    }
+    digits
+}
+
+/// Inserts `separator` between every `size` characters, counting from the right.
+pub(crate) fn group_digits(digits: &str, size: usize, separator: char) -> String {
+    let len = digits.len();
+    let mut result = String::with_capacity(len + len / size);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (len - i).is_multiple_of(size) {
+            result.push(separator);
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// How hex output without an explicit width is padded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexPadding {
+    /// Minimal digits, e.g. "0x0" and "0xf"
+    Nibbles,
+    /// Whole bytes like OpenZeppelin's `toHexString`, e.g. "0x00" and "0x0f"
+    WholeBytes,
+}
+
+/// Padding used by `U256Fmt::hex` unless `padding` is set: `WholeBytes`
+/// with the `oz-compat` feature, `Nibbles` otherwise
+#[cfg(not(feature = "oz-compat"))]
+pub const DEFAULT_HEX_PADDING: HexPadding = HexPadding::Nibbles;
+
+/// Padding used by `U256Fmt::hex` unless `padding` is set: `WholeBytes`
+/// with the `oz-compat` feature, `Nibbles` otherwise
+#[cfg(feature = "oz-compat")]
+pub const DEFAULT_HEX_PADDING: HexPadding = HexPadding::WholeBytes;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Base {
+    Decimal,
+    Hex,
+}
+
+/// Fluent formatting builder for U256 values.
+///
+/// Options are recorded as they are chained and validated together by
+/// `try_to_string`. `Display` (and so `to_string`) cannot return the
+/// `FormatError`, so an invalid combination renders a visible
+/// `<invalid U256Fmt: ...>` marker instead of the value. Call `validate` or
+/// `try_to_string` first when the options are not fixed at compile time.
+#[derive(Debug, Clone, Copy)]
+pub struct U256Fmt {
+    value: U256,
+    base: Base,
+    uppercase: bool,
+    prefixed: Option<bool>,
+    width: usize,
+    grouping: Option<(usize, char)>,
+    separator: Option<char>,
+    compact_above: Option<U256>,
+    padding: HexPadding,
+}
+
+impl U256Fmt {
+    /// Starts a builder for `value`, defaulting to plain decimal output.
+    pub fn of(value: U256) -> Self {
+        Self {
+            value,
+            base: Base::Decimal,
+            uppercase: false,
+            prefixed: None,
+            width: 0,
+            grouping: None,
+            separator: None,
+            compact_above: None,
+            padding: DEFAULT_HEX_PADDING,
+        }
+    }
+
+    /// Selects decimal output.
+    pub fn decimal(mut self) -> Self {
+        self.base = Base::Decimal;
+        self
+    }
+
+    /// Selects hex output, "0x"-prefixed unless `prefixed(false)` is set.
+    pub fn hex(mut self) -> Self {
+        self.base = Base::Hex;
+        self
+    }
+
+    /// Uses uppercase hex digits (hex only).
+    pub fn uppercase(mut self) -> Self {
+        self.uppercase = true;
+        self
+    }
+
+    /// Controls the "0x" prefix (hex only).
+    pub fn prefixed(mut self, prefixed: bool) -> Self {
+        self.prefixed = Some(prefixed);
+        self
+    }
+
+    /// Zero-pads to at least `width` digits, not counting prefix or separators.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Inserts `separator` between groups of `size` digits from the right.
+    pub fn grouped(mut self, size: usize, separator: char) -> Self {
+        self.grouping = Some((size, separator));
+        self
+    }
+
+    /// Inserts `separator` between thousands (decimal only).
+    pub fn separators(mut self, separator: char) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// Switches to compact notation for values at or above `threshold`
+    /// (decimal only). Cannot be combined with `grouped`, whose groups the
+    /// compact form has no room for; `separators` still applies below the
+    /// threshold.
+    pub fn compact_above(mut self, threshold: U256) -> Self {
+        self.compact_above = Some(threshold);
+        self
+    }
+
+    /// Sets how hex digits are padded before any `width` is applied. Has
+    /// no effect on decimal output.
+    pub fn padding(mut self, padding: HexPadding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Checks the option combination without formatting.
+    pub fn validate(&self) -> Result<(), FormatError> {
+        match self.base {
+            Base::Decimal => {
+                if self.uppercase {
+                    return Err(FormatError::HexOnlyOption("uppercase"));
+                }
+                if self.prefixed.is_some() {
+                    return Err(FormatError::HexOnlyOption("prefixed"));
+                }
+            }
+            Base::Hex => {
+                if self.separator.is_some() {
+                    return Err(FormatError::DecimalOnlyOption("separators"));
+                }
+                if self.compact_above.is_some() {
+                    return Err(FormatError::DecimalOnlyOption("compact_above"));
+                }
+            }
+        }
+        if self.grouping.is_some() && self.separator.is_some() {
+            return Err(FormatError::ConflictingOptions("grouped", "separators"));
+        }
+        if self.grouping.is_some() && self.compact_above.is_some() {
+            return Err(FormatError::ConflictingOptions("grouped", "compact_above"));
+        }
+        if self.compact_above.is_some() && self.width > 0 {
+            return Err(FormatError::ConflictingOptions("compact_above", "width"));
+        }
+        if matches!(self.grouping, Some((0, _))) {
+            return Err(FormatError::InvalidGroupSize);
+        }
+        Ok(())
+    }
+
+    /// Formats the value, or returns the first invalid option combination.
+    pub fn try_to_string(&self) -> Result<String, FormatError> {
+        self.validate()?;
+
+        if let Some(threshold) = self.compact_above {
+            if self.value >= threshold {
+                return Ok(to_compact_string(self.value));
+            }
+        }
+
+        let mut digits = match self.base {
+            Base::Decimal => to_string(self.value),
+            Base::Hex => hex_digits(self.value, self.padding, self.uppercase),
+        };
+        if digits.len() < self.width {
+            digits.insert_str(0, &"0".repeat(self.width - digits.len()));
+        }
+
+        let grouping = self.grouping.or(self.separator.map(|c| (3, c)));
+        if let Some((size, separator)) = grouping {
+            digits = group_digits(&digits, size, separator);
+        }
+
+        if self.base == Base::Hex && self.prefixed.unwrap_or(true) {
+            digits.insert_str(0, "0x");
+        }
+        Ok(digits)
+    }
+}
+
+impl fmt::Display for U256Fmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.try_to_string() {
+            Ok(s) => f.pad(&s),
+            Err(err) => write!(f, "<invalid U256Fmt: {}>", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_golden_chains() {
+        let v = U256::from(0xdeadbeefu64);
+        let million = U256::from(1_000_000u64);
+        let max_hex = "f".repeat(64);
+        let cases = [
+            (U256Fmt::of(v), "3735928559"),
+            (U256Fmt::of(v).hex(), "0xdeadbeef"),
+            (U256Fmt::of(v).hex().uppercase(), "0xDEADBEEF"),
+            (U256Fmt::of(v).hex().prefixed(false), "deadbeef"),
+            (U256Fmt::of(v).hex().width(12), "0x0000deadbeef"),
+            (
+                U256Fmt::of(v).hex().uppercase().width(16).grouped(4, '_').prefixed(false),
+                "0000_0000_DEAD_BEEF",
+            ),
+            (U256Fmt::of(v).decimal().separators(','), "3,735,928,559"),
+            (U256Fmt::of(v).grouped(2, ' '), "37 35 92 85 59"),
+            (U256Fmt::of(U256::from(42)).width(5), "00042"),
+            (U256Fmt::of(v).decimal().separators(',').compact_above(million), "3.7B"),
+            (
+                U256Fmt::of(U256::from(999_999)).separators(',').compact_above(million),
+                "999,999",
+            ),
+            (U256Fmt::of(million).compact_above(million), "1M"),
+            (U256Fmt::of(U256::ZERO).hex().padding(HexPadding::Nibbles), "0x0"),
+            (U256Fmt::of(U256::ZERO).hex().padding(HexPadding::WholeBytes), "0x00"),
+            (U256Fmt::of(U256::from(0xabc)).hex().padding(HexPadding::WholeBytes), "0x0abc"),
+            (U256Fmt::of(U256::MAX).hex().prefixed(false), max_hex.as_str()),
+        ];
+        for (builder, expected) in cases {
+            assert_eq!(builder.try_to_string().unwrap(), expected, "{:?}", builder);
+            assert_eq!(builder.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_default_hex_padding() {
+        #[cfg(not(feature = "oz-compat"))]
+        let expected = ["0x0", "0xf", "0xff"];
+        #[cfg(feature = "oz-compat")]
+        let expected = ["0x00", "0x0f", "0xff"];
+        for (value, expected) in [0u64, 0xf, 0xff].into_iter().zip(expected) {
+            assert_eq!(U256Fmt::of(U256::from(value)).hex().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_incompatible_combinations() {
+        let v = U256::from(1);
+        assert_eq!(
+            U256Fmt::of(v).uppercase().try_to_string(),
+            Err(FormatError::HexOnlyOption("uppercase"))
+        );
+        assert_eq!(
+            U256Fmt::of(v).decimal().prefixed(true).try_to_string(),
+            Err(FormatError::HexOnlyOption("prefixed"))
+        );
+        assert_eq!(
+            U256Fmt::of(v).hex().separators(',').try_to_string(),
+            Err(FormatError::DecimalOnlyOption("separators"))
+        );
+        assert_eq!(
+            U256Fmt::of(v).hex().compact_above(v).try_to_string(),
+            Err(FormatError::DecimalOnlyOption("compact_above"))
+        );
+        assert_eq!(
+            U256Fmt::of(v).grouped(3, '_').separators(',').try_to_string(),
+            Err(FormatError::ConflictingOptions("grouped", "separators"))
+        );
+        assert_eq!(
+            U256Fmt::of(v).grouped(2, ' ').compact_above(v).try_to_string(),
+            Err(FormatError::ConflictingOptions("grouped", "compact_above"))
+        );
+        assert_eq!(
+            U256Fmt::of(v).width(8).compact_above(v).try_to_string(),
+            Err(FormatError::ConflictingOptions("compact_above", "width"))
+        );
+        assert_eq!(
+            U256Fmt::of(v).grouped(0, '_').try_to_string(),
+            Err(FormatError::InvalidGroupSize)
+        );
+    }
+
+    #[test]
+    fn test_display_marks_invalid_options() {
+        let builder = U256Fmt::of(U256::from(0xdeadbeefu64)).uppercase();
+        assert!(builder.try_to_string().is_err());
+        assert_eq!(builder.to_string(), "<invalid U256Fmt: `uppercase` only applies to hex output>");
+        assert_eq!(
+            format!("{:>12}", U256Fmt::of(U256::from(255)).hex().separators(',')),
+            "<invalid U256Fmt: `separators` only applies to decimal output>"
+        );
+        assert_eq!(format!("{:>12}", U256Fmt::of(U256::from(255)).hex()), "        0xff");
+    }
+
+    #[test]
+    fn test_last_base_selection_wins() {
+        let v = U256::from(255);
+        assert_eq!(U256Fmt::of(v).hex().decimal().try_to_string().unwrap(), "255");
+        assert_eq!(U256Fmt::of(v).decimal().hex().try_to_string().unwrap(), "0xff");
+    }
+
+    #[test]
+    fn test_to_compact_string() {
+        assert_eq!(to_compact_string(U256::from(999)), "999");
+        assert_eq!(to_compact_string(U256::from(1000)), "1K");
+        assert_eq!(to_compact_string(U256::from(1_500_000)), "1.5M");
+        assert_eq!(to_compact_string(U256::from(12_345_678)), "12.3M");
+        assert_eq!(to_compact_string(U256::from(999_999_999u64)), "999.9M");
+        assert_eq!(to_compact_string(U256::from(999_999_999_999_999u64)), "999.9T");
+        assert_eq!(to_compact_string(U256::from(1_000_000_000_000_000u64)), "1e15");
+        assert_eq!(to_compact_string(U256::from(2_500_000_000_000_000u64)), "2.5e15");
+        assert_eq!(to_compact_string(U256::MAX), "1.1e77");
+    }
+
+    #[test]
+    fn test_group_digits() {
+        assert_eq!(group_digits("1234567", 3, ','), "1,234,567");
+        assert_eq!(group_digits("123", 3, ','), "123");
+        assert_eq!(group_digits("", 3, ','), "");
+    }
+}
@@ -0,0 +1,319 @@
+//! Length-prefixed framing for passing several strings in one `bytes` blob.
+//!
+//! The format is a u32 big-endian string count followed, per string, by a
+//! u32 big-endian byte length and the UTF-8 bytes. It is not ABI encoding:
+//! it is compact and cheap to decode, but only this crate understands it.
+//! Use it between your own Stylus contracts; use ABI-encoded `string[]`
+//! when Solidity contracts or off-chain tools must read the data.
+//!
+//! `split_message` and `join_message` handle the text case instead: a long
+//! string emitted across several events, each chunk led by a "[i/n]"
+//! header so readers can reassemble it in any order.
+
+/// Size of the count and length prefixes in bytes
+const PREFIX: usize = 4;
+
+/// Error types for decoding framed strings and joining message chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameError {
+    /// Input ended at `offset` where `needed` more bytes were expected
+    Truncated { offset: usize, needed: usize },
+    /// String starting at byte `offset` is not valid UTF-8
+    InvalidUtf8 { offset: usize },
+    /// Input continues past the last string, from byte `offset`
+    TrailingBytes { offset: usize },
+    /// Input chunk at position `chunk` has no valid "[i/n]" header
+    InvalidHeader { chunk: usize },
+    /// Input chunk at position `chunk` disagrees with the first on `n`
+    InconsistentTotal { chunk: usize },
+    /// More than one chunk claims header index `index`
+    DuplicateChunk { index: usize },
+    /// No chunk has header index `index`
+    MissingChunk { index: usize },
+}
+
+/// Encodes `parts` as a count followed by length-prefixed UTF-8 strings.
+///
+/// Panics if there are more than `u32::MAX` parts or a part is longer than
+/// `u32::MAX` bytes, neither of which fits in contract memory.
+pub fn encode_strings(parts: &[&str]) -> Vec<u8> {
+    let total = PREFIX + parts.iter().map(|part| PREFIX + part.len()).sum::<usize>();
+    let mut result = Vec::with_capacity(total);
+    result.extend_from_slice(&prefix(parts.len()));
+    for part in parts {
+        result.extend_from_slice(&prefix(part.len()));
+        result.extend_from_slice(part.as_bytes());
+    }
+    result
+}
+
+/// Decodes the output of `encode_strings`.
+///
+/// Every length is checked against the remaining input before it is used,
+/// so truncated or hostile input returns an error rather than panicking or
+/// allocating for a forged count.
+pub fn decode_strings(data: &[u8]) -> Result<Vec<String>, FrameError> {
+    let mut offset = 0;
+    let count = read_prefix(data, &mut offset)?;
+    // Each string needs at least its prefix, which bounds a forged count
+    let mut result = Vec::with_capacity(count.min(data.len() / PREFIX));
+    for _ in 0..count {
+        let len = read_prefix(data, &mut offset)?;
+        let bytes = take(data, offset, len)?;
+        let s = core::str::from_utf8(bytes).map_err(|_| FrameError::InvalidUtf8 { offset })?;
+        result.push(s.to_string());
+        offset += len;
+    }
+    if offset != data.len() {
+        return Err(FrameError::TrailingBytes { offset });
+    }
+    Ok(result)
+}
+
+/// Splits `s` into chunks of at most `max_bytes` bytes, each starting with
+/// a "[i/n]" header (1-based `i`, `n` chunks in total) that counts toward
+/// the limit.
+///
+/// Chunks break only on UTF-8 character boundaries. The header is always
+/// added, even when `s` fits in a single chunk ("[1/1]…") or is empty, so
+/// readers never need to guess whether a message was split.
+///
+/// Panics if `max_bytes` cannot hold a header plus one character.
+pub fn split_message(s: &str, max_bytes: usize) -> Vec<String> {
+    // Longer headers can only add chunks, so this settles from below
+    let mut total = 1;
+    loop {
+        let chunks = split_with_total(s, max_bytes, total);
+        if chunks.len() == total {
+            return chunks;
+        }
+        total = chunks.len();
+    }
+}
+
+/// Reassembles the output of `split_message`, in any order.
+///
+/// Every chunk must carry a header with the same `n`, and indices `1..=n`
+/// must each appear exactly once.
+pub fn join_message(chunks: &[&str]) -> Result<String, FrameError> {
+    let mut total = None;
+    let mut parts = Vec::with_capacity(chunks.len());
+    for (chunk, text) in chunks.iter().enumerate() {
+        let (index, n, body) = parse_chunk_header(text).ok_or(FrameError::InvalidHeader { chunk })?;
+        if index == 0 || index > n {
+            return Err(FrameError::InvalidHeader { chunk });
+        }
+        if *total.get_or_insert(n) != n {
+            return Err(FrameError::InconsistentTotal { chunk });
+        }
+        parts.push((index, body));
+    }
+
+    // Sorting instead of indexing by `n` avoids allocating for a forged total
+    parts.sort_by_key(|&(index, _)| index);
+    let mut result = String::new();
+    let mut expected = 1;
+    for (index, body) in parts {
+        if index < expected {
+            return Err(FrameError::DuplicateChunk { index });
+        }
+        if index > expected {
+            return Err(FrameError::MissingChunk { index: expected });
+        }
+        result.push_str(body);
+        expected += 1;
+    }
+    if expected <= total.unwrap_or(1) {
+        return Err(FrameError::MissingChunk { index: expected });
+    }
+    Ok(result)
+}
+
+fn split_with_total(s: &str, max_bytes: usize, total: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut rest = s;
+    loop {
+        let header = format!("[{}/{}]", chunks.len() + 1, total);
+        let mut end = max_bytes.saturating_sub(header.len()).min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        assert!(
+            header.len() <= max_bytes && (end > 0 || rest.is_empty()),
+            "max_bytes too small for a chunk header and one character"
+        );
+        chunks.push(header + &rest[..end]);
+        rest = &rest[end..];
+        if rest.is_empty() {
+            return chunks;
+        }
+    }
+}
+
+/// Splits "[i/n]body" into its parts; the numbers must be plain digits.
+fn parse_chunk_header(chunk: &str) -> Option<(usize, usize, &str)> {
+    let (header, body) = chunk.strip_prefix('[')?.split_once(']')?;
+    let (index, total) = header.split_once('/')?;
+    let number = |digits: &str| {
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse::<usize>().ok()
+    };
+    Some((number(index)?, number(total)?, body))
+}
+
+fn prefix(len: usize) -> [u8; PREFIX] {
+    u32::try_from(len).expect("length exceeds u32::MAX").to_be_bytes()
+}
+
+fn read_prefix(data: &[u8], offset: &mut usize) -> Result<usize, FrameError> {
+    let bytes = take(data, *offset, PREFIX)?;
+    *offset += PREFIX;
+    Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+}
+
+fn take(data: &[u8], offset: usize, len: usize) -> Result<&[u8], FrameError> {
+    let remaining = data.len() - offset;
+    if len > remaining {
+        return Err(FrameError::Truncated { offset: data.len(), needed: len - remaining });
+    }
+    Ok(&data[offset..offset + len])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARTS: [&str; 3] = ["alice", "", "bob ✓"];
+
+    #[test]
+    fn test_encoding_layout() {
+        let data = encode_strings(&["ab", "c"]);
+        assert_eq!(data, [0, 0, 0, 2, 0, 0, 0, 2, b'a', b'b', 0, 0, 0, 1, b'c']);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data = encode_strings(&PARTS);
+        assert_eq!(decode_strings(&data).unwrap(), PARTS);
+    }
+
+    #[test]
+    fn test_empty_list() {
+        let data = encode_strings(&[]);
+        assert_eq!(data, [0, 0, 0, 0]);
+        assert_eq!(decode_strings(&data).unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_embedded_nul() {
+        let parts = ["a\0b", "\0", "end\0"];
+        assert_eq!(decode_strings(&encode_strings(&parts)).unwrap(), parts);
+    }
+
+    #[test]
+    fn test_every_truncation_is_an_error() {
+        let data = encode_strings(&PARTS);
+        for len in 0..data.len() {
+            assert!(
+                matches!(decode_strings(&data[..len]), Err(FrameError::Truncated { offset, .. }) if offset == len),
+                "truncated at {}",
+                len
+            );
+        }
+        assert_eq!(decode_strings(&data[..6]), Err(FrameError::Truncated { offset: 6, needed: 2 }));
+        assert_eq!(decode_strings(&data[..10]), Err(FrameError::Truncated { offset: 10, needed: 3 }));
+    }
+
+    #[test]
+    fn test_trailing_bytes() {
+        let mut data = encode_strings(&PARTS);
+        let end = data.len();
+        data.push(0);
+        assert_eq!(decode_strings(&data), Err(FrameError::TrailingBytes { offset: end }));
+    }
+
+    #[test]
+    fn test_forged_lengths() {
+        // Count claims four billion strings
+        assert_eq!(decode_strings(&[0xff; 4]), Err(FrameError::Truncated { offset: 4, needed: 4 }));
+        // One string claiming more bytes than remain
+        let data = [0, 0, 0, 1, 0xff, 0xff, 0xff, 0xff, b'a'];
+        assert_eq!(decode_strings(&data), Err(FrameError::Truncated { offset: 9, needed: 0xffff_ffff - 1 }));
+    }
+
+    #[test]
+    fn test_single_chunk_keeps_header() {
+        assert_eq!(split_message("gm", 64), ["[1/1]gm"]);
+        assert_eq!(split_message("", 5), ["[1/1]"]);
+        assert_eq!(join_message(&["[1/1]gm"]).unwrap(), "gm");
+        assert_eq!(join_message(&["[1/1]"]).unwrap(), "");
+    }
+
+    #[test]
+    fn test_split_respects_limit_and_boundaries() {
+        // "é" is two bytes; a 9-byte limit leaves 4 body bytes per chunk
+        let s = "aéé€b";
+        let chunks = split_message(s, 9);
+        assert_eq!(chunks, ["[1/3]aé", "[2/3]é", "[3/3]€b"]);
+        let chunks = split_message(s, 10);
+        assert_eq!(chunks, ["[1/2]aéé", "[2/2]€b"]);
+        for max in 8..24 {
+            let chunks = split_message(s, max);
+            assert!(chunks.iter().all(|c| c.len() <= max), "limit {}", max);
+            let refs: Vec<&str> = chunks.iter().map(String::as_str).collect();
+            assert_eq!(join_message(&refs).unwrap(), s);
+        }
+    }
+
+    #[test]
+    fn test_split_header_width_grows() {
+        // Ten chunks need two-digit headers, which shrinks every body
+        let s = "x".repeat(40);
+        let chunks = split_message(&s, 9);
+        assert_eq!(chunks.len(), 16);
+        assert_eq!(chunks[0], "[1/16]xxx");
+        assert_eq!(chunks[9], "[10/16]xx");
+        assert_eq!(chunks[15], "[16/16]x");
+        assert!(chunks.iter().all(|c| c.len() <= 9));
+    }
+
+    #[test]
+    #[should_panic(expected = "max_bytes too small")]
+    fn test_split_limit_too_small() {
+        split_message("€", 7);
+    }
+
+    #[test]
+    fn test_join_shuffled() {
+        let s = "a long report that needs several events ✓";
+        let chunks = split_message(s, 12);
+        let mut refs: Vec<&str> = chunks.iter().map(String::as_str).collect();
+        refs.reverse();
+        refs.swap(0, 2);
+        assert_eq!(join_message(&refs).unwrap(), s);
+    }
+
+    #[test]
+    fn test_join_errors() {
+        assert_eq!(join_message(&["[1/3]a", "[3/3]c"]), Err(FrameError::MissingChunk { index: 2 }));
+        assert_eq!(join_message(&["[2/2]b"]), Err(FrameError::MissingChunk { index: 1 }));
+        assert_eq!(join_message(&["[1/2]a"]), Err(FrameError::MissingChunk { index: 2 }));
+        assert_eq!(join_message(&[]), Err(FrameError::MissingChunk { index: 1 }));
+        assert_eq!(join_message(&["[1/2]a", "[1/2]a", "[2/2]b"]), Err(FrameError::DuplicateChunk { index: 1 }));
+        assert_eq!(join_message(&["[1/2]a", "[2/3]b"]), Err(FrameError::InconsistentTotal { chunk: 1 }));
+        assert_eq!(join_message(&["[1/2]a", "b"]), Err(FrameError::InvalidHeader { chunk: 1 }));
+        assert_eq!(join_message(&["[0/1]a"]), Err(FrameError::InvalidHeader { chunk: 0 }));
+        assert_eq!(join_message(&["[3/2]a"]), Err(FrameError::InvalidHeader { chunk: 0 }));
+        assert_eq!(join_message(&["[+1/1]a"]), Err(FrameError::InvalidHeader { chunk: 0 }));
+        // A forged total is reported, not allocated for
+        assert_eq!(join_message(&["[1/4000000000]a"]), Err(FrameError::MissingChunk { index: 2 }));
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let data = [0, 0, 0, 1, 0, 0, 0, 2, 0xc3, 0x28];
+        assert_eq!(decode_strings(&data), Err(FrameError::InvalidUtf8 { offset: 8 }));
+    }
+}
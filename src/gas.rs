@@ -0,0 +1,392 @@
+//! Gas usage reports for benchmarks, as aligned text or Markdown tables.
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::U256;
+
+use crate::fluent::group_digits;
+use crate::progress::format_percent;
+use crate::table::render_table;
+
+/// Row order used when rendering a `GasReport`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GasSort {
+    /// Rows in the order they were added
+    #[default]
+    Insertion,
+    /// Largest gas first; rows with equal gas keep insertion order
+    GasDesc,
+}
+
+/// One measured value: absolute gas used, or a change between two runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GasEntry {
+    Used(u64),
+    Delta(i128),
+}
+
+impl GasEntry {
+    fn value(self) -> i128 {
+        match self {
+            GasEntry::Used(gas) => gas as i128,
+            GasEntry::Delta(delta) => delta,
+        }
+    }
+}
+
+/// Label of the summary row
+const TOTAL_LABEL: &str = "total";
+
+/// Collects labelled gas measurements and renders them as a report.
+///
+/// Rows are added with the chaining `add` and `add_delta`; `render` and
+/// `render_markdown` show each row with thousands separators and as a
+/// percentage of the largest row, followed by the total. Deltas are always
+/// signed, e.g. "+300" or "-1,200", so improvements stand out.
+///
+/// Labels are unique: adding a label again replaces its row in place, so
+/// the total always matches the rows shown. The summary row is always last
+/// and summed from every row, never looked up by label, so a measurement
+/// labelled "total" is an ordinary row.
+#[derive(Debug, Clone, Default)]
+pub struct GasReport {
+    rows: Vec<(String, GasEntry)>,
+    sort: GasSort,
+}
+
+impl GasReport {
+    /// Creates an empty report in insertion order.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a row for `gas` used, replacing any row with the same label.
+    pub fn add(self, label: &str, gas: u64) -> Self {
+        self.insert(label, GasEntry::Used(gas))
+    }
+
+    /// Adds a row for the change from `before` to `after`, replacing any
+    /// row with the same label.
+    pub fn add_delta(self, label: &str, before: u64, after: u64) -> Self {
+        self.insert(label, GasEntry::Delta(after as i128 - before as i128))
+    }
+
+    /// Sets the row for `label`, keeping the position of an existing one
+    fn insert(mut self, label: &str, entry: GasEntry) -> Self {
+        match self.rows.iter_mut().find(|(existing, _)| existing == label) {
+            Some(row) => row.1 = entry,
+            None => self.rows.push((label.to_string(), entry)),
+        }
+        self
+    }
+
+    /// Sets the row order used by the renderers.
+    pub fn sort(mut self, sort: GasSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Renders the report as an aligned plain-text table:
+    ///
+    /// ```text
+    /// label         gas  % of max
+    /// mint      120,000      100%
+    /// transfer   45,000     37.5%
+    /// total     165,000
+    /// ```
+    pub fn render(&self) -> String {
+        render_table(&self.cells())
+    }
+
+    /// Renders the report as a Markdown table with right-aligned numbers
+    /// and a bold total row.
+    pub fn render_markdown(&self) -> String {
+        let cells = self.cells();
+        let mut lines = Vec::with_capacity(cells.len() + 1);
+        for (index, row) in cells.iter().enumerate() {
+            let mut row = row.clone();
+            if index == cells.len() - 1 {
+                row = row.iter().map(|cell| format!("**{}**", cell)).collect();
+                row.push(String::new());
+            }
+            lines.push(format!("| {} |", row.join(" | ")));
+            if index == 0 {
+                lines.push("| --- | ---: | ---: |".to_string());
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Compares this report against `baseline`, matching rows by label:
+    ///
+    /// ```text
+    /// label     before    after   delta   change
+    /// approve   24,600                   removed
+    /// mint     120,000  118,000  -2,000   -1.67%
+    /// permit             31,000              new
+    /// total    144,600  149,000  +4,400   +3.04%
+    /// ```
+    ///
+    /// Rows are sorted by label so the output is deterministic. Changes are
+    /// relative to the baseline, signed and rounded half up to two decimals,
+    /// and "n/a" when the baseline is zero. The final row compares the
+    /// report totals.
+    pub fn diff(&self, baseline: &GasReport) -> String {
+        let before = baseline.by_label();
+        let after = self.by_label();
+        let mut labels: Vec<&str> = before.keys().chain(after.keys()).copied().collect();
+        labels.sort_unstable();
+        labels.dedup();
+
+        let mut cells = vec![["label", "before", "after", "delta", "change"].map(String::from).to_vec()];
+        for label in labels {
+            let row = match (before.get(label), after.get(label)) {
+                (Some(&old), Some(&new)) => compare_row(label, old, new),
+                (Some(&old), None) => {
+                    vec![label.to_string(), format_gas(old, false), String::new(), String::new(), "removed".to_string()]
+                }
+                (None, Some(&new)) => {
+                    vec![label.to_string(), String::new(), format_gas(new, false), String::new(), "new".to_string()]
+                }
+                (None, None) => unreachable!("labels come from one of the reports"),
+            };
+            cells.push(row);
+        }
+        cells.push(compare_row(TOTAL_LABEL, baseline.total(), self.total()));
+        render_table(&cells)
+    }
+
+    /// Value recorded for each label
+    fn by_label(&self) -> BTreeMap<&str, i128> {
+        self.rows.iter().map(|(label, entry)| (label.as_str(), entry.value())).collect()
+    }
+
+    /// Sum of all rows
+    fn total(&self) -> i128 {
+        self.rows.iter().map(|(_, entry)| entry.value()).sum()
+    }
+
+    /// Rows in display order
+    fn ordered(&self) -> Vec<&(String, GasEntry)> {
+        let mut rows: Vec<_> = self.rows.iter().collect();
+        if self.sort == GasSort::GasDesc {
+            rows.sort_by(|a, b| b.1.value().cmp(&a.1.value()));
+        }
+        rows
+    }
+
+    /// Header, one row per entry and the total, as table cells
+    fn cells(&self) -> Vec<Vec<String>> {
+        let max = self.rows.iter().map(|(_, entry)| entry.value().unsigned_abs()).max().unwrap_or(0);
+        let has_deltas = self.rows.iter().any(|(_, entry)| matches!(entry, GasEntry::Delta(_)));
+
+        let mut cells = vec![vec!["label".to_string(), "gas".to_string(), "% of max".to_string()]];
+        for (label, entry) in self.ordered() {
+            let value = entry.value();
+            let percent = if max == 0 {
+                "0%".to_string()
+            } else {
+                format_percent(U256::from(value.unsigned_abs()), U256::from(max))
+            };
+            let gas = format_gas(value, matches!(entry, GasEntry::Delta(_)));
+            cells.push(vec![label.clone(), gas, percent]);
+        }
+        cells.push(vec![TOTAL_LABEL.to_string(), format_gas(self.total(), has_deltas)]);
+        cells
+    }
+}
+
+/// Before, after, delta and percentage change cells for one label
+fn compare_row(label: &str, before: i128, after: i128) -> Vec<String> {
+    let delta = after - before;
+    vec![
+        label.to_string(),
+        format_gas(before, false),
+        format_gas(after, false),
+        format_gas(delta, true),
+        format_change(delta, before),
+    ]
+}
+
+/// Formats `delta / base` as a signed percentage with two decimals,
+/// rounded half up in magnitude, e.g. "+3.04%"; "n/a" for a zero base.
+fn format_change(delta: i128, base: i128) -> String {
+    if base == 0 {
+        return "n/a".to_string();
+    }
+    let base = base.unsigned_abs();
+    let hundredths = (delta.unsigned_abs() * 10_000 + base / 2) / base;
+    let sign = match delta {
+        d if d > 0 => "+",
+        d if d < 0 => "-",
+        _ => "",
+    };
+    format!("{}{}.{:02}%", sign, hundredths / 100, hundredths % 100)
+}
+
+/// Groups the digits of `value`; negative values are always signed, and
+/// positive ones get a "+" when `signed`.
+fn format_gas(value: i128, signed: bool) -> String {
+    let digits = group_digits(&value.unsigned_abs().to_string(), 3, ',');
+    if value < 0 {
+        format!("-{}", digits)
+    } else if signed && value > 0 {
+        format!("+{}", digits)
+    } else {
+        digits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> GasReport {
+        GasReport::new().add("transfer", 45_000).add("mint", 120_000).add("approve", 24_600)
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            report().render(),
+            "label         gas  % of max\n\
+             transfer   45,000     37.5%\n\
+             mint      120,000      100%\n\
+             approve    24,600     20.5%\n\
+             total     189,600"
+        );
+    }
+
+    #[test]
+    fn test_render_sorted_by_gas() {
+        let rendered = report().sort(GasSort::GasDesc).render();
+        let labels: Vec<&str> = rendered.lines().map(|line| line.split(' ').next().unwrap()).collect();
+        assert_eq!(labels, ["label", "mint", "transfer", "approve", "total"]);
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        assert_eq!(
+            report().render_markdown(),
+            "| label | gas | % of max |\n\
+             | --- | ---: | ---: |\n\
+             | transfer | 45,000 | 37.5% |\n\
+             | mint | 120,000 | 100% |\n\
+             | approve | 24,600 | 20.5% |\n\
+             | **total** | **189,600** |  |"
+        );
+    }
+
+    #[test]
+    fn test_deltas_are_signed() {
+        let rendered = GasReport::new()
+            .add_delta("transfer", 45_000, 43_800)
+            .add_delta("mint", 120_000, 120_300)
+            .add_delta("burn", 30_000, 30_000)
+            .render();
+        assert_eq!(
+            rendered,
+            "label        gas  % of max\n\
+             transfer  -1,200      100%\n\
+             mint        +300       25%\n\
+             burn           0        0%\n\
+             total       -900"
+        );
+    }
+
+    #[test]
+    fn test_repeated_label_replaces_row() {
+        let rendered = report().add("mint", 100_000).add("approve", 20_000).render();
+        assert_eq!(
+            rendered,
+            "label         gas  % of max\n\
+             transfer   45,000       45%\n\
+             mint      100,000      100%\n\
+             approve    20,000       20%\n\
+             total     165,000"
+        );
+        let baseline = GasReport::new().add("mint", 120_000).add("mint", 110_000);
+        assert!(GasReport::new().add("mint", 110_000).diff(&baseline).ends_with("total  110,000  110,000      0   0.00%"));
+    }
+
+    #[test]
+    fn test_total_label_is_an_ordinary_row() {
+        assert_eq!(
+            GasReport::new().add("total", 1_000).add("mint", 500).render(),
+            "label    gas  % of max\n\
+             total  1,000      100%\n\
+             mint     500       50%\n\
+             total  1,500"
+        );
+        let baseline = GasReport::new().add("total", 1_000);
+        assert_eq!(
+            GasReport::new().add("total", 900).add("mint", 500).diff(&baseline),
+            "label  before  after  delta   change\n\
+             mint             500             new\n\
+             total   1,000    900   -100  -10.00%\n\
+             total   1,000  1,400   +400  +40.00%"
+        );
+    }
+
+    #[test]
+    fn test_empty_report() {
+        assert_eq!(GasReport::new().render(), "label  gas  % of max\ntotal    0");
+        assert_eq!(
+            GasReport::new().render_markdown(),
+            "| label | gas | % of max |\n| --- | ---: | ---: |\n| **total** | **0** |  |"
+        );
+    }
+
+    #[test]
+    fn test_diff_identical_reports() {
+        assert_eq!(
+            report().diff(&report()),
+            "label      before    after  delta  change\n\
+             approve    24,600   24,600      0   0.00%\n\
+             mint      120,000  120,000      0   0.00%\n\
+             transfer   45,000   45,000      0   0.00%\n\
+             total     189,600  189,600      0   0.00%"
+        );
+    }
+
+    #[test]
+    fn test_diff_improvement() {
+        let optimized = GasReport::new().add("transfer", 43_800).add("mint", 120_000).add("approve", 24_600);
+        let diff = optimized.diff(&report());
+        let lines: Vec<&str> = diff.lines().collect();
+        assert!(lines[3].starts_with("transfer   45,000   43,800") && lines[3].ends_with("-1,200  -2.67%"));
+        assert!(lines[4].ends_with("-1,200  -0.63%"));
+    }
+
+    #[test]
+    fn test_diff_regression() {
+        let slower = GasReport::new().add("mint", 130_000);
+        let diff = slower.diff(&GasReport::new().add("mint", 120_000));
+        assert_eq!(
+            diff,
+            "label   before    after    delta  change\n\
+             mint   120,000  130,000  +10,000  +8.33%\n\
+             total  120,000  130,000  +10,000  +8.33%"
+        );
+    }
+
+    #[test]
+    fn test_diff_disjoint_labels() {
+        let current = GasReport::new().add("mint", 118_000).add("permit", 31_000);
+        let baseline = GasReport::new().add("mint", 120_000).add("approve", 24_600);
+        assert_eq!(
+            current.diff(&baseline),
+            "label     before    after   delta   change\n\
+             approve   24,600                   removed\n\
+             mint     120,000  118,000  -2,000   -1.67%\n\
+             permit             31,000              new\n\
+             total    144,600  149,000  +4,400   +3.04%"
+        );
+    }
+
+    #[test]
+    fn test_diff_zero_baseline() {
+        let diff = GasReport::new().add("noop", 5).diff(&GasReport::new().add("noop", 0));
+        assert!(diff.lines().nth(1).unwrap().ends_with("+5     n/a"));
+    }
+}
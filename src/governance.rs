@@ -0,0 +1,262 @@
+//! Governance summaries: vote tallies and quorum progress.
+
+use alloy_primitives::{U256, U512};
+
+use crate::format_relative;
+use crate::progress::format_percent;
+use crate::time::timestamp_label;
+use crate::units::format_grouped_units;
+
+/// Window after the ETA during which a queued proposal can be executed,
+/// as in Compound's `Timelock.GRACE_PERIOD`
+const GRACE_PERIOD: u64 = 14 * 86_400;
+
+/// Formats a vote tally as one line per bucket plus a quorum line:
+///
+/// ```text
+/// for: 600 (60.0%)
+/// against: 300 (30.0%)
+/// abstain: 100 (10.0%)
+/// quorum: 700 / 500 (140%) reached
+/// ```
+///
+/// Bucket percentages are shares of all votes cast with one decimal place,
+/// apportioned by the largest-remainder method so they always sum to exactly
+/// 100.0% (ties go to the earlier bucket). With no votes cast every bucket
+/// is 0.0%.
+///
+/// As in OpenZeppelin's `GovernorCountingSimple`, quorum counts `for` and
+/// `abstain` votes. A zero quorum is always reached and has no percentage.
+pub fn format_tally(for_votes: U256, against: U256, abstain: U256, quorum: U256, decimals: u8) -> String {
+    let votes = [for_votes, against, abstain];
+    let shares = apportion_per_mille(&votes);
+
+    let mut lines: Vec<String> = ["for", "against", "abstain"]
+        .iter()
+        .zip(votes.iter().zip(shares))
+        .map(|(label, (&amount, share))| {
+            format!(
+                "{}: {} ({}.{}%)",
+                label,
+                format_grouped_units(amount, decimals, 0),
+                share / 10,
+                share % 10
+            )
+        })
+        .collect();
+
+    let participation = for_votes.saturating_add(abstain);
+    let status = if participation >= quorum { "reached" } else { "not reached" };
+    let progress = format!(
+        "{} / {}",
+        format_grouped_units(participation, decimals, 0),
+        format_grouped_units(quorum, decimals, 0)
+    );
+    if quorum.is_zero() {
+        lines.push(format!("quorum: {} {}", progress, status));
+    } else {
+        lines.push(format!("quorum: {} ({}) {}", progress, format_percent(participation, quorum), status));
+    }
+    lines.join("\n")
+}
+
+/// Describes where a proposal is in its lifecycle, e.g.
+/// "voting ends in 2d 4h; execution earliest 2024-07-02T12:00:00Z".
+///
+/// The state is inferred from the timestamps with OpenZeppelin `Governor`
+/// boundaries: a proposal is pending while `now <= vote_start` and active
+/// while `now <= vote_end`. After voting, a proposal without an `eta` has
+/// simply ended; with one it is queued while `now < eta`, executable from
+/// `eta` until the 14-day grace period has passed, and expired from then on.
+///
+/// | state      | output                                                 |
+/// |------------|--------------------------------------------------------|
+/// | pending    | "pending; created 2h ago, voting starts in 1d"         |
+/// | active     | "voting ends in 2d 4h" (+ "; execution earliest …")    |
+/// | ended      | "voting ended 3h ago"                                  |
+/// | queued     | "queued; execution earliest … (in 1d)"                 |
+/// | executable | "executable since …; expires in 13d 23h"               |
+/// | expired    | "expired 2d ago"                                       |
+pub fn format_proposal_timeline(
+    created: U256,
+    vote_start: U256,
+    vote_end: U256,
+    eta: Option<U256>,
+    now: U256,
+) -> String {
+    if now <= vote_start {
+        return format!(
+            "pending; created {}, voting starts {}",
+            format_relative(created, now),
+            format_relative(vote_start, now)
+        );
+    }
+    if now <= vote_end {
+        let voting = format!("voting ends {}", format_relative(vote_end, now));
+        return match eta {
+            Some(eta) => format!("{}; execution earliest {}", voting, timestamp_label(eta)),
+            None => voting,
+        };
+    }
+
+    let Some(eta) = eta else {
+        return format!("voting ended {}", format_relative(vote_end, now));
+    };
+    let expiry = eta.saturating_add(U256::from(GRACE_PERIOD));
+    if now < eta {
+        format!("queued; execution earliest {} ({})", timestamp_label(eta), format_relative(eta, now))
+    } else if now < expiry {
+        format!("executable since {}; expires {}", timestamp_label(eta), format_relative(expiry, now))
+    } else {
+        format!("expired {}", format_relative(expiry, now))
+    }
+}
+
+/// Splits 1000 per-mille among `values` by the largest-remainder method.
+fn apportion_per_mille(values: &[U256; 3]) -> [u16; 3] {
+    let total = values.iter().fold(U512::ZERO, |sum, &v| sum + U512::from(v));
+    if total.is_zero() {
+        return [0; 3];
+    }
+
+    let mut shares = [0u16; 3];
+    let mut remainders = [U512::ZERO; 3];
+    for (i, &value) in values.iter().enumerate() {
+        let scaled = U512::from(value) * U512::from(1000);
+        shares[i] = (scaled / total).to::<u16>();
+        remainders[i] = scaled % total;
+    }
+
+    let mut order = [0, 1, 2];
+    // Stable sort keeps earlier buckets first among equal remainders
+    order.sort_by(|&a, &b| remainders[b].cmp(&remainders[a]));
+    let missing = 1000 - shares.iter().sum::<u16>();
+    for &i in order.iter().take(missing as usize) {
+        shares[i] += 1;
+    }
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_string;
+
+    fn votes(value: u64) -> U256 {
+        U256::from(value)
+    }
+
+    #[test]
+    fn test_typical_tally() {
+        let tally = format_tally(votes(600), votes(300), votes(100), votes(500), 0);
+        assert_eq!(
+            tally,
+            "for: 600 (60.0%)\nagainst: 300 (30.0%)\nabstain: 100 (10.0%)\nquorum: 700 / 500 (140%) reached"
+        );
+    }
+
+    #[test]
+    fn test_tally_with_decimals_not_reached() {
+        let e18 = U256::from(10).pow(U256::from(18));
+        let tally = format_tally(votes(1_250) * e18, votes(2_500) * e18, U256::ZERO, votes(4_000) * e18, 18);
+        assert_eq!(
+            tally,
+            "for: 1,250 (33.3%)\nagainst: 2,500 (66.7%)\nabstain: 0 (0.0%)\nquorum: 1,250 / 4,000 (31.2%) not reached"
+        );
+    }
+
+    #[test]
+    fn test_all_zero_tally() {
+        assert_eq!(
+            format_tally(U256::ZERO, U256::ZERO, U256::ZERO, U256::ZERO, 18),
+            "for: 0 (0.0%)\nagainst: 0 (0.0%)\nabstain: 0 (0.0%)\nquorum: 0 / 0 reached"
+        );
+        assert!(format_tally(U256::ZERO, U256::ZERO, U256::ZERO, votes(1), 0).ends_with("(0%) not reached"));
+    }
+
+    #[test]
+    fn test_percentages_never_exceed_100() {
+        // Rounding each share independently would give 16.7 + 16.7 + 66.7 = 100.1
+        let tally = format_tally(votes(1), votes(1), votes(4), votes(5), 0);
+        assert_eq!(
+            tally,
+            "for: 1 (16.7%)\nagainst: 1 (16.7%)\nabstain: 4 (66.6%)\nquorum: 5 / 5 (100%) reached"
+        );
+        assert_eq!(apportion_per_mille(&[votes(1), votes(1), votes(1)]), [334, 333, 333]);
+        assert_eq!(apportion_per_mille(&[U256::MAX, U256::MAX, U256::ZERO]), [500, 500, 0]);
+    }
+
+    const DAY: u64 = 86_400;
+    const HOUR: u64 = 3_600;
+    /// 2024-07-02T12:00:00Z
+    const ETA: u64 = 1_719_921_600;
+    const VOTE_END: u64 = ETA - 2 * DAY;
+    const VOTE_START: u64 = VOTE_END - 3 * DAY;
+    const CREATED: u64 = VOTE_START - DAY;
+
+    fn timeline(eta: Option<u64>, now: u64) -> String {
+        format_proposal_timeline(
+            votes(CREATED),
+            votes(VOTE_START),
+            votes(VOTE_END),
+            eta.map(votes),
+            votes(now),
+        )
+    }
+
+    #[test]
+    fn test_timeline_pending() {
+        assert_eq!(timeline(None, CREATED), "pending; created now, voting starts in 1d");
+        assert_eq!(timeline(None, CREATED + 2 * HOUR), "pending; created 2h ago, voting starts in 22h");
+        // Voting has not started at exactly vote_start
+        assert_eq!(timeline(None, VOTE_START), "pending; created 1d ago, voting starts now");
+    }
+
+    #[test]
+    fn test_timeline_active() {
+        assert_eq!(timeline(None, VOTE_START + 1), "voting ends in 2d 23h");
+        assert_eq!(
+            timeline(Some(ETA), VOTE_END - 2 * DAY - 4 * HOUR),
+            "voting ends in 2d 4h; execution earliest 2024-07-02T12:00:00Z"
+        );
+        // Still active at exactly vote_end
+        assert_eq!(timeline(None, VOTE_END), "voting ends now");
+    }
+
+    #[test]
+    fn test_timeline_ended_without_eta() {
+        assert_eq!(timeline(None, VOTE_END + 1), "voting ended 1s ago");
+        assert_eq!(timeline(None, VOTE_END + 3 * HOUR), "voting ended 3h ago");
+    }
+
+    #[test]
+    fn test_timeline_queued() {
+        assert_eq!(
+            timeline(Some(ETA), VOTE_END + 1),
+            "queued; execution earliest 2024-07-02T12:00:00Z (in 1d 23h)"
+        );
+        assert_eq!(
+            timeline(Some(ETA), ETA - 1),
+            "queued; execution earliest 2024-07-02T12:00:00Z (in 1s)"
+        );
+    }
+
+    #[test]
+    fn test_timeline_executable() {
+        assert_eq!(timeline(Some(ETA), ETA), "executable since 2024-07-02T12:00:00Z; expires in 14d");
+        assert_eq!(
+            timeline(Some(ETA), ETA + GRACE_PERIOD - 1),
+            "executable since 2024-07-02T12:00:00Z; expires in 1s"
+        );
+    }
+
+    #[test]
+    fn test_timeline_expired() {
+        assert_eq!(timeline(Some(ETA), ETA + GRACE_PERIOD), "expired now");
+        assert_eq!(timeline(Some(ETA), ETA + GRACE_PERIOD + 2 * DAY), "expired 2d ago");
+        assert_eq!(
+            format_proposal_timeline(U256::ZERO, U256::ZERO, U256::ZERO, Some(U256::MAX), votes(1)),
+            format!("queued; execution earliest timestamp {} ({})", to_string(U256::MAX), format_relative(U256::MAX, votes(1)))
+        );
+    }
+}
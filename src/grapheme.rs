@@ -0,0 +1,136 @@
+//! A practical subset of Unicode grapheme segmentation, enough to avoid
+//! splitting the emoji and accented letters found in names.
+//!
+//! Clusters are kept whole across these boundaries (after UAX #29):
+//! - CR LF
+//! - before an extending character: combining marks (U+0300–036F,
+//!   1AB0–1AFF, 1DC0–1DFF, 20D0–20FF, FE20–FE2F), variation selectors
+//!   (U+FE00–FE0F, E0100–E01EF), emoji skin tone modifiers (U+1F3FB–1F3FF),
+//!   emoji tag characters (U+E0020–E007F) and the zero width joiner
+//! - after a zero width joiner, so ZWJ emoji sequences stay together
+//! - between the two regional indicators (U+1F1E6–1F1FF) of a flag, pairing
+//!   from the start of a run
+//!
+//! Hangul syllable composition, prepend characters and Indic conjuncts are
+//! not covered; such text breaks between characters.
+
+pub(crate) const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+/// Returns the longest prefix of `s` with at most `max_graphemes` grapheme
+/// clusters.
+///
+/// A cluster that does not fit is dropped whole, so a flag or a ZWJ family
+/// emoji is never cut into its parts. For ASCII text this is the same as
+/// truncating to `max_graphemes` characters.
+pub fn truncate_graphemes(s: &str, max_graphemes: usize) -> &str {
+    let mut end = 0;
+    for _ in 0..max_graphemes {
+        if end == s.len() {
+            break;
+        }
+        end += grapheme_len(&s[end..]);
+    }
+    &s[..end]
+}
+
+/// Counts the grapheme clusters in `s`.
+pub(crate) fn grapheme_count(s: &str) -> usize {
+    let mut count = 0;
+    let mut rest = s;
+    while !rest.is_empty() {
+        rest = &rest[grapheme_len(rest)..];
+        count += 1;
+    }
+    count
+}
+
+/// Returns the byte length of the first grapheme cluster of `s`, or 0 if
+/// `s` is empty.
+pub(crate) fn grapheme_len(s: &str) -> usize {
+    let mut chars = s.char_indices();
+    let mut prev = match chars.next() {
+        Some((_, c)) => c,
+        None => return 0,
+    };
+    let mut regional_run = usize::from(is_regional_indicator(prev));
+    for (index, c) in chars {
+        let joined = (prev == '\r' && c == '\n')
+            || is_extend(c)
+            || prev == ZERO_WIDTH_JOINER
+            || (is_regional_indicator(c) && regional_run % 2 == 1);
+        if !joined {
+            return index;
+        }
+        regional_run = if is_regional_indicator(c) { regional_run + 1 } else { 0 };
+        prev = c;
+    }
+    s.len()
+}
+
+fn is_extend(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{E0100}'..='\u{E01EF}'
+            | '\u{1F3FB}'..='\u{1F3FF}'
+            | '\u{E0020}'..='\u{E007F}'
+            | ZERO_WIDTH_JOINER
+    )
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c, '\u{1F1E6}'..='\u{1F1FF}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAMILY: &str = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+
+    #[test]
+    fn test_ascii_matches_char_truncation() {
+        assert_eq!(truncate_graphemes("alice.eth", 5), "alice");
+        assert_eq!(truncate_graphemes("alice.eth", 9), "alice.eth");
+        assert_eq!(truncate_graphemes("alice.eth", 100), "alice.eth");
+        assert_eq!(truncate_graphemes("alice", 0), "");
+        assert_eq!(truncate_graphemes("", 3), "");
+        assert_eq!(grapheme_count("alice"), 5);
+    }
+
+    #[test]
+    fn test_zwj_family_kept_whole_or_dropped() {
+        let name = format!("ab{}c", FAMILY);
+        assert_eq!(truncate_graphemes(&name, 2), "ab");
+        assert_eq!(truncate_graphemes(&name, 3), format!("ab{}", FAMILY));
+        assert_eq!(truncate_graphemes(&name, 4), name);
+        assert_eq!(grapheme_count(&name), 4);
+        assert_eq!(grapheme_len(FAMILY), FAMILY.len());
+    }
+
+    #[test]
+    fn test_flag_pairs() {
+        let flags = "\u{1F1FA}\u{1F1F8}\u{1F1EF}\u{1F1F5}";
+        assert_eq!(truncate_graphemes(flags, 1), "\u{1F1FA}\u{1F1F8}");
+        assert_eq!(grapheme_count(flags), 2);
+        // An odd indicator is a cluster of its own
+        assert_eq!(grapheme_count("\u{1F1FA}\u{1F1F8}\u{1F1EF}"), 2);
+        // Subdivision flags are a black flag followed by tag characters
+        let scotland = "\u{1F3F4}\u{E0067}\u{E0062}\u{E0073}\u{E0063}\u{E0074}\u{E007F}";
+        assert_eq!(grapheme_count(scotland), 1);
+    }
+
+    #[test]
+    fn test_skin_tones_and_combining_marks() {
+        let thumbs = "\u{1F44D}\u{1F3FD}\u{1F44D}";
+        assert_eq!(truncate_graphemes(thumbs, 1), "\u{1F44D}\u{1F3FD}");
+        assert_eq!(truncate_graphemes("e\u{0301}\u{0323}x", 1), "e\u{0301}\u{0323}");
+        assert_eq!(truncate_graphemes("\u{2764}\u{FE0F}!", 1), "\u{2764}\u{FE0F}");
+        assert_eq!(grapheme_count("a\r\nb"), 3);
+    }
+}
@@ -0,0 +1,173 @@
+//! Namespaced global IDs such as "punks:1:0xAb58…:42".
+//!
+//! Segments are joined with `:`. String segments may contain any text:
+//! `%` is written as `%25` and `:` as `%3A`, so the separator never appears
+//! inside a segment and composing then parsing is lossless. Numbers are
+//! decimal and addresses EIP-55 checksummed.
+
+use std::borrow::Cow;
+
+use alloy_primitives::{Address, U256};
+
+use crate::parse::parse_digits;
+use crate::{address_to_checksum_hex_string, to_string, ParseError};
+
+/// Segment separator
+const SEPARATOR: char = ':';
+
+/// One segment of a namespaced ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdPart<'a> {
+    /// Free text, escaped on output
+    Str(Cow<'a, str>),
+    /// Decimal number
+    Num(U256),
+    /// Checksummed address
+    Addr(Address),
+}
+
+/// Expected kind of an ID segment for `parse_id`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdKind {
+    Str,
+    Num,
+    Addr,
+}
+
+/// Joins `parts` with `:`, escaping string segments.
+pub fn compose_id(parts: &[IdPart]) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            IdPart::Str(s) => s.replace('%', "%25").replace(SEPARATOR, "%3A"),
+            IdPart::Num(n) => to_string(*n),
+            IdPart::Addr(addr) => address_to_checksum_hex_string(*addr),
+        })
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Parses an ID composed by `compose_id`, checking each segment against
+/// `schema`.
+///
+/// String segments are unescaped, borrowing from `s` when they contain no
+/// escapes. Numbers must be plain decimal; addresses must be "0x" and 40
+/// hex digits in any case. Error indices refer to the whole input.
+pub fn parse_id<'a>(s: &'a str, schema: &[IdKind]) -> Result<Vec<IdPart<'a>>, ParseError> {
+    let found = s.split(SEPARATOR).count();
+    if found != schema.len() {
+        return Err(ParseError::WrongSegmentCount { expected: schema.len(), found });
+    }
+
+    let mut offset = 0;
+    s.split(SEPARATOR)
+        .zip(schema)
+        .map(|(segment, kind)| {
+            let start = offset;
+            offset += segment.len() + 1;
+            match kind {
+                IdKind::Str => unescape(segment, start).map(IdPart::Str),
+                IdKind::Num => parse_digits(segment, start, 10).map(IdPart::Num),
+                IdKind::Addr => parse_address(segment, start).map(IdPart::Addr),
+            }
+        })
+        .collect()
+}
+
+fn unescape(segment: &str, offset: usize) -> Result<Cow<'_, str>, ParseError> {
+    if !segment.contains('%') {
+        return Ok(Cow::Borrowed(segment));
+    }
+    let mut result = String::with_capacity(segment.len());
+    let mut rest = segment;
+    while let Some(percent) = rest.find('%') {
+        result.push_str(&rest[..percent]);
+        let escaped = match rest.get(percent + 1..percent + 3) {
+            Some("25") => '%',
+            Some("3A" | "3a") => SEPARATOR,
+            _ => {
+                let index = offset + (segment.len() - rest.len()) + percent;
+                return Err(ParseError::InvalidDigit { index, found: '%' });
+            }
+        };
+        result.push(escaped);
+        rest = &rest[percent + 3..];
+    }
+    result.push_str(rest);
+    Ok(Cow::Owned(result))
+}
+
+pub(crate) fn parse_address(segment: &str, offset: usize) -> Result<Address, ParseError> {
+    let digits = segment.strip_prefix("0x").ok_or(ParseError::MissingHexPrefix)?;
+    if digits.len() != 40 {
+        return Err(ParseError::InvalidLength { expected: 40, found: digits.len() });
+    }
+    let value = parse_digits(digits, offset + 2, 16)?;
+    Ok(Address::from_slice(&value.to_be_bytes::<32>()[12..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDR: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    fn addr() -> Address {
+        Address::from_slice(&parse_digits(&ADDR[2..], 0, 16).unwrap().to_be_bytes::<32>()[12..])
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let parts = vec![
+            IdPart::Str("punks".into()),
+            IdPart::Num(U256::from(1)),
+            IdPart::Addr(addr()),
+            IdPart::Num(U256::from(42)),
+        ];
+        let id = compose_id(&parts);
+        assert_eq!(id, format!("punks:1:{}:42", ADDR));
+        let schema = [IdKind::Str, IdKind::Num, IdKind::Addr, IdKind::Num];
+        assert_eq!(parse_id(&id, &schema).unwrap(), parts);
+    }
+
+    #[test]
+    fn test_string_escaping() {
+        let parts = vec![IdPart::Str("a:b%c".into()), IdPart::Str("".into())];
+        let id = compose_id(&parts);
+        assert_eq!(id, "a%3Ab%25c:");
+        assert_eq!(parse_id(&id, &[IdKind::Str, IdKind::Str]).unwrap(), parts);
+        assert_eq!(parse_id("x%3ay", &[IdKind::Str]).unwrap(), vec![IdPart::Str("x:y".into())]);
+        assert!(matches!(parse_id("plain", &[IdKind::Str]).unwrap()[0], IdPart::Str(Cow::Borrowed("plain"))));
+        assert_eq!(parse_id("ab:c%2", &[IdKind::Str, IdKind::Str]), Err(ParseError::InvalidDigit { index: 4, found: '%' }));
+    }
+
+    #[test]
+    fn test_wrong_arity() {
+        assert_eq!(
+            parse_id("a:1", &[IdKind::Str]),
+            Err(ParseError::WrongSegmentCount { expected: 1, found: 2 })
+        );
+        assert_eq!(
+            parse_id("a", &[IdKind::Str, IdKind::Num]),
+            Err(ParseError::WrongSegmentCount { expected: 2, found: 1 })
+        );
+    }
+
+    #[test]
+    fn test_segment_kind_check() {
+        assert_eq!(parse_id("punks:1x", &[IdKind::Str, IdKind::Num]), Err(ParseError::InvalidDigit { index: 7, found: 'x' }));
+        assert_eq!(parse_id("punks:", &[IdKind::Str, IdKind::Num]), Err(ParseError::Empty));
+        assert_eq!(parse_id("1:abc", &[IdKind::Num, IdKind::Addr]), Err(ParseError::MissingHexPrefix));
+        assert_eq!(
+            parse_id("1:0xabc", &[IdKind::Num, IdKind::Addr]),
+            Err(ParseError::InvalidLength { expected: 40, found: 3 })
+        );
+    }
+
+    #[test]
+    fn test_address_checksum_normalized() {
+        let lower = format!("c:{}", ADDR.to_lowercase());
+        let parts = parse_id(&lower, &[IdKind::Str, IdKind::Addr]).unwrap();
+        assert_eq!(compose_id(&parts), format!("c:{}", ADDR));
+    }
+}
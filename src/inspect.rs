@@ -0,0 +1,179 @@
+//! One-line forensic summaries of opaque return or revert data.
+
+use alloy_primitives::U256;
+
+use crate::selector::{format_selector, selector_signature};
+use crate::{bytes_to_hex_string, display_safe, to_string};
+
+const WORD: usize = 32;
+const SELECTOR: usize = 4;
+
+/// Characters of text shown by `summarize_bytes`
+const PREVIEW_CHARS: usize = 48;
+
+/// Bytes shown at each end when the data is too long to show whole
+const EDGE_BYTES: usize = 8;
+
+/// Summarizes arbitrary bytes for logs, e.g.
+/// "100 bytes (selector + 3 words): selector Error(string) [0x08c379a0];
+/// reason "Not owner"; head 0x08c379a000000000, tail 0x0000000000000000".
+///
+/// The checks run from most to least specific:
+/// 1. length, and word alignment (after the selector if one is recognized)
+/// 2. empty or all-zero data stops there, since nothing more is informative
+/// 3. a selector from `COMMON_SELECTORS`, with the reason string decoded
+///    for `Error(string)`
+/// 4. otherwise valid UTF-8 is previewed via `display_safe`, and anything
+///    else is called "binary"
+/// 5. the bytes in hex: all of them up to 16 bytes, else the first and last
+///    8
+pub fn summarize_bytes(data: &[u8]) -> String {
+    let known = data
+        .get(..SELECTOR)
+        .map(|sel| [sel[0], sel[1], sel[2], sel[3]])
+        .filter(|&sel| selector_signature(sel, &[]).is_some());
+
+    let mut result = to_string(U256::from(data.len()));
+    result.push_str(" bytes (");
+    let body = if known.is_some() {
+        result.push_str("selector + ");
+        data.len() - SELECTOR
+    } else {
+        data.len()
+    };
+    if body % WORD == 0 {
+        let words = body / WORD;
+        result.push_str(&to_string(U256::from(words)));
+        result.push_str(if words == 1 { " word" } else { " words" });
+    } else {
+        result.push_str("unaligned");
+    }
+    result.push_str("): ");
+
+    if data.is_empty() {
+        result.push_str("empty");
+        return result;
+    }
+    if data.iter().all(|&b| b == 0) {
+        result.push_str("all zero");
+        return result;
+    }
+
+    if let Some(sel) = known {
+        result.push_str("selector ");
+        result.push_str(&format_selector(sel, &[]));
+        if selector_signature(sel, &[]) == Some("Error(string)") {
+            if let Some(reason) = decode_error_string(&data[SELECTOR..]) {
+                result.push_str("; reason \"");
+                result.push_str(&display_safe(reason, PREVIEW_CHARS));
+                result.push('"');
+            }
+        }
+    } else if let Ok(text) = core::str::from_utf8(data) {
+        result.push_str("UTF-8 \"");
+        result.push_str(&display_safe(text, PREVIEW_CHARS));
+        result.push('"');
+    } else {
+        result.push_str("binary");
+    }
+
+    if data.len() <= 2 * EDGE_BYTES {
+        result.push_str("; hex ");
+        result.push_str(&bytes_to_hex_string(data));
+    } else {
+        result.push_str("; head ");
+        result.push_str(&bytes_to_hex_string(&data[..EDGE_BYTES]));
+        result.push_str(", tail ");
+        result.push_str(&bytes_to_hex_string(&data[data.len() - EDGE_BYTES..]));
+    }
+    result
+}
+
+/// Decodes the ABI `string` argument of an `Error(string)` payload
+fn decode_error_string(args: &[u8]) -> Option<&str> {
+    let offset = read_word(args, 0)?;
+    let len = read_word(args, offset)?;
+    let start = offset.checked_add(WORD)?;
+    let bytes = args.get(start..start.checked_add(len)?)?;
+    core::str::from_utf8(bytes).ok()
+}
+
+/// Reads the word at byte `at` as a usize, if it fits
+fn read_word(data: &[u8], at: usize) -> Option<usize> {
+    let word = data.get(at..at.checked_add(WORD)?)?;
+    if word[..WORD - 8].iter().any(|&b| b != 0) {
+        return None;
+    }
+    usize::try_from(u64::from_be_bytes(word[WORD - 8..].try_into().ok()?)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_payload(reason: &str) -> Vec<u8> {
+        let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+        let mut word = [0u8; WORD];
+        word[WORD - 1] = 0x20;
+        data.extend_from_slice(&word);
+        word[WORD - 1] = reason.len() as u8;
+        data.extend_from_slice(&word);
+        data.extend_from_slice(reason.as_bytes());
+        data.resize(SELECTOR + 2 * WORD + reason.len().div_ceil(WORD) * WORD, 0);
+        data
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(summarize_bytes(&[]), "0 bytes (0 words): empty");
+    }
+
+    #[test]
+    fn test_all_zero_word() {
+        assert_eq!(summarize_bytes(&[0; 32]), "32 bytes (1 word): all zero");
+        assert_eq!(summarize_bytes(&[0; 3]), "3 bytes (unaligned): all zero");
+    }
+
+    #[test]
+    fn test_error_string_revert() {
+        assert_eq!(
+            summarize_bytes(&error_payload("Not owner")),
+            "100 bytes (selector + 3 words): selector Error(string) [0x08c379a0]; \
+             reason \"Not owner\"; head 0x08c379a000000000, tail 0x0000000000000000"
+        );
+        // A reason that would break the log line is escaped
+        let summary = summarize_bytes(&error_payload("bad\nline"));
+        assert!(summary.contains("reason \"bad\\x0Aline\""), "{}", summary);
+        // Truncated payloads still report the selector
+        let data = error_payload("Not owner");
+        assert_eq!(
+            summarize_bytes(&data[..40]),
+            "40 bytes (selector + unaligned): selector Error(string) [0x08c379a0]; \
+             head 0x08c379a000000000, tail 0x0000002000000000"
+        );
+    }
+
+    #[test]
+    fn test_utf8_payload() {
+        assert_eq!(
+            summarize_bytes(b"ipfs://bafy/1.json"),
+            "18 bytes (unaligned): UTF-8 \"ipfs://bafy/1.json\"; head 0x697066733a2f2f62, tail 0x792f312e6a736f6e"
+        );
+        assert_eq!(summarize_bytes("✓ ok".as_bytes()), "6 bytes (unaligned): UTF-8 \"✓ ok\"; hex 0xe29c93206f6b");
+    }
+
+    #[test]
+    fn test_random_bytes() {
+        let data: Vec<u8> = (0u32..64).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8).collect();
+        assert_eq!(
+            summarize_bytes(&data),
+            "64 bytes (2 words): binary; head 0x009e3cda7817b553, tail 0x9c3ad87615b351ef"
+        );
+        assert_eq!(summarize_bytes(&[0xde, 0xad, 0xbe, 0xef]), "4 bytes (unaligned): binary; hex 0xdeadbeef");
+        // Known selector with no arguments
+        assert_eq!(
+            summarize_bytes(&[0x82, 0xb4, 0x29, 0x00]),
+            "4 bytes (selector + 0 words): selector Unauthorized() [0x82b42900]; hex 0x82b42900"
+        );
+    }
+}
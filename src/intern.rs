@@ -0,0 +1,155 @@
+//! Interning of repeated strings into one owned arena.
+
+use alloy_primitives::U256;
+
+use crate::to_string;
+
+/// Handle to a string stored in an `Interner`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+/// Pool of distinct strings, each stored once.
+///
+/// All text lives in a single arena `String`; a symbol is an index into a
+/// table of spans within it, and a second table keeps the symbols sorted
+/// by text so lookups are a binary search. Interning a new string therefore
+/// grows the arena and two `Vec`s rather than allocating per string, which
+/// keeps WASM memory compact. There is no global pool: the owner (e.g. the
+/// contract) keeps the instance and symbols are only meaningful for the
+/// interner that produced them.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    arena: String,
+    spans: Vec<(u32, u32)>,
+    sorted: Vec<Symbol>,
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty interner whose arena can hold `bytes` of text
+    /// before reallocating.
+    pub fn with_capacity(bytes: usize) -> Self {
+        Self { arena: String::with_capacity(bytes), spans: Vec::new(), sorted: Vec::new() }
+    }
+
+    /// Returns the symbol for `s`, storing it if it is new.
+    pub fn intern(&mut self, s: &str) -> Symbol {
+        match self.sorted.binary_search_by(|&symbol| self.resolve(symbol).cmp(s)) {
+            Ok(position) => self.sorted[position],
+            Err(position) => {
+                let symbol = Symbol(u32::try_from(self.spans.len()).expect("too many symbols"));
+                let start = u32::try_from(self.arena.len()).expect("arena too large");
+                let len = u32::try_from(s.len()).expect("string too large");
+                self.arena.push_str(s);
+                self.spans.push((start, len));
+                self.sorted.insert(position, symbol);
+                symbol
+            }
+        }
+    }
+
+    /// Formats `value` in decimal, as by `to_string`, and interns it.
+    pub fn intern_dec(&mut self, value: U256) -> Symbol {
+        self.intern(&to_string(value))
+    }
+
+    /// Returns the symbol for `s` if it has been interned.
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        self.sorted
+            .binary_search_by(|&symbol| self.resolve(symbol).cmp(s))
+            .ok()
+            .map(|position| self.sorted[position])
+    }
+
+    /// Returns the text of `symbol`.
+    ///
+    /// Panics if `symbol` came from a different interner with more strings.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        let (start, len) = self.spans[symbol.0 as usize];
+        &self.arena[start as usize..(start + len) as usize]
+    }
+
+    /// Returns the number of distinct strings interned.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Returns true if nothing has been interned.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Returns the total bytes of interned text.
+    pub fn arena_len(&self) -> usize {
+        self.arena.len()
+    }
+
+    /// Returns the bytes the arena can hold before reallocating.
+    pub fn capacity(&self) -> usize {
+        self.arena.capacity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicates_share_a_symbol() {
+        let mut pool = Interner::new();
+        let gold = pool.intern("Gold");
+        let silver = pool.intern("Silver");
+        assert_eq!(pool.intern("Gold"), gold);
+        assert_ne!(gold, silver);
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.get("Silver"), Some(silver));
+        assert_eq!(pool.get("Bronze"), None);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut pool = Interner::new();
+        let symbols: Vec<Symbol> = ["Background", "", "Eyes", "Laser", "background"]
+            .iter()
+            .map(|s| pool.intern(s))
+            .collect();
+        assert_eq!(pool.resolve(symbols[0]), "Background");
+        assert_eq!(pool.resolve(symbols[1]), "");
+        assert_eq!(pool.resolve(symbols[3]), "Laser");
+        assert_eq!(pool.resolve(symbols[4]), "background");
+        assert!(Interner::new().is_empty());
+    }
+
+    #[test]
+    fn test_arena_is_compact() {
+        let mut pool = Interner::with_capacity(64);
+        for s in ["Hat", "Cap", "Hat", "Crown", "Cap"] {
+            pool.intern(s);
+        }
+        assert_eq!(pool.arena_len(), "HatCapCrown".len());
+        assert_eq!(pool.arena, "HatCapCrown");
+        assert!(pool.capacity() >= 64);
+        // Resolved text points into the arena rather than a separate allocation
+        let range = pool.arena.as_bytes().as_ptr_range();
+        for &symbol in &pool.sorted {
+            assert!(range.contains(&pool.resolve(symbol).as_ptr()));
+        }
+    }
+
+    #[test]
+    fn test_thousand_formatted_values() {
+        let mut pool = Interner::new();
+        let symbols: Vec<Symbol> = (0..1000u64).map(|i| pool.intern_dec(U256::from(i % 500))).collect();
+        assert_eq!(pool.len(), 500);
+        for (i, &symbol) in symbols.iter().enumerate() {
+            assert_eq!(pool.resolve(symbol), (i % 500).to_string());
+            assert_eq!(symbols[i % 500], symbol);
+        }
+        // 10 one-digit, 90 two-digit and 400 three-digit values
+        assert_eq!(pool.arena_len(), 10 + 180 + 1200);
+    }
+}
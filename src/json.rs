@@ -0,0 +1,847 @@
+//! A small JSON parser and canonical serializer for hashing metadata.
+//!
+//! Only what metadata needs is supported: objects, arrays, strings,
+//! booleans, null and integers in the `int256`/`uint256` range. Fractions
+//! and exponents are rejected rather than rounded, since a commitment must
+//! not depend on float formatting.
+
+use alloy_primitives::{keccak256, B256, I256, U256};
+use core::fmt;
+
+use crate::{to_string, to_string_signed};
+
+/// Nesting depth beyond which documents are rejected
+const MAX_DEPTH: usize = 64;
+
+/// Error types for JSON parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonError {
+    /// Input ended inside a value
+    UnexpectedEnd,
+    /// Character at byte `index` is not valid here
+    UnexpectedChar { index: usize, found: char },
+    /// Number at byte `index` has a fraction or exponent
+    FloatNotSupported(usize),
+    /// Integer at byte `index` is outside the int256/uint256 range
+    NumberOutOfRange(usize),
+    /// Object key at byte `index` repeats an earlier key
+    DuplicateKey(usize),
+    /// Escape sequence at byte `index` is malformed or a lone surrogate
+    InvalidEscape(usize),
+    /// Arrays and objects nest more than 64 deep at byte `index`
+    TooDeep(usize),
+    /// Path resolves to an object or array rather than a leaf value
+    NotALeaf,
+    /// `key` called outside an object or while a key awaits its value
+    UnexpectedKey,
+    /// Value written inside an object without a preceding `key`
+    MissingKey,
+    /// `end_*` does not match the innermost open container
+    MismatchedEnd,
+    /// Object closed or document finished with a key lacking its value
+    DanglingKey,
+    /// Document finished with containers still open
+    Unclosed,
+    /// Value written after the top-level value was complete
+    MultipleRoots,
+    /// Document finished before any value was written
+    EmptyDocument,
+}
+
+/// Leaf value extracted by `json_get`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonValue {
+    Str(String),
+    /// Non-negative integer
+    Uint(U256),
+    /// Negative integer
+    Int(I256),
+    Bool(bool),
+    Null,
+}
+
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonValue::Str(s) => f.pad(s),
+            JsonValue::Uint(value) => f.pad(&to_string(*value)),
+            JsonValue::Int(value) => f.pad(&to_string_signed(*value)),
+            JsonValue::Bool(value) => f.pad(if *value { "true" } else { "false" }),
+            JsonValue::Null => f.pad("null"),
+        }
+    }
+}
+
+/// Parsed JSON value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Node {
+    Null,
+    Bool(bool),
+    /// Integer as sign and magnitude; negative magnitudes are at most 2^255
+    Number { negative: bool, magnitude: U256 },
+    Str(String),
+    Array(Vec<Node>),
+    /// Members in document order
+    Object(Vec<(String, Node)>),
+}
+
+/// Re-emits a JSON document in canonical form: object keys sorted by their
+/// UTF-16 code units as in RFC 8785 (JCS), no insignificant whitespace,
+/// integers in plain decimal and strings escaped by `escape_json`.
+///
+/// Documents that differ only in whitespace, key order, escaping or
+/// integer spelling (e.g. `-0`) canonicalize identically. Duplicate keys
+/// are an error, since they have no agreed meaning.
+pub fn canonicalize_json(s: &str) -> Result<String, JsonError> {
+    let node = parse(s)?;
+    let mut result = String::with_capacity(s.len());
+    write_canonical(&node, &mut result);
+    Ok(result)
+}
+
+/// Returns keccak256 of `canonicalize_json(s)`.
+pub fn hash_canonical_json(s: &str) -> Result<B256, JsonError> {
+    canonicalize_json(s).map(keccak256)
+}
+
+/// Extracts the leaf at a dotted `path` such as `"attributes.3.value"`,
+/// where numeric segments index into arrays. An empty path selects the
+/// whole document.
+///
+/// The whole document is validated first, so malformed JSON is an error
+/// even if the path resolves before it. Returns `Ok(None)` when a segment
+/// is missing or steps into a leaf, and `Err(JsonError::NotALeaf)` when
+/// the path ends on an object or array. Keys are matched after escape
+/// decoding and may not contain `.`.
+pub fn json_get(s: &str, path: &str) -> Result<Option<JsonValue>, JsonError> {
+    let root = parse(s)?;
+    let mut node = &root;
+    if !path.is_empty() {
+        for segment in path.split('.') {
+            let next = match node {
+                Node::Object(members) => members.iter().find(|(key, _)| key == segment).map(|(_, value)| value),
+                Node::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => None,
+            };
+            match next {
+                Some(next) => node = next,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    let value = match node {
+        Node::Null => JsonValue::Null,
+        Node::Bool(value) => JsonValue::Bool(*value),
+        Node::Number { negative: true, magnitude } if !magnitude.is_zero() => {
+            // `parse` bounds negative magnitudes by 2^255, so this is exact
+            JsonValue::Int(I256::from_raw(magnitude.wrapping_neg()))
+        }
+        Node::Number { magnitude, .. } => JsonValue::Uint(*magnitude),
+        Node::Str(s) => JsonValue::Str(s.clone()),
+        Node::Array(_) | Node::Object(_) => return Err(JsonError::NotALeaf),
+    };
+    Ok(Some(value))
+}
+
+/// Streaming JSON emitter for nested documents.
+///
+/// Calls chain like the crate's other builders. The first misuse is
+/// recorded and later calls are ignored, so `finish` reports it; a
+/// document that reaches `finish` cleanly is well-formed. Strings are
+/// escaped as by `canonicalize_json`, but keys are written in call order.
+///
+/// ```
+/// use strings_utils_stylus::JsonWriter;
+/// use alloy_primitives::U256;
+///
+/// let json = JsonWriter::new()
+///     .begin_object()
+///     .key("name").value_str("Token #1")
+///     .key("level").value_u256(U256::from(3))
+///     .end_object()
+///     .finish()
+///     .unwrap();
+/// assert_eq!(json, r#"{"name":"Token #1","level":3}"#);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct JsonWriter {
+    out: String,
+    stack: Vec<Frame>,
+    started: bool,
+    error: Option<JsonError>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    object: bool,
+    empty: bool,
+    key_pending: bool,
+}
+
+impl JsonWriter {
+    /// Creates an empty writer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens an object.
+    pub fn begin_object(self) -> Self {
+        self.open(true, '{')
+    }
+
+    /// Closes the innermost object.
+    pub fn end_object(self) -> Self {
+        self.close(true, '}')
+    }
+
+    /// Opens an array.
+    pub fn begin_array(self) -> Self {
+        self.open(false, '[')
+    }
+
+    /// Closes the innermost array.
+    pub fn end_array(self) -> Self {
+        self.close(false, ']')
+    }
+
+    /// Writes an object key; the next value belongs to it.
+    pub fn key(self, key: &str) -> Self {
+        self.step(|w| {
+            let frame = match w.stack.last_mut() {
+                Some(frame) if frame.object && !frame.key_pending => frame,
+                _ => return Err(JsonError::UnexpectedKey),
+            };
+            if !frame.empty {
+                w.out.push(',');
+            }
+            frame.empty = false;
+            frame.key_pending = true;
+            w.out.push('"');
+            escape_json(key, &mut w.out);
+            w.out.push_str("\":");
+            Ok(())
+        })
+    }
+
+    /// Writes an escaped string value.
+    pub fn value_str(self, value: &str) -> Self {
+        self.step(|w| {
+            w.before_value()?;
+            w.out.push('"');
+            escape_json(value, &mut w.out);
+            w.out.push('"');
+            Ok(())
+        })
+    }
+
+    /// Writes an integer value in decimal.
+    pub fn value_u256(self, value: U256) -> Self {
+        self.step(|w| {
+            w.before_value()?;
+            w.out.push_str(&to_string(value));
+            Ok(())
+        })
+    }
+
+    /// Writes `true` or `false`.
+    pub fn value_bool(self, value: bool) -> Self {
+        self.step(|w| {
+            w.before_value()?;
+            w.out.push_str(if value { "true" } else { "false" });
+            Ok(())
+        })
+    }
+
+    /// Writes `null`.
+    pub fn value_null(self) -> Self {
+        self.step(|w| {
+            w.before_value()?;
+            w.out.push_str("null");
+            Ok(())
+        })
+    }
+
+    /// Returns the document, or the first misuse.
+    pub fn finish(self) -> Result<String, JsonError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        match self.stack.last() {
+            Some(frame) if frame.key_pending => Err(JsonError::DanglingKey),
+            Some(_) => Err(JsonError::Unclosed),
+            None if !self.started => Err(JsonError::EmptyDocument),
+            None => Ok(self.out),
+        }
+    }
+
+    fn step(mut self, f: impl FnOnce(&mut Self) -> Result<(), JsonError>) -> Self {
+        if self.error.is_none() {
+            if let Err(error) = f(&mut self) {
+                self.error = Some(error);
+            }
+        }
+        self
+    }
+
+    /// Claims the slot for a value: the pending key, the next array
+    /// element, or the top level.
+    fn before_value(&mut self) -> Result<(), JsonError> {
+        match self.stack.last_mut() {
+            None if self.started => return Err(JsonError::MultipleRoots),
+            None => self.started = true,
+            Some(frame) if frame.object => {
+                if !frame.key_pending {
+                    return Err(JsonError::MissingKey);
+                }
+                frame.key_pending = false;
+            }
+            Some(frame) => {
+                if !frame.empty {
+                    self.out.push(',');
+                }
+                frame.empty = false;
+            }
+        }
+        Ok(())
+    }
+
+    fn open(self, object: bool, bracket: char) -> Self {
+        self.step(|w| {
+            w.before_value()?;
+            w.out.push(bracket);
+            w.stack.push(Frame { object, empty: true, key_pending: false });
+            Ok(())
+        })
+    }
+
+    fn close(self, object: bool, bracket: char) -> Self {
+        self.step(|w| {
+            match w.stack.last() {
+                Some(frame) if frame.object != object => return Err(JsonError::MismatchedEnd),
+                Some(frame) if frame.key_pending => return Err(JsonError::DanglingKey),
+                Some(_) => {}
+                None => return Err(JsonError::MismatchedEnd),
+            }
+            w.stack.pop();
+            w.out.push(bracket);
+            Ok(())
+        })
+    }
+}
+
+/// Escapes `s` as the contents of a JSON string: `"` and `\` are
+/// backslash-escaped, control characters use the short forms `\b`, `\t`,
+/// `\n`, `\f` and `\r` or else `\u00xx`, and everything else is literal.
+pub(crate) fn escape_json(s: &str, out: &mut String) {
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{c}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if c < ' ' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Parses a complete JSON document; trailing non-whitespace is an error.
+pub(crate) fn parse(s: &str) -> Result<Node, JsonError> {
+    let mut parser = Parser { input: s, index: 0 };
+    let node = parser.value(0)?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(node),
+        Some(found) => Err(JsonError::UnexpectedChar { index: parser.index, found }),
+    }
+}
+
+fn write_canonical(node: &Node, out: &mut String) {
+    match node {
+        Node::Null => out.push_str("null"),
+        Node::Bool(true) => out.push_str("true"),
+        Node::Bool(false) => out.push_str("false"),
+        Node::Number { negative, magnitude } => {
+            if *negative && !magnitude.is_zero() {
+                out.push('-');
+            }
+            out.push_str(&to_string(*magnitude));
+        }
+        Node::Str(s) => {
+            out.push('"');
+            escape_json(s, out);
+            out.push('"');
+        }
+        Node::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Node::Object(members) => {
+            let mut sorted: Vec<&(String, Node)> = members.iter().collect();
+            sorted.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push('{');
+            for (i, (key, value)) in sorted.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push('"');
+                escape_json(key, out);
+                out.push_str("\":");
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    index: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.index..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.index += c.len_utf8();
+        Some(c)
+    }
+
+    fn unexpected(&self) -> JsonError {
+        match self.peek() {
+            Some(found) => JsonError::UnexpectedChar { index: self.index, found },
+            None => JsonError::UnexpectedEnd,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.index += 1;
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), JsonError> {
+        if self.peek() != Some(c) {
+            return Err(self.unexpected());
+        }
+        self.index += 1;
+        Ok(())
+    }
+
+    fn literal(&mut self, word: &str, node: Node) -> Result<Node, JsonError> {
+        for expected in word.chars() {
+            if self.peek() != Some(expected) {
+                return Err(self.unexpected());
+            }
+            self.index += 1;
+        }
+        Ok(node)
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Node, JsonError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(depth + 1),
+            Some('[') => self.array(depth + 1),
+            Some('"') => self.string().map(Node::Str),
+            Some('t') => self.literal("true", Node::Bool(true)),
+            Some('f') => self.literal("false", Node::Bool(false)),
+            Some('n') => self.literal("null", Node::Null),
+            Some('-' | '0'..='9') => self.number(),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Node, JsonError> {
+        if depth > MAX_DEPTH {
+            return Err(JsonError::TooDeep(self.index));
+        }
+        self.index += 1;
+        let mut members: Vec<(String, Node)> = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.index += 1;
+            return Ok(Node::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.unexpected());
+            }
+            let key_index = self.index;
+            let key = self.string()?;
+            if members.iter().any(|(existing, _)| *existing == key) {
+                return Err(JsonError::DuplicateKey(key_index));
+            }
+            self.skip_whitespace();
+            self.expect(':')?;
+            let value = self.value(depth)?;
+            members.push((key, value));
+            self.skip_whitespace();
+            match self.bump() {
+                Some(',') => continue,
+                Some('}') => return Ok(Node::Object(members)),
+                Some(_) => {
+                    self.index -= 1;
+                    return Err(self.unexpected());
+                }
+                None => return Err(JsonError::UnexpectedEnd),
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Node, JsonError> {
+        if depth > MAX_DEPTH {
+            return Err(JsonError::TooDeep(self.index));
+        }
+        self.index += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.index += 1;
+            return Ok(Node::Array(items));
+        }
+        loop {
+            items.push(self.value(depth)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.index += 1,
+                Some(']') => {
+                    self.index += 1;
+                    return Ok(Node::Array(items));
+                }
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Node, JsonError> {
+        let start = self.index;
+        let negative = self.peek() == Some('-');
+        if negative {
+            self.index += 1;
+        }
+        let digits_start = self.index;
+        while matches!(self.peek(), Some('0'..='9')) {
+            self.index += 1;
+        }
+        let digits = &self.input[digits_start..self.index];
+        if digits.is_empty() || (digits.len() > 1 && digits.starts_with('0')) {
+            if digits.is_empty() {
+                return Err(self.unexpected());
+            }
+            return Err(JsonError::UnexpectedChar { index: digits_start + 1, found: digits.as_bytes()[1] as char });
+        }
+        if matches!(self.peek(), Some('.' | 'e' | 'E')) {
+            return Err(JsonError::FloatNotSupported(start));
+        }
+
+        let magnitude = U256::from_str_radix(digits, 10).map_err(|_| JsonError::NumberOutOfRange(start))?;
+        if negative && magnitude > U256::from(1) << 255 {
+            return Err(JsonError::NumberOutOfRange(start));
+        }
+        Ok(Node::Number { negative, magnitude })
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.index += 1;
+        let mut result = String::new();
+        loop {
+            let escape_index = self.index;
+            match self.bump().ok_or(JsonError::UnexpectedEnd)? {
+                '"' => return Ok(result),
+                '\\' => {
+                    let c = match self.bump().ok_or(JsonError::UnexpectedEnd)? {
+                        '"' => '"',
+                        '\\' => '\\',
+                        '/' => '/',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => self.unicode_escape(escape_index)?,
+                        _ => return Err(JsonError::InvalidEscape(escape_index)),
+                    };
+                    result.push(c);
+                }
+                c if c < ' ' => {
+                    return Err(JsonError::UnexpectedChar { index: escape_index, found: c });
+                }
+                c => result.push(c),
+            }
+        }
+    }
+
+    /// Decodes the digits after `\u`, combining a surrogate pair
+    fn unicode_escape(&mut self, escape_index: usize) -> Result<char, JsonError> {
+        let high = self.hex4(escape_index)?;
+        let code = match high {
+            0xd800..=0xdbff => {
+                if !self.input[self.index..].starts_with("\\u") {
+                    return Err(JsonError::InvalidEscape(escape_index));
+                }
+                self.index += 2;
+                let low = self.hex4(escape_index)?;
+                if !(0xdc00..=0xdfff).contains(&low) {
+                    return Err(JsonError::InvalidEscape(escape_index));
+                }
+                0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+            }
+            0xdc00..=0xdfff => return Err(JsonError::InvalidEscape(escape_index)),
+            code => code,
+        };
+        char::from_u32(code).ok_or(JsonError::InvalidEscape(escape_index))
+    }
+
+    fn hex4(&mut self, escape_index: usize) -> Result<u32, JsonError> {
+        let digits = self.input.get(self.index..self.index + 4).ok_or(JsonError::InvalidEscape(escape_index))?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(JsonError::InvalidEscape(escape_index));
+        }
+        self.index += 4;
+        Ok(u32::from_str_radix(digits, 16).expect("checked hex digits"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_order_and_whitespace_do_not_matter() {
+        let a = r#"{"name": "Token #1", "edition": 3, "image": "ipfs://x"}"#;
+        let b = "{\n  \"image\":\"ipfs://x\",\n  \"edition\" : 3,\n  \"name\":\"Token #1\"\n}";
+        assert_eq!(canonicalize_json(a).unwrap(), r#"{"edition":3,"image":"ipfs://x","name":"Token #1"}"#);
+        assert_eq!(canonicalize_json(a), canonicalize_json(b));
+        assert_eq!(hash_canonical_json(a), hash_canonical_json(b));
+    }
+
+    #[test]
+    fn test_nested_structures() {
+        let doc = r#" { "b": [1, -2, {"y": null, "x": true}], "a": {"d": [], "c": {}} } "#;
+        assert_eq!(
+            canonicalize_json(doc).unwrap(),
+            r#"{"a":{"c":{},"d":[]},"b":[1,-2,{"x":true,"y":null}]}"#
+        );
+    }
+
+    #[test]
+    fn test_escaping_is_normalized() {
+        assert_eq!(canonicalize_json(r#""A\/é""#).unwrap(), "\"A/é\"");
+        assert_eq!(canonicalize_json(r#""😀""#).unwrap(), "\"😀\"");
+        assert_eq!(canonicalize_json(r#""tab\u0009nul\u0000\"q\"""#).unwrap(), r#""tab\tnul\u0000\"q\"""#);
+        // Escaped and literal keys are the same key
+        assert_eq!(canonicalize_json(r#"{"\u0041": 1, "A": 2}"#), Err(JsonError::DuplicateKey(14)));
+    }
+
+    #[test]
+    fn test_utf16_key_order() {
+        // U+1F600 sorts after U+FF61 by code point but before it in UTF-16
+        assert_eq!(canonicalize_json("{\"｡\":2,\"😀\":1}").unwrap(), "{\"😀\":1,\"｡\":2}");
+    }
+
+    #[test]
+    fn test_integers() {
+        let max = to_string(U256::MAX);
+        assert_eq!(canonicalize_json(&max).unwrap(), max);
+        let min = format!("-{}", to_string(U256::from(1) << 255));
+        assert_eq!(canonicalize_json(&min).unwrap(), min);
+        assert_eq!(canonicalize_json("-0").unwrap(), "0");
+        assert_eq!(canonicalize_json(&format!("{}0", max)), Err(JsonError::NumberOutOfRange(0)));
+        assert_eq!(canonicalize_json(&format!("[{}9]", &min[..min.len() - 1])), Err(JsonError::NumberOutOfRange(1)));
+        assert_eq!(canonicalize_json("012"), Err(JsonError::UnexpectedChar { index: 1, found: '1' }));
+    }
+
+    #[test]
+    fn test_floats_rejected() {
+        assert_eq!(canonicalize_json("1.5"), Err(JsonError::FloatNotSupported(0)));
+        assert_eq!(canonicalize_json(r#"{"x": 1e3}"#), Err(JsonError::FloatNotSupported(6)));
+        assert_eq!(canonicalize_json("[-2E1]"), Err(JsonError::FloatNotSupported(1)));
+    }
+
+    #[test]
+    fn test_duplicate_keys_rejected() {
+        assert_eq!(canonicalize_json(r#"{"a": 1, "a": 1}"#), Err(JsonError::DuplicateKey(9)));
+        // Same key in different objects is fine
+        assert!(canonicalize_json(r#"{"a": {"a": 1}}"#).is_ok());
+    }
+
+    #[test]
+    fn test_malformed_documents() {
+        assert_eq!(canonicalize_json(""), Err(JsonError::UnexpectedEnd));
+        assert_eq!(canonicalize_json("[1,]"), Err(JsonError::UnexpectedChar { index: 3, found: ']' }));
+        assert_eq!(canonicalize_json(r#"{"a" 1}"#), Err(JsonError::UnexpectedChar { index: 5, found: '1' }));
+        assert_eq!(canonicalize_json(r#"{"a": 1"#), Err(JsonError::UnexpectedEnd));
+        assert_eq!(canonicalize_json("true false"), Err(JsonError::UnexpectedChar { index: 5, found: 'f' }));
+        assert_eq!(canonicalize_json("nul"), Err(JsonError::UnexpectedEnd));
+        assert_eq!(canonicalize_json(r#""\x""#), Err(JsonError::InvalidEscape(1)));
+        assert_eq!(canonicalize_json(r#""\ud800""#), Err(JsonError::InvalidEscape(1)));
+        assert_eq!(canonicalize_json("\"a\nb\""), Err(JsonError::UnexpectedChar { index: 2, found: '\n' }));
+        assert_eq!(canonicalize_json(&"[".repeat(65)), Err(JsonError::TooDeep(64)));
+    }
+
+    const METADATA: &str = r#"{
+        "name": "Token #7",
+        "attributes": [
+            {"trait_type": "Eyes", "value": "Laser"},
+            {"trait_type": "Level", "value": 12},
+            {"trait_type": "Delta", "value": -3},
+            {"trait_type": "Legendary", "value": true},
+            {"trait_type": "Note", "value": null}
+        ],
+        "tab\tkey": {"caf\u00e9": "ok"}
+    }"#;
+
+    #[test]
+    fn test_json_get_nested() {
+        assert_eq!(json_get(METADATA, "name"), Ok(Some(JsonValue::Str("Token #7".into()))));
+        assert_eq!(json_get(METADATA, "attributes.0.value"), Ok(Some(JsonValue::Str("Laser".into()))));
+        assert_eq!(json_get(METADATA, "attributes.1.value"), Ok(Some(JsonValue::Uint(U256::from(12)))));
+        assert_eq!(json_get(METADATA, "attributes.2.value"), Ok(Some(JsonValue::Int(I256::try_from(-3).unwrap()))));
+        assert_eq!(json_get(METADATA, "attributes.3.value"), Ok(Some(JsonValue::Bool(true))));
+        assert_eq!(json_get(METADATA, "attributes.4.value"), Ok(Some(JsonValue::Null)));
+        assert_eq!(json_get("42", ""), Ok(Some(JsonValue::Uint(U256::from(42)))));
+    }
+
+    #[test]
+    fn test_json_get_missing() {
+        assert_eq!(json_get(METADATA, "description"), Ok(None));
+        assert_eq!(json_get(METADATA, "attributes.5.value"), Ok(None));
+        assert_eq!(json_get(METADATA, "attributes.first"), Ok(None));
+        assert_eq!(json_get(METADATA, "name.length"), Ok(None));
+        assert_eq!(json_get(METADATA, "attributes..value"), Ok(None));
+    }
+
+    #[test]
+    fn test_json_get_not_a_leaf() {
+        assert_eq!(json_get(METADATA, "attributes"), Err(JsonError::NotALeaf));
+        assert_eq!(json_get(METADATA, "attributes.0"), Err(JsonError::NotALeaf));
+        assert_eq!(json_get(METADATA, ""), Err(JsonError::NotALeaf));
+    }
+
+    #[test]
+    fn test_json_get_escaped_keys() {
+        assert_eq!(json_get(METADATA, "tab\tkey.café"), Ok(Some(JsonValue::Str("ok".into()))));
+        assert_eq!(json_get(METADATA, "tab\\tkey.café"), Ok(None));
+    }
+
+    #[test]
+    fn test_json_get_number_range() {
+        let max = to_string(U256::MAX);
+        assert_eq!(json_get(&max, ""), Ok(Some(JsonValue::Uint(U256::MAX))));
+        assert_eq!(json_get(&format!(r#"{{"x": {}0}}"#, max), "x"), Err(JsonError::NumberOutOfRange(6)));
+        let min = format!("-{}", to_string(U256::from(1) << 255));
+        assert_eq!(json_get(&min, ""), Ok(Some(JsonValue::Int(I256::MIN))));
+        assert_eq!(json_get("-0", ""), Ok(Some(JsonValue::Uint(U256::ZERO))));
+        // Validation covers the whole document, not just the path
+        assert_eq!(json_get(r#"{"a": 1, "b": 1.5}"#, "a"), Err(JsonError::FloatNotSupported(14)));
+    }
+
+    fn nested_document() -> JsonWriter {
+        JsonWriter::new()
+            .begin_object()
+            .key("name")
+            .value_str("Orb \"Prime\"")
+            .key("description")
+            .value_str("line 1\nline 2\t\u{1}")
+            .key("attributes")
+            .begin_array()
+            .begin_object()
+            .key("trait_type")
+            .value_str("Level")
+            .key("value")
+            .value_u256(U256::from(12))
+            .end_object()
+            .begin_object()
+            .end_object()
+            .end_array()
+            .key("properties")
+            .begin_object()
+            .key("supply")
+            .value_u256(U256::MAX)
+            .key("tags")
+            .begin_array()
+            .value_bool(true)
+            .value_null()
+            .begin_array()
+            .end_array()
+            .end_array()
+            .end_object()
+            .end_object()
+    }
+
+    #[test]
+    fn test_writer_nested_golden() {
+        let expected = format!(
+            concat!(
+                r#"{{"name":"Orb \"Prime\"","description":"line 1\nline 2\t\u0001","#,
+                r#""attributes":[{{"trait_type":"Level","value":12}},{{}}],"#,
+                r#""properties":{{"supply":{},"tags":[true,null,[]]}}}}"#
+            ),
+            to_string(U256::MAX)
+        );
+        assert_eq!(nested_document().finish().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_writer_top_level_scalars() {
+        assert_eq!(JsonWriter::new().value_str("a\\b").finish().unwrap(), r#""a\\b""#);
+        assert_eq!(JsonWriter::new().value_null().finish().unwrap(), "null");
+        assert_eq!(JsonWriter::new().begin_array().end_array().finish().unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_writer_output_parses() {
+        let json = nested_document().finish().unwrap();
+        assert!(serde_json::from_str::<serde_json::Value>(&json).is_ok());
+        // Our own parser reads it back too, with keys reordered
+        assert_eq!(json_get(&json, "attributes.0.value"), Ok(Some(JsonValue::Uint(U256::from(12)))));
+        assert_eq!(json_get(&json, "name"), Ok(Some(JsonValue::Str("Orb \"Prime\"".into()))));
+        assert!(canonicalize_json(&json).unwrap().starts_with(r#"{"attributes":"#));
+    }
+
+    #[test]
+    fn test_writer_misuse() {
+        let w = JsonWriter::new;
+        assert_eq!(w().key("a").finish(), Err(JsonError::UnexpectedKey));
+        assert_eq!(w().begin_array().key("a").finish(), Err(JsonError::UnexpectedKey));
+        assert_eq!(w().begin_object().key("a").key("b").finish(), Err(JsonError::UnexpectedKey));
+        assert_eq!(w().begin_object().value_bool(true).finish(), Err(JsonError::MissingKey));
+        assert_eq!(w().begin_object().begin_array().finish(), Err(JsonError::MissingKey));
+        assert_eq!(w().begin_object().end_array().finish(), Err(JsonError::MismatchedEnd));
+        assert_eq!(w().begin_array().end_object().finish(), Err(JsonError::MismatchedEnd));
+        assert_eq!(w().end_array().finish(), Err(JsonError::MismatchedEnd));
+        assert_eq!(w().begin_object().key("a").end_object().finish(), Err(JsonError::DanglingKey));
+        assert_eq!(w().begin_object().key("a").finish(), Err(JsonError::DanglingKey));
+        assert_eq!(w().begin_object().begin_object().finish(), Err(JsonError::MissingKey));
+        assert_eq!(w().begin_array().begin_object().finish(), Err(JsonError::Unclosed));
+        assert_eq!(w().value_null().value_null().finish(), Err(JsonError::MultipleRoots));
+        assert_eq!(w().begin_array().end_array().begin_array().finish(), Err(JsonError::MultipleRoots));
+        assert_eq!(w().finish(), Err(JsonError::EmptyDocument));
+        // The first error wins even if later calls would have fixed things
+        assert_eq!(w().begin_object().value_null().key("a").value_null().end_object().finish(), Err(JsonError::MissingKey));
+    }
+
+    #[test]
+    fn test_json_value_display() {
+        assert_eq!(JsonValue::Str("Laser".into()).to_string(), "Laser");
+        assert_eq!(JsonValue::Uint(U256::from(1_000_000)).to_string(), "1000000");
+        assert_eq!(JsonValue::Int(I256::MIN).to_string(), format!("-{}", to_string(U256::from(1) << 255)));
+        assert_eq!(JsonValue::Bool(false).to_string(), "false");
+        assert_eq!(format!("{:>6}", JsonValue::Null), "  null");
+    }
+}
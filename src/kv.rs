@@ -0,0 +1,207 @@
+//! `key = value` configuration strings, one entry per line.
+//!
+//! Blank lines and lines starting with `#` are skipped. Keys and unquoted
+//! values are trimmed; an unquoted value runs to the end of the line, so
+//! `#` inside it is literal. Double-quoted values keep their whitespace,
+//! decode `\"`, `\\`, `\n`, `\t` and `\r`, and may be followed by a comment.
+
+use core::fmt;
+
+use alloy_primitives::{Address, U256};
+
+use crate::id::parse_address;
+use crate::parse::{parse_u256, ParseError};
+
+/// Error types for key-value config parsing and lookup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KvError {
+    /// Line has no `=`
+    MissingEquals { line: usize },
+    /// Nothing before the `=`
+    EmptyKey { line: usize },
+    /// Key was already defined on `first_line`
+    DuplicateKey { line: usize, first_line: usize },
+    /// Quoted value has no closing quote
+    UnterminatedQuote { line: usize },
+    /// Unknown escape sequence in a quoted value
+    InvalidEscape { line: usize, found: char },
+    /// Something other than a comment follows a quoted value
+    TrailingCharacters { line: usize },
+    /// Requested key is not present
+    MissingKey,
+    /// Value for the requested key did not parse
+    InvalidValue(ParseError),
+}
+
+impl fmt::Display for KvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KvError::MissingEquals { line } => write!(f, "line {}: expected `key = value`", line),
+            KvError::EmptyKey { line } => write!(f, "line {}: empty key", line),
+            KvError::DuplicateKey { line, first_line } => {
+                write!(f, "line {}: duplicate key, first defined on line {}", line, first_line)
+            }
+            KvError::UnterminatedQuote { line } => write!(f, "line {}: unterminated quoted value", line),
+            KvError::InvalidEscape { line, found } => {
+                write!(f, "line {}: invalid escape \\{}", line, found)
+            }
+            KvError::TrailingCharacters { line } => {
+                write!(f, "line {}: unexpected characters after quoted value", line)
+            }
+            KvError::MissingKey => write!(f, "key not found"),
+            KvError::InvalidValue(error) => write!(f, "invalid value: {}", error),
+        }
+    }
+}
+
+/// Parses a `key = value` config string into entries in file order.
+///
+/// Line numbers in errors are 1-based.
+pub fn parse_kv(s: &str) -> Result<Vec<(String, String)>, KvError> {
+    let mut entries: Vec<(String, String)> = Vec::new();
+    let mut lines_of: Vec<usize> = Vec::new();
+    for (i, raw) in s.lines().enumerate() {
+        let line = i + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = trimmed.split_once('=').ok_or(KvError::MissingEquals { line })?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(KvError::EmptyKey { line });
+        }
+        if let Some(pos) = entries.iter().position(|(existing, _)| existing == key) {
+            return Err(KvError::DuplicateKey { line, first_line: lines_of[pos] });
+        }
+
+        let value = value.trim();
+        let value = match value.strip_prefix('"') {
+            Some(quoted) => parse_quoted(quoted, line)?,
+            None => value.to_string(),
+        };
+        entries.push((key.to_string(), value));
+        lines_of.push(line);
+    }
+    Ok(entries)
+}
+
+/// Returns the value for `key` parsed by `parse_u256` (decimal or "0x" hex).
+pub fn kv_get_u256(kvs: &[(String, String)], key: &str) -> Result<U256, KvError> {
+    parse_u256(kv_get(kvs, key)?).map_err(KvError::InvalidValue)
+}
+
+/// Returns the value for `key` parsed as a "0x"-prefixed 40-digit address.
+///
+/// Checksum casing is not enforced.
+pub fn kv_get_address(kvs: &[(String, String)], key: &str) -> Result<Address, KvError> {
+    parse_address(kv_get(kvs, key)?, 0).map_err(KvError::InvalidValue)
+}
+
+fn kv_get<'a>(kvs: &'a [(String, String)], key: &str) -> Result<&'a str, KvError> {
+    kvs.iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.as_str())
+        .ok_or(KvError::MissingKey)
+}
+
+/// Decodes a quoted value; `rest` starts after the opening quote.
+fn parse_quoted(rest: &str, line: usize) -> Result<String, KvError> {
+    let mut result = String::new();
+    let mut chars = rest.char_indices();
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '"' => {
+                let tail = chars.as_str().trim_start();
+                if !tail.is_empty() && !tail.starts_with('#') {
+                    return Err(KvError::TrailingCharacters { line });
+                }
+                return Ok(result);
+            }
+            '\\' => {
+                let escaped = match chars.next().map(|(_, e)| e) {
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    Some('r') => '\r',
+                    Some(found) => return Err(KvError::InvalidEscape { line, found }),
+                    None => return Err(KvError::UnterminatedQuote { line }),
+                };
+                result.push(escaped);
+            }
+            c => result.push(c),
+        }
+    }
+    Err(KvError::UnterminatedQuote { line })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kv(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_comments_and_blank_lines() {
+        let config = "# deployer config\n\n  name = Genesis Drop  \n\t# indented comment\nsupply=1000\r\ncolor = #ff00aa\n";
+        assert_eq!(
+            parse_kv(config).unwrap(),
+            kv(&[("name", "Genesis Drop"), ("supply", "1000"), ("color", "#ff00aa")])
+        );
+        assert_eq!(parse_kv("").unwrap(), kv(&[]));
+        assert_eq!(parse_kv("empty =").unwrap(), kv(&[("empty", "")]));
+    }
+
+    #[test]
+    fn test_quoted_values() {
+        let config = r#"uri = "ipfs://x?a=1&b=2"  # gateway
+pad = "  spaced  "
+esc = "say \"hi\"\\n\tend""#;
+        assert_eq!(
+            parse_kv(config).unwrap(),
+            kv(&[("uri", "ipfs://x?a=1&b=2"), ("pad", "  spaced  "), ("esc", "say \"hi\"\\n\tend")])
+        );
+        assert_eq!(parse_kv("a = \"open"), Err(KvError::UnterminatedQuote { line: 1 }));
+        assert_eq!(parse_kv("a = \"x\\\""), Err(KvError::UnterminatedQuote { line: 1 }));
+        assert_eq!(parse_kv("\na = \"\\q\""), Err(KvError::InvalidEscape { line: 2, found: 'q' }));
+        assert_eq!(parse_kv("a = \"x\" y"), Err(KvError::TrailingCharacters { line: 1 }));
+    }
+
+    #[test]
+    fn test_line_errors() {
+        let config = "# header\nowner = 0x1\n\nsupply = 5\nowner = 0x2\n";
+        let err = parse_kv(config).unwrap_err();
+        assert_eq!(err, KvError::DuplicateKey { line: 5, first_line: 2 });
+        assert_eq!(err.to_string(), "line 5: duplicate key, first defined on line 2");
+        assert_eq!(parse_kv("a = 1\njust text"), Err(KvError::MissingEquals { line: 2 }));
+        assert_eq!(parse_kv(" = 1").unwrap_err().to_string(), "line 1: empty key");
+    }
+
+    #[test]
+    fn test_typed_accessors() {
+        let kvs = parse_kv(
+            "supply = 10000\nmask = 0xff\nowner = 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed\nbad = 12a\nshort = 0x1234",
+        )
+        .unwrap();
+        assert_eq!(kv_get_u256(&kvs, "supply"), Ok(U256::from(10000)));
+        assert_eq!(kv_get_u256(&kvs, "mask"), Ok(U256::from(255)));
+        let owner = kv_get_address(&kvs, "owner").unwrap();
+        assert_eq!(crate::address_to_checksum_hex_string(owner), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+
+        assert_eq!(kv_get_u256(&kvs, "missing"), Err(KvError::MissingKey));
+        assert_eq!(
+            kv_get_u256(&kvs, "bad"),
+            Err(KvError::InvalidValue(ParseError::InvalidDigit { index: 2, found: 'a' }))
+        );
+        assert_eq!(
+            kv_get_address(&kvs, "short"),
+            Err(KvError::InvalidValue(ParseError::InvalidLength { expected: 40, found: 4 }))
+        );
+        assert_eq!(kv_get_address(&kvs, "supply"), Err(KvError::InvalidValue(ParseError::MissingHexPrefix)));
+        assert_eq!(kv_get_address(&kvs, "missing").unwrap_err().to_string(), "key not found");
+    }
+}
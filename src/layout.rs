@@ -0,0 +1,97 @@
+//! Approximate text measurement for laying out on-chain SVG.
+
+/// Font whose metrics `estimate_text_width` uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontFamily {
+    /// Fixed advance of 600/1000 em, as in Courier and most code fonts
+    Monospace,
+    /// Proportional widths of Helvetica, which Arial matches
+    SansSerif,
+}
+
+/// Advance of every monospace character, in 1/1000 em
+const MONOSPACE_ADVANCE: u32 = 600;
+
+/// Advance assumed for sans-serif characters outside printable ASCII, in
+/// 1/1000 em: the width of a digit, close to the average lowercase letter
+const SANS_AVERAGE_ADVANCE: u32 = 556;
+
+/// Helvetica advance widths in 1/1000 em for ' ' through '~'
+const SANS_ADVANCES: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // ' ' to '/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // '0' to '?'
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // '@' to 'O'
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // 'P' to '_'
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // '`' to 'o'
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // 'p' to '~'
+];
+
+/// Estimates the rendered width of `s` in user units at `font_size`.
+///
+/// Each character's advance comes from the table for `font`, and the sum
+/// is scaled by `font_size / 1000` and rounded half up. Characters outside
+/// printable ASCII count as 600/1000 em in monospace and 556/1000 em in
+/// sans-serif, so wide CJK text is underestimated. Kerning is ignored; the
+/// result is deterministic and proportionate, not exact.
+pub fn estimate_text_width(s: &str, font_size: u32, font: FontFamily) -> u32 {
+    let units: u64 = s.chars().map(|c| u64::from(advance(c, font))).sum();
+    let width = (units * u64::from(font_size) + 500) / 1000;
+    u32::try_from(width).unwrap_or(u32::MAX)
+}
+
+/// Returns the x coordinate at which `text` starts so that it is centered
+/// in a container `container_width` wide, or 0 if it does not fit.
+///
+/// For use with the default `text-anchor="start"`.
+pub fn center_x(container_width: u32, text: &str, font_size: u32, font: FontFamily) -> u32 {
+    container_width.saturating_sub(estimate_text_width(text, font_size, font)) / 2
+}
+
+fn advance(c: char, font: FontFamily) -> u32 {
+    match font {
+        FontFamily::Monospace => MONOSPACE_ADVANCE,
+        FontFamily::SansSerif => match c {
+            ' '..='~' => u32::from(SANS_ADVANCES[c as usize - ' ' as usize]),
+            _ => SANS_AVERAGE_ADVANCE,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monospace_is_count_times_advance() {
+        assert_eq!(estimate_text_width("0x1234", 10, FontFamily::Monospace), 6 * 6);
+        assert_eq!(estimate_text_width("héllo", 20, FontFamily::Monospace), 5 * 12);
+        assert_eq!(estimate_text_width("abc", 15, FontFamily::Monospace), 27);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(estimate_text_width("", 16, FontFamily::Monospace), 0);
+        assert_eq!(estimate_text_width("", 16, FontFamily::SansSerif), 0);
+        assert_eq!(estimate_text_width("abc", 0, FontFamily::SansSerif), 0);
+    }
+
+    #[test]
+    fn test_sans_proportions() {
+        let narrow = estimate_text_width("iii", 100, FontFamily::SansSerif);
+        let wide = estimate_text_width("WWW", 100, FontFamily::SansSerif);
+        assert_eq!(narrow, 67);
+        assert_eq!(wide, 283);
+        assert!(estimate_text_width("Hello", 100, FontFamily::SansSerif) < estimate_text_width("HELLO", 100, FontFamily::SansSerif));
+        assert_eq!(SANS_ADVANCES[(b'~' - b' ') as usize], 584);
+        assert_eq!(estimate_text_width("€", 1000, FontFamily::SansSerif), 556);
+    }
+
+    #[test]
+    fn test_center_x() {
+        // "#42" in 20px monospace is 36 wide
+        assert_eq!(center_x(350, "#42", 20, FontFamily::Monospace), 157);
+        assert_eq!(center_x(36, "#42", 20, FontFamily::Monospace), 0);
+        assert_eq!(center_x(10, "#42", 20, FontFamily::Monospace), 0);
+        assert_eq!(center_x(100, "", 20, FontFamily::SansSerif), 50);
+    }
+}
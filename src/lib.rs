@@ -6,9 +6,166 @@
 
 use alloy_primitives::{Address, I256, U256};
 
+mod abi;
+mod allowance;
+mod armor;
+mod backoff;
+mod banner;
+mod bidi;
+mod braille;
+mod build_info;
+mod canonical;
+mod chart;
+mod checksum;
+mod classify;
+mod color;
+mod commit;
+pub mod compat;
+mod content;
+mod create2;
+mod ct;
+mod decimal;
+mod diagnose;
+mod diff;
+mod display;
+mod dump;
+mod eip191;
+mod encoding;
+mod escape;
+mod expand;
+mod fluent;
+mod frame;
+mod gas;
+mod governance;
+mod grapheme;
+mod id;
+mod inspect;
+mod intern;
+mod json;
+mod kv;
+mod layout;
+mod lines;
+mod literal;
+mod log;
+mod mask;
+mod message;
+mod narrow;
+mod normalize;
+mod orderbook;
+mod ownership;
+mod packed;
+mod parse;
+mod progress;
+mod qr;
+mod query;
+mod rarity;
+mod retryable;
+mod revert;
+mod sanitize;
+mod schedule;
+mod search;
+mod seed;
+mod selector;
+mod semver;
+mod stats;
+mod stream;
+mod svg;
+mod t9;
+mod table;
+mod time;
+mod trade;
+mod tx;
+mod units;
+mod uri;
+mod userop;
+mod vesting;
+mod width;
+mod wrappers;
+
+pub use abi::{decode_static_args, AbiError, AbiType};
+pub use allowance::{format_allowance, format_allowance_with_threshold, is_effectively_unlimited, HALF_MAX_ALLOWANCE};
+pub use armor::{armor, unarmor, wrap_fixed, ArmorError};
+pub use backoff::{backoff_delay, format_backoff, format_backoff_at};
+pub use banner::{to_banner, BannerFont};
+pub use bidi::{isolate_bidi, BidiMode};
+pub use braille::{to_braille, u256_to_braille};
+pub use build_info::{format_build_info, pack_build_info, BuildInfoError};
+pub use canonical::CanonicalHex;
+pub use chart::{bar, bar_ascii, labeled_bars, labeled_bars_with, sparkline, sparkline_ascii, tick_labels};
+pub use checksum::{to_checksum_address_chain, verify_checksum_chain};
+pub use classify::{is_power_of_ten, is_power_of_two, is_round_number, magnitude, nearest_power_of_ten};
+pub use color::{format_hex_color, hsl_to_rgb, linear_gradient_svg, palette_from_seed};
+pub use commit::{CommitmentBuilder, Keccak};
+pub use content::{content_filename, content_path, FilenameError};
+pub use create2::{create2_address, format_create2_preview};
+pub use ct::{eq_constant_time, eq_str_constant_time};
+pub use decimal::{add_decimal_strings, compare_decimal_strings, div_decimal_string, mul_decimal_string, sub_decimal_strings, MathError};
+pub use diagnose::{suggest_hex_fix, HexDiagnosis};
+pub use diff::{simple_diff, simple_diff_capped, DEFAULT_MAX_DIFF_LINES};
+pub use display::{display_safe, display_safe_bytes, display_safe_with, TruncateMode};
+pub use dump::StateDump;
+pub use eip191::{eip191_hash, eip191_prefix, format_eip191_preview};
+pub use encoding::{base64_decode, base64_encode, base64_encoded_len, Base64Encoder, Base64Error};
+pub use escape::{escape_html_attr, escape_html_text};
+pub use expand::{expand_vars, ExpandError, MAX_EXPANSIONS};
+pub use fluent::{to_compact_string, FormatError, HexPadding, U256Fmt, DEFAULT_HEX_PADDING};
+pub use frame::{decode_strings, encode_strings, join_message, split_message, FrameError};
+pub use gas::{GasReport, GasSort};
+pub use governance::{format_proposal_timeline, format_tally};
+pub use grapheme::truncate_graphemes;
+pub use id::{compose_id, parse_id, IdKind, IdPart};
+pub use inspect::summarize_bytes;
+pub use intern::{Interner, Symbol};
+pub use json::{canonicalize_json, hash_canonical_json, json_get, JsonError, JsonValue, JsonWriter};
+pub use kv::{kv_get_address, kv_get_u256, parse_kv, KvError};
+pub use layout::{center_x, estimate_text_width, FontFamily};
+pub use lines::{indent, numbered_lines, prefix_lines};
+pub use literal::{to_rust_literal, to_solidity_literal, SolidityStyle};
+pub use log::{decimal_digits, hex_bytes, log10, log256};
+pub use mask::{apply_mask_string, format_hex_masked, format_hex_masked_with, BitField, MASK_PLACEHOLDER};
+pub use message::{msgf, msgf_with, Arg, ErrorRegistry, TemplateError};
+pub use narrow::{to_string_expect_u128, to_string_expect_u32, to_string_expect_u64, RangeError};
+pub use normalize::{Normalizer, NormalizerBuilder};
+pub use orderbook::{format_price_level, format_tick_price, Market};
+pub use ownership::{format_packed_ownership, OwnershipLayout};
+pub use packed::{EncodePacked, PackError};
+pub use parse::{hex_decode_to, parse_decimal, parse_hex, parse_u256, try_u256_from_str, ParseError, TryFromHexBytes};
+pub use progress::{format_progress, format_progress_bar};
+pub use qr::{qr_matrix, qr_to_svg_rects, qr_to_unicode, EcLevel, QrError};
+pub use query::{parse_query, parse_query_with, query_get_u256, QueryError, QueryMode};
+pub use rarity::{format_rank, format_rarity_score, format_trait_rarity};
+pub use retryable::{format_aliased, format_retryable, l1_to_l2_alias};
+pub use revert::{format_panic, panic_code_name};
+pub use sanitize::{sanitize_amount_input, sanitize_amount_input_with, DecimalSeparator};
+pub use schedule::{describe_period, next_occurrence_string};
+pub use search::{count_occurrences, index_of, last_index_of, replace_all, replace_first, SearchError};
+pub use seed::{trait_from_seed, trait_from_seed_unbiased, weighted_trait_from_seed};
+pub use selector::{compute_selector, format_selector, selector_signature, COMMON_SELECTORS};
+pub use semver::{compare_semver, format_semver, parse_semver};
+pub use stats::{format_histogram, format_percentiles, format_stats};
+pub use stream::{format_flow_rate, format_streamed_total};
+pub use svg::{estimate_embedded_size, svg_data_uri, svg_data_uri_base64, svg_data_uri_utf8, SvgEmbedding};
+pub use t9::{t9_candidates, t9_encode, CharsetError};
+pub use table::format_table;
+pub use time::{format_date_long, format_date_long_with, format_date_short, format_date_short_with, format_duration, format_iso8601, format_relative, weekday, weekday_with, DateNames, LongDateOrder};
+pub use trade::{format_price_impact, format_slippage};
+pub use tx::format_tx_summary;
+pub use units::{abbreviate_address, format_byte_size, format_ether_in, format_ether_smart, format_units, EtherUnit};
+pub use uri::{join_uri, normalize_base_uri, normalize_base_uri_with, resolve_token_uri, UriError, DEFAULT_URI_SCHEMES, MAX_BASE_URI_LEN};
+pub use userop::format_user_op;
+pub use vesting::format_vesting_schedule;
+pub use width::{display_width, pad_left, pad_right, text_width, WidthMode};
+pub use wrappers::{AnyNum, Dec, Fixed, Hex, Radix};
+
 /// Hex digits constant used for hex string conversion
 const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
 
+/// Uppercase hex digits for `HexOptions::uppercase`
+const HEX_DIGITS_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Digits for radix conversion up to base 36
+const RADIX_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
 /// Address length in bytes (20 bytes = 40 hex characters)
 const ADDRESS_LENGTH: usize = 20;
 
@@ -17,6 +174,10 @@ const ADDRESS_LENGTH: usize = 20;
 pub enum StringsError {
     /// Hex length insufficient for the given value
     InsufficientHexLength { value: U256, length: usize },
+    /// Decimal width insufficient for the given value, which needs `required` digits
+    InsufficientDecimalLength { value: U256, digits: usize, required: usize },
+    /// Digit group size must be non-zero
+    InvalidGroupSize,
 }
 
 /// Converts a U256 value to its ASCII decimal string representation.
@@ -30,12 +191,7 @@ pub fn to_string(value: U256) -> String {
     }
     
     // Count digits first
-    let mut temp = value;
-    let mut digits = 0;
-    while !temp.is_zero() {
-        digits += 1;
-        temp /= U256::from(10);
-    }
+    let mut digits = decimal_digits(value) as usize;
     
     // Create buffer
     let mut buffer = vec![0u8; digits];
@@ -51,13 +207,62 @@ pub fn to_string(value: U256) -> String {
     String::from_utf8(buffer).expect("Invalid UTF-8 from digits")
 }
 
+/// Converts a U256 value to a decimal string of exactly `digits` characters,
+/// zero-padded on the left.
+/// 
+/// The decimal counterpart of `to_hex_string_with_length`: a value with more
+/// digits than `digits` is an error rather than a longer string. Like the hex
+/// version, zero fits in a width of 0 and renders as the empty string.
+pub fn to_string_fixed(value: U256, digits: usize) -> Result<String, StringsError> {
+    if value.is_zero() {
+        return Ok("0".repeat(digits));
+    }
+    let required = decimal_digits(value) as usize;
+    if required > digits {
+        return Err(StringsError::InsufficientDecimalLength { value, digits, required });
+    }
+    
+    let mut result = "0".repeat(digits - required);
+    result.push_str(&to_string(value));
+    Ok(result)
+}
+
+/// Returns 10^exp, or None if it does not fit in a U256 (exp > 77).
+pub(crate) fn pow10(exp: usize) -> Option<U256> {
+    U256::from(10).checked_pow(U256::from(exp))
+}
+
+/// Converts a U256 value to its ASCII representation in the given radix.
+/// 
+/// Uses lowercase letters for digits above 9 and no prefix. Panics if
+/// `radix` is outside `2..=36`.
+pub fn to_string_radix(value: U256, radix: u8) -> String {
+    assert!((2..=36).contains(&radix), "radix must be in 2..=36");
+    if value.is_zero() {
+        return "0".to_string();
+    }
+    
+    let base = U256::from(radix);
+    let mut buffer = Vec::new();
+    let mut remaining = value;
+    
+    // Collect digits least-significant first, then reverse
+    while !remaining.is_zero() {
+        buffer.push(RADIX_DIGITS[(remaining % base).to::<usize>()]);
+        remaining /= base;
+    }
+    buffer.reverse();
+    
+    String::from_utf8(buffer).expect("Invalid UTF-8 from radix digits")
+}
+
 /// Converts an I256 (signed integer) to its ASCII decimal string representation.
 /// 
 /// This function replicates OpenZeppelin's `toStringSigned(int256)` function.
 /// It handles negative values by prepending a minus sign.
 pub fn to_string_signed(value: I256) -> String {
     if value < I256::ZERO {
-        format!("-{}", to_string((-value).into_raw()))
+        format!("-{}", to_string(value.unsigned_abs()))
     } else {
         to_string(value.into_raw())
     }
@@ -68,20 +273,8 @@ pub fn to_string_signed(value: I256) -> String {
 /// This function replicates OpenZeppelin's `toHexString(uint256)` function.
 /// It returns "0x00" for zero and uses variable length for other values.
 pub fn to_hex_string(value: U256) -> String {
-    if value.is_zero() {
-        return "0x00".to_string();
-    }
-    
-    // Calculate required length in bytes (each byte = 2 hex chars)
-    let mut temp = value;
-    let mut length = 0;
-    while !temp.is_zero() {
-        length += 1;
-        temp >>= 8;
-    }
-    
-    // Use the fixed-length version with calculated length
-    to_hex_string_with_length(value, length).unwrap()
+    // Stripping whole zero bytes gives the minimal byte length, and "0x00" for zero
+    hex_encode(value.to_be_bytes::<32>(), HexOptions::new().strip_leading_zeros(true))
 }
 
 /// Converts a U256 value to its ASCII hexadecimal string representation with fixed length.
@@ -110,20 +303,122 @@ pub fn to_hex_string_with_length(value: U256, length: usize) -> Result<String, S
     Ok(String::from_utf8(buffer).expect("Invalid UTF-8 from hex digits"))
 }
 
+/// Converts a U256 value to a fixed-length hex string of `length` bytes with
+/// `separator` between every `group` hex digits, e.g. "0x0000_00ff" for
+/// length 4 and group 4.
+/// 
+/// Groups are counted from the right, so when `group` does not divide the
+/// `2 * length` digits the leftmost group is shorter. The result has
+/// `2 + 2 * length` characters plus one per separator. Fails like
+/// `to_hex_string_with_length` if the value does not fit, and for a `group`
+/// of 0.
+pub fn to_hex_string_fixed_grouped(
+    value: U256,
+    length: usize,
+    group: usize,
+    separator: char,
+) -> Result<String, StringsError> {
+    if group == 0 {
+        return Err(StringsError::InvalidGroupSize);
+    }
+    let hex = to_hex_string_with_length(value, length)?;
+    let mut result = String::from("0x");
+    result.push_str(&fluent::group_digits(&hex[2..], group, separator));
+    Ok(result)
+}
+
+/// Removes every `separator` from a string, the inverse of
+/// `to_hex_string_fixed_grouped` and other output grouped with that
+/// separator.
+/// 
+/// Only the separator is dropped, so a decimal point or any other stray
+/// character is kept for `parse_hex` or `parse_decimal` to reject.
+pub fn strip_grouping(s: &str, separator: char) -> String {
+    s.chars().filter(|&c| c != separator).collect()
+}
+
 /// Converts an Address to its ASCII hexadecimal string representation (not checksummed).
 /// 
 /// This function replicates OpenZeppelin's `toHexString(address)` function.
 /// It converts the address to a 40-character hex string with "0x" prefix.
 pub fn address_to_hex_string(addr: Address) -> String {
     // Convert address bytes directly to hex string
-    let mut result = String::with_capacity(42); // 40 hex chars + "0x"
-    result.push_str("0x");
-    
-    for &byte in addr.as_slice() {
-        result.push(HEX_DIGITS[(byte >> 4) as usize] as char);
-        result.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+    bytes_to_hex_string(addr.as_slice())
+}
+
+/// Converts raw bytes to a "0x"-prefixed lowercase hex string, two digits per byte.
+pub(crate) fn bytes_to_hex_string(bytes: &[u8]) -> String {
+    hex_encode(bytes, HexOptions::new())
+}
+
+/// Output options for `hex_encode`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexOptions {
+    prefix: bool,
+    uppercase: bool,
+    strip_leading_zeros: bool,
+}
+
+impl HexOptions {
+    /// "0x"-prefixed lowercase digits for every byte, as `address_to_hex_string`.
+    pub const fn new() -> Self {
+        Self { prefix: true, uppercase: false, strip_leading_zeros: false }
+    }
+
+    /// Whether to start with "0x" (the default).
+    pub const fn prefix(mut self, prefix: bool) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Whether to use `A`-`F` for digits; the "0x" prefix stays lowercase.
+    pub const fn uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// Whether to drop leading zero bytes, reading the input as a big-endian
+    /// number.
+    ///
+    /// Whole bytes are stripped, never single nibbles, so the output always
+    /// has an even number of digits like `to_hex_string`: `[0x00, 0x0f]`
+    /// gives "0x0f", not "0xf". Input that is empty or all zero keeps one
+    /// byte and gives "0x00".
+    pub const fn strip_leading_zeros(mut self, strip: bool) -> Self {
+        self.strip_leading_zeros = strip;
+        self
+    }
+}
+
+impl Default for HexOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hex-encodes any byte container — `Address`, `B256`, `FixedBytes<N>`,
+/// `Vec<u8>`, arrays or slices — two digits per byte.
+///
+/// The typed converters (`to_hex_string`, `address_to_hex_string`) delegate
+/// here, so equal bytes always produce equal output.
+pub fn hex_encode(data: impl AsRef<[u8]>, opts: HexOptions) -> String {
+    let mut bytes = data.as_ref();
+    if opts.strip_leading_zeros {
+        bytes = match bytes.iter().position(|&b| b != 0) {
+            Some(first) => &bytes[first..],
+            None => &[0],
+        };
+    }
+    let digits = if opts.uppercase { HEX_DIGITS_UPPER } else { HEX_DIGITS };
+
+    let mut result = String::with_capacity(2 + 2 * bytes.len());
+    if opts.prefix {
+        result.push_str("0x");
+    }
+    for &byte in bytes {
+        result.push(digits[(byte >> 4) as usize] as char);
+        result.push(digits[(byte & 0xf) as usize] as char);
     }
-    
     result
 }
 
@@ -179,6 +474,45 @@ mod tests {
         assert_eq!(to_string(large_num), u128::MAX.to_string());
     }
     
+    #[test]
+    fn test_to_string_fixed() {
+        assert_eq!(to_string_fixed(U256::from(12345), 5).unwrap(), "12345");
+        assert_eq!(to_string_fixed(U256::from(42), 6).unwrap(), "000042");
+        assert_eq!(to_string_fixed(U256::ZERO, 3).unwrap(), "000");
+        assert_eq!(to_string_fixed(U256::MAX, 78).unwrap(), to_string(U256::MAX));
+        assert_eq!(to_string_fixed(U256::from(7), 80).unwrap().len(), 80);
+    }
+    
+    #[test]
+    fn test_to_string_fixed_overflow() {
+        assert!(matches!(
+            to_string_fixed(U256::from(123456), 4),
+            Err(StringsError::InsufficientDecimalLength { digits: 4, required: 6, .. })
+        ));
+        assert!(matches!(
+            to_string_fixed(U256::MAX, 77),
+            Err(StringsError::InsufficientDecimalLength { required: 78, .. })
+        ));
+    }
+    
+    #[test]
+    fn test_to_string_fixed_width_zero() {
+        assert_eq!(to_string_fixed(U256::ZERO, 0).unwrap(), "");
+        assert!(matches!(
+            to_string_fixed(U256::from(1), 0),
+            Err(StringsError::InsufficientDecimalLength { digits: 0, required: 1, .. })
+        ));
+    }
+    
+    #[test]
+    fn test_to_string_radix() {
+        assert_eq!(to_string_radix(U256::ZERO, 2), "0");
+        assert_eq!(to_string_radix(U256::from(10), 2), "1010");
+        assert_eq!(to_string_radix(U256::from(8), 8), "10");
+        assert_eq!(to_string_radix(U256::from(35), 36), "z");
+        assert_eq!(to_string_radix(U256::MAX, 10), to_string(U256::MAX));
+    }
+    
     #[test]
     fn test_to_string_signed() {
         assert_eq!(to_string_signed(I256::ZERO), "0");
@@ -188,6 +522,20 @@ mod tests {
         assert_eq!(to_string_signed(I256::try_from(i64::MIN).unwrap()), i64::MIN.to_string());
     }
     
+    #[test]
+    fn test_to_string_signed_extremes() {
+        // Negating int256 min overflows, so its magnitude is taken unsigned
+        assert_eq!(
+            to_string_signed(I256::MIN),
+            "-57896044618658097711785492504343953926634992332820282019728792003956564819968"
+        );
+        assert_eq!(
+            to_string_signed(I256::MAX),
+            "57896044618658097711785492504343953926634992332820282019728792003956564819967"
+        );
+        assert_eq!(to_string_signed(I256::MINUS_ONE), "-1");
+    }
+    
     #[test]
     fn test_to_hex_string_basic() {
         assert_eq!(to_hex_string(U256::ZERO), "0x00");
@@ -212,6 +560,46 @@ mod tests {
         assert!(matches!(result, Err(StringsError::InsufficientHexLength { .. })));
     }
     
+    #[test]
+    fn test_to_hex_string_fixed_grouped() {
+        let word = to_hex_string_fixed_grouped(U256::from(0xdeadbeefu64), 32, 8, '_').unwrap();
+        assert_eq!(word, format!("0x{}deadbeef", "00000000_".repeat(7)));
+        assert_eq!(word.len(), 2 + 64 + 7);
+        assert_eq!(to_hex_string_fixed_grouped(U256::ZERO, 1, 2, '_').unwrap(), "0x00");
+    }
+    
+    #[test]
+    fn test_to_hex_string_fixed_grouped_uneven() {
+        // 6 digits in groups of 4: the leftmost group is shorter
+        assert_eq!(to_hex_string_fixed_grouped(U256::from(0x12345), 3, 4, ' ').unwrap(), "0x01 2345");
+        assert_eq!(to_hex_string_fixed_grouped(U256::from(0xabc), 2, 3, ',').unwrap(), "0x0,abc");
+    }
+    
+    #[test]
+    fn test_to_hex_string_fixed_grouped_errors() {
+        assert!(matches!(
+            to_hex_string_fixed_grouped(U256::from(1), 32, 0, '_'),
+            Err(StringsError::InvalidGroupSize)
+        ));
+        assert!(matches!(
+            to_hex_string_fixed_grouped(U256::from(0x100), 1, 2, '_'),
+            Err(StringsError::InsufficientHexLength { length: 1, .. })
+        ));
+    }
+    
+    #[test]
+    fn test_strip_grouping_round_trip() {
+        for value in [U256::ZERO, U256::from(0x12345), U256::MAX] {
+            let grouped = to_hex_string_fixed_grouped(value, 32, 5, '_').unwrap();
+            assert_eq!(parse_hex(&strip_grouping(&grouped, '_')).unwrap(), value);
+        }
+        assert_eq!(strip_grouping("1,234,567", ','), "1234567");
+        assert_eq!(strip_grouping("1,234.5", ','), "1234.5");
+        assert_eq!(strip_grouping("1 234 567", ' '), "1234567");
+        assert!(parse_hex(&strip_grouping("0x12_3g", '_')).is_err());
+        assert!(parse_hex(&strip_grouping("0x12,3", '_')).is_err());
+    }
+    
     #[test]
     fn test_address_to_hex_string() {
         let zero_addr = Address::ZERO;
@@ -224,6 +612,54 @@ mod tests {
     assert_eq!(address_to_hex_string(addr), "0x123456789abcdef0112233445566778899aabbcc");
     }
     
+    #[test]
+    fn test_hex_encode_same_bytes_same_output() {
+        use alloy_primitives::{FixedBytes, B256};
+
+        let raw: [u8; 20] = core::array::from_fn(|i| (i * 13) as u8);
+        let addr = Address::from_slice(&raw);
+        let expected = "0x000d1a2734414e5b6875828f9ca9b6c3d0ddeaf7";
+        assert_eq!(address_to_hex_string(addr), expected);
+        assert_eq!(hex_encode(addr, HexOptions::new()), expected);
+        assert_eq!(hex_encode(FixedBytes::<20>::from(raw), HexOptions::new()), expected);
+        assert_eq!(hex_encode(raw, HexOptions::new()), expected);
+        let vec: Vec<u8> = raw.into();
+        assert_eq!(hex_encode(vec, HexOptions::new()), expected);
+        assert_eq!(hex_encode(&raw[..], HexOptions::default()), expected);
+
+        let value = U256::from(0xabcdefu64);
+        let word = B256::from(value.to_be_bytes::<32>());
+        let stripped = HexOptions::new().strip_leading_zeros(true);
+        assert_eq!(hex_encode(word, stripped), to_hex_string(value));
+        assert_eq!(hex_encode(word, stripped), "0xabcdef");
+        assert_eq!(hex_encode(word, HexOptions::new()), format!("0x{}abcdef", "0".repeat(58)));
+    }
+
+    #[test]
+    fn test_hex_encode_options() {
+        let bytes = [0x00, 0xab, 0x0c];
+        assert_eq!(hex_encode(bytes, HexOptions::new()), "0x00ab0c");
+        assert_eq!(hex_encode(bytes, HexOptions::new().prefix(false)), "00ab0c");
+        assert_eq!(hex_encode(bytes, HexOptions::new().uppercase(true)), "0x00AB0C");
+        assert_eq!(hex_encode(bytes, HexOptions::new().prefix(false).uppercase(true)), "00AB0C");
+        assert_eq!(hex_encode([] as [u8; 0], HexOptions::new()), "0x");
+    }
+
+    #[test]
+    fn test_hex_encode_strip_semantics() {
+        let strip = HexOptions::new().strip_leading_zeros(true);
+        // Whole bytes are stripped, never the high nibble of the first byte
+        assert_eq!(hex_encode([0x00, 0x00, 0x0f], strip), "0x0f");
+        assert_eq!(hex_encode([0x00, 0x10, 0x00], strip), "0x1000");
+        // Interior and trailing zeros are kept
+        assert_eq!(hex_encode([0x01, 0x00, 0x00], strip), "0x010000");
+        // Zero keeps one byte, matching to_hex_string
+        assert_eq!(hex_encode([0u8; 32], strip), "0x00");
+        assert_eq!(hex_encode([] as [u8; 0], strip), "0x00");
+        assert_eq!(hex_encode([0u8; 32], strip), to_hex_string(U256::ZERO));
+        assert_eq!(hex_encode([0x00, 0xab], strip.prefix(false).uppercase(true)), "AB");
+    }
+
     #[test]
     fn test_address_to_checksum_hex_string() {
         // Test with zero address (should remain all lowercase)
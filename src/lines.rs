@@ -0,0 +1,109 @@
+//! Line-oriented helpers for composing nested multi-line output.
+//!
+//! Lines are split on `\n` and a trailing newline is preserved. Empty lines
+//! are never given trailing whitespace.
+
+/// Indents every non-empty line by `spaces` spaces.
+pub fn indent(s: &str, spaces: usize) -> String {
+    map_lines(s, |_, line, out| {
+        if !line.is_empty() {
+            out.extend(core::iter::repeat_n(' ', spaces));
+            out.push_str(line);
+        }
+    })
+}
+
+/// Prefixes every line with `prefix`, e.g. "> " for quoting. Empty lines
+/// get the prefix with trailing whitespace removed (">").
+pub fn prefix_lines(s: &str, prefix: &str) -> String {
+    map_lines(s, |_, line, out| {
+        if line.is_empty() {
+            out.push_str(prefix.trim_end());
+        } else {
+            out.push_str(prefix);
+            out.push_str(line);
+        }
+    })
+}
+
+/// Numbers every line as "N | line", right-aligning the numbers to the
+/// width of the largest one.
+pub fn numbered_lines(s: &str) -> String {
+    let width = line_count(s).to_string().len();
+    map_lines(s, |index, line, out| {
+        let number = format!("{:>width$}", index + 1, width = width);
+        out.push_str(&number);
+        if line.is_empty() {
+            out.push_str(" |");
+        } else {
+            out.push_str(" | ");
+            out.push_str(line);
+        }
+    })
+}
+
+/// Number of lines in `s`; a trailing newline does not start a new line.
+fn line_count(s: &str) -> usize {
+    s.split_inclusive('\n').count()
+}
+
+/// Rebuilds `s` line by line, letting `f` write each line (without its
+/// newline) and re-appending the newlines.
+fn map_lines(s: &str, mut f: impl FnMut(usize, &str, &mut String)) -> String {
+    let mut result = String::with_capacity(s.len());
+    for (index, line) in s.split_inclusive('\n').enumerate() {
+        match line.strip_suffix('\n') {
+            Some(content) => {
+                f(index, content, &mut result);
+                result.push('\n');
+            }
+            None => f(index, line, &mut result),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_string() {
+        assert_eq!(indent("", 2), "");
+        assert_eq!(prefix_lines("", "> "), "");
+        assert_eq!(numbered_lines(""), "");
+    }
+
+    #[test]
+    fn test_single_line() {
+        assert_eq!(indent("abc", 4), "    abc");
+        assert_eq!(prefix_lines("abc", "> "), "> abc");
+        assert_eq!(numbered_lines("abc"), "1 | abc");
+    }
+
+    #[test]
+    fn test_trailing_newline() {
+        assert_eq!(indent("a\nb\n", 2), "  a\n  b\n");
+        assert_eq!(indent("a\n\nb", 2), "  a\n\n  b");
+        assert_eq!(prefix_lines("a\n\nb\n", "> "), "> a\n>\n> b\n");
+        assert_eq!(numbered_lines("a\n\nb\n"), "1 | a\n2 |\n3 | b\n");
+    }
+
+    #[test]
+    fn test_nested_indent() {
+        assert_eq!(indent(&indent("x\ny", 2), 2), "    x\n    y");
+    }
+
+    #[test]
+    fn test_number_alignment() {
+        let input: String = (1..=100).map(|i| format!("line {}\n", i)).collect();
+        let numbered = numbered_lines(&input);
+        let lines: Vec<&str> = numbered.lines().collect();
+        assert_eq!(lines.len(), 100);
+        assert_eq!(lines[0], "  1 | line 1");
+        assert_eq!(lines[9], " 10 | line 10");
+        assert_eq!(lines[99], "100 | line 100");
+        assert_eq!(numbered_lines("a\nb\nc\nd\ne\nf\ng\nh\ni\nj").lines().next(), Some(" 1 | a"));
+        assert!(numbered_lines("a\nb\nc\nd\ne\nf\ng\nh\ni").starts_with("1 | a"));
+    }
+}
@@ -0,0 +1,121 @@
+//! Source-code literals for `U256` values, for build scripts that generate
+//! Rust or Solidity constants.
+
+use alloy_primitives::U256;
+
+use crate::fluent::group_digits;
+use crate::{to_hex_string, to_string};
+
+/// How `to_solidity_literal` writes a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SolidityStyle {
+    /// Hex in whole bytes, e.g. "0x0f4240"
+    #[default]
+    Hex,
+    /// Decimal grouped in threes with underscores, e.g. "1_000_000"
+    Decimal,
+}
+
+/// Returns a Rust expression evaluating to `value`.
+///
+/// Values up to `u128::MAX` use `U256::from` with a grouped decimal `u128`
+/// literal; anything larger uses `U256::from_limbs`, least significant limb
+/// first as `from_limbs` expects, and is usable in `const` items.
+///
+/// ```
+/// use alloy_primitives::U256;
+/// use strings_utils_stylus::to_rust_literal;
+///
+/// let small = U256::from(1_000_000u128);
+/// assert_eq!(to_rust_literal(small), "U256::from(1_000_000u128)");
+///
+/// const LARGE: U256 = U256::from_limbs([0x0, 0x0, 0x1, 0x0]);
+/// assert_eq!(to_rust_literal(LARGE), "U256::from_limbs([0x0, 0x0, 0x1, 0x0])");
+/// ```
+pub fn to_rust_literal(value: U256) -> String {
+    if let Ok(small) = u128::try_from(value) {
+        return format!("U256::from({}u128)", group_digits(&small.to_string(), 3, '_'));
+    }
+    let limbs: Vec<String> = value.as_limbs().iter().map(|limb| format!("{:#x}", limb)).collect();
+    format!("U256::from_limbs([{}])", limbs.join(", "))
+}
+
+/// Returns a Solidity literal for `value` in the given style.
+///
+/// Hex literals of 39 to 41 digits are taken by solc to be addresses and
+/// rejected unless checksummed, so a 20-byte value gets one extra leading
+/// zero byte.
+pub fn to_solidity_literal(value: U256, style: SolidityStyle) -> String {
+    match style {
+        SolidityStyle::Hex => {
+            let hex = to_hex_string(value);
+            if hex.len() == 2 + 40 {
+                format!("0x00{}", &hex[2..])
+            } else {
+                hex
+            }
+        }
+        SolidityStyle::Decimal => group_digits(&to_string(value), 3, '_'),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rust_small_and_u64() {
+        assert_eq!(to_rust_literal(U256::ZERO), "U256::from(0u128)");
+        assert_eq!(to_rust_literal(U256::from(999)), "U256::from(999u128)");
+        assert_eq!(to_rust_literal(U256::from(1000)), "U256::from(1_000u128)");
+        assert_eq!(to_rust_literal(U256::from(u64::MAX)), "U256::from(18_446_744_073_709_551_615u128)");
+    }
+
+    #[test]
+    fn test_rust_u128_boundary() {
+        assert_eq!(
+            to_rust_literal(U256::from(u128::MAX)),
+            "U256::from(340_282_366_920_938_463_463_374_607_431_768_211_455u128)"
+        );
+        assert_eq!(
+            to_rust_literal(U256::from(u128::MAX) + U256::from(1)),
+            "U256::from_limbs([0x0, 0x0, 0x1, 0x0])"
+        );
+    }
+
+    #[test]
+    fn test_rust_full_width() {
+        assert_eq!(
+            to_rust_literal(U256::MAX),
+            "U256::from_limbs([0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff])"
+        );
+        let value = U256::from_limbs([0x1234, 0, 0xabcdef, 1 << 63]);
+        assert_eq!(to_rust_literal(value), "U256::from_limbs([0x1234, 0x0, 0xabcdef, 0x8000000000000000])");
+    }
+
+    #[test]
+    fn test_solidity_styles() {
+        assert_eq!(to_solidity_literal(U256::ZERO, SolidityStyle::Hex), "0x00");
+        assert_eq!(to_solidity_literal(U256::ZERO, SolidityStyle::Decimal), "0");
+        assert_eq!(to_solidity_literal(U256::from(1_000_000), SolidityStyle::Hex), "0x0f4240");
+        assert_eq!(to_solidity_literal(U256::from(1_000_000), SolidityStyle::Decimal), "1_000_000");
+        assert_eq!(to_solidity_literal(U256::from(u64::MAX), SolidityStyle::Hex), "0xffffffffffffffff");
+        assert_eq!(
+            to_solidity_literal(U256::from(u128::MAX), SolidityStyle::Decimal),
+            "340_282_366_920_938_463_463_374_607_431_768_211_455"
+        );
+        assert_eq!(
+            to_solidity_literal(U256::MAX, SolidityStyle::Decimal),
+            "115_792_089_237_316_195_423_570_985_008_687_907_853_269_984_665_640_564_039_457_584_007_913_129_639_935"
+        );
+        assert_eq!(to_solidity_literal(U256::MAX, SolidityStyle::Hex), format!("0x{}", "f".repeat(64)));
+    }
+
+    #[test]
+    fn test_solidity_hex_avoids_address_length() {
+        let value = U256::from(1) << 152;
+        assert_eq!(to_solidity_literal(value, SolidityStyle::Hex), format!("0x0001{}", "0".repeat(38)));
+        assert_eq!(to_solidity_literal(value >> 8, SolidityStyle::Hex).len(), 2 + 38);
+        assert_eq!(to_solidity_literal(value << 8, SolidityStyle::Hex).len(), 2 + 42);
+    }
+}
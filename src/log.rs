@@ -0,0 +1,97 @@
+//! Integer logarithms of `U256` values, matching OpenZeppelin's `Math.sol`.
+//!
+//! Like OpenZeppelin, `log10(0)` and `log256(0)` are defined as 0, so they
+//! are one less than the digit or byte count only for non-zero values:
+//! `decimal_digits` and `hex_bytes` add the one back and report 1 for zero,
+//! which is what `to_string` and `to_hex_string` print.
+
+use alloy_primitives::U256;
+
+use crate::pow10;
+
+/// Floor of the base-10 logarithm, 0 for 0 (as in `Math.log10`).
+///
+/// Rather than dividing repeatedly, the bit length gives an estimate via
+/// `bits * 1233 >> 12` (1233 / 4096 is just under log10(2)), which is
+/// either exact or one too high; a single comparison against that power of
+/// ten corrects it.
+pub fn log10(value: U256) -> u32 {
+    let estimate = (value.bit_len() as u32 * 1233) >> 12;
+    // At most 256 * 1233 >> 12 = 77, and 10^77 fits in a U256
+    let power = pow10(estimate as usize).expect("estimate is at most 77");
+    if value < power {
+        estimate.saturating_sub(1)
+    } else {
+        estimate
+    }
+}
+
+/// Floor of the base-256 logarithm, 0 for 0 (as in `Math.log256`).
+pub fn log256(value: U256) -> u32 {
+    (value.bit_len() as u32).saturating_sub(1) / 8
+}
+
+/// Counts the decimal digits of `value`; zero counts as one digit,
+/// matching the output of `to_string`.
+pub fn decimal_digits(value: U256) -> u32 {
+    log10(value) + 1
+}
+
+/// Counts the bytes needed to represent `value`; zero counts as one byte,
+/// matching the "0x00" of `to_hex_string`.
+pub fn hex_bytes(value: U256) -> u32 {
+    log256(value) + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_hex_string, to_string};
+
+    #[test]
+    fn test_zero_and_max() {
+        assert_eq!(log10(U256::ZERO), 0);
+        assert_eq!(log256(U256::ZERO), 0);
+        assert_eq!(decimal_digits(U256::ZERO), 1);
+        assert_eq!(hex_bytes(U256::ZERO), 1);
+        assert_eq!(log10(U256::MAX), 77);
+        assert_eq!(log256(U256::MAX), 31);
+        assert_eq!(decimal_digits(U256::MAX), 78);
+        assert_eq!(hex_bytes(U256::MAX), 32);
+    }
+
+    #[test]
+    fn test_power_of_ten_boundaries() {
+        for k in 1..=77u32 {
+            let power = pow10(k as usize).unwrap();
+            assert_eq!(log10(power - U256::from(1)), k - 1, "10^{} - 1", k);
+            assert_eq!(log10(power), k, "10^{}", k);
+            assert_eq!(log10(power + U256::from(1)), k, "10^{} + 1", k);
+            assert_eq!(decimal_digits(power - U256::from(1)), k);
+            assert_eq!(decimal_digits(power), k + 1);
+        }
+        // 10^78 would overflow, so the last boundary is U256::MAX itself
+        assert_eq!(decimal_digits(U256::MAX), to_string(U256::MAX).len() as u32);
+    }
+
+    #[test]
+    fn test_power_of_256_boundaries() {
+        assert_eq!(log256(U256::from(1)), 0);
+        assert_eq!(log256(U256::from(255)), 0);
+        for k in 1..32u32 {
+            let power = U256::from(1) << (8 * k as usize);
+            assert_eq!(log256(power - U256::from(1)), k - 1, "256^{} - 1", k);
+            assert_eq!(log256(power), k, "256^{}", k);
+            assert_eq!(hex_bytes(power - U256::from(1)), k);
+            assert_eq!(hex_bytes(power), k + 1);
+        }
+    }
+
+    #[test]
+    fn test_consistent_with_formatting() {
+        for value in [U256::from(1), U256::from(9), U256::from(255), U256::from(256), U256::from(u64::MAX), U256::MAX >> 3] {
+            assert_eq!(decimal_digits(value) as usize, to_string(value).len());
+            assert_eq!(hex_bytes(value) as usize, (to_hex_string(value).len() - 2) / 2);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Full-word hex with masked-out nibbles hidden, and bit fields, for
+//! inspecting packed storage words.
+
+use alloy_primitives::U256;
+
+use crate::to_hex_string_with_length;
+
+/// Placeholder used by `format_hex_masked` for nibbles outside the mask
+pub const MASK_PLACEHOLDER: char = '.';
+
+/// A run of `width` bits starting `offset` bits from the least significant
+/// end of a word
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitField {
+    pub offset: usize,
+    pub width: usize,
+}
+
+impl BitField {
+    /// Creates a field of `width` bits at `offset`.
+    pub const fn new(offset: usize, width: usize) -> Self {
+        Self { offset, width }
+    }
+
+    /// Returns the field's bits shifted down to bit 0. Bits past the top of
+    /// the word read as zero.
+    pub fn extract(self, word: U256) -> U256 {
+        if self.offset >= 256 {
+            return U256::ZERO;
+        }
+        (word >> self.offset) & low_bits(self.width)
+    }
+
+    /// Returns a mask covering the field in place, for `format_hex_masked`.
+    pub fn mask(self) -> U256 {
+        if self.offset >= 256 {
+            return U256::ZERO;
+        }
+        low_bits(self.width) << self.offset
+    }
+}
+
+/// Renders `value` as 32-byte hex, aligned with
+/// `to_hex_string_with_length(value, 32)`, with every nibble where `mask`
+/// is zero replaced by '.'.
+///
+/// Masking is per nibble: a nibble is shown in full if any of its mask bits
+/// are set, so a mask covering only part of a nibble still reveals all
+/// four value bits there.
+pub fn format_hex_masked(value: U256, mask: U256) -> String {
+    format_hex_masked_with(value, mask, MASK_PLACEHOLDER)
+}
+
+/// `format_hex_masked` with a custom placeholder character.
+pub fn format_hex_masked_with(value: U256, mask: U256, placeholder: char) -> String {
+    let value_hex = full_word(value);
+    let mask_hex = full_word(mask);
+    let mut result = String::with_capacity(value_hex.len());
+    result.push_str("0x");
+    for (digit, mask_digit) in value_hex[2..].chars().zip(mask_hex[2..].chars()) {
+        result.push(if mask_digit == '0' { placeholder } else { digit });
+    }
+    result
+}
+
+/// Renders `value & mask` as 32-byte hex, aligned with `format_hex_masked`.
+pub fn apply_mask_string(value: U256, mask: U256) -> String {
+    full_word(value & mask)
+}
+
+fn low_bits(width: usize) -> U256 {
+    if width >= 256 {
+        U256::MAX
+    } else {
+        (U256::from(1) << width) - U256::from(1)
+    }
+}
+
+fn full_word(value: U256) -> String {
+    to_hex_string_with_length(value, 32).expect("any U256 fits in 32 bytes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALUE: U256 = U256::from_limbs([0x1122_3344_5566_7788, 0, 0, 0xaabb_ccdd_0000_0000]);
+
+    #[test]
+    fn test_full_mask_is_plain_hex() {
+        assert_eq!(format_hex_masked(VALUE, U256::MAX), full_word(VALUE));
+        assert_eq!(apply_mask_string(VALUE, U256::MAX), full_word(VALUE));
+    }
+
+    #[test]
+    fn test_zero_mask_hides_everything() {
+        assert_eq!(format_hex_masked(VALUE, U256::ZERO), format!("0x{}", ".".repeat(64)));
+        assert_eq!(format_hex_masked_with(VALUE, U256::ZERO, '-'), format!("0x{}", "-".repeat(64)));
+        assert_eq!(apply_mask_string(VALUE, U256::ZERO), format!("0x{}", "0".repeat(64)));
+    }
+
+    #[test]
+    fn test_partial_nibble_shows_whole_nibble() {
+        // Mask bit 0 only: the lowest nibble 0x8 is shown in full
+        let masked = format_hex_masked(VALUE, U256::from(1));
+        assert_eq!(masked, format!("0x{}8", ".".repeat(63)));
+        // apply_mask_string keeps only the masked bit
+        assert_eq!(apply_mask_string(VALUE, U256::from(1)), format!("0x{}0", "0".repeat(63)));
+    }
+
+    #[test]
+    fn test_alignment_with_fixed_hex() {
+        let mask = U256::from(0xffff_0000u64) | (U256::from(0xff) << 248);
+        let masked = format_hex_masked(VALUE, mask);
+        let plain = to_hex_string_with_length(VALUE, 32).unwrap();
+        assert_eq!(masked.len(), plain.len());
+        assert_eq!(masked, format!("0xaa{}5566....", ".".repeat(54)));
+        for (shown, original) in masked.chars().zip(plain.chars()) {
+            assert!(shown == MASK_PLACEHOLDER || shown == original);
+        }
+        assert_eq!(apply_mask_string(VALUE, mask), format!("0xaa{}55660000", "0".repeat(54)));
+    }
+
+    #[test]
+    fn test_bit_field() {
+        let flag = BitField::new(224, 1);
+        assert_eq!(flag.extract(U256::from(1) << 224), U256::from(1));
+        assert_eq!(flag.mask(), U256::from(1) << 224);
+        assert_eq!(BitField::new(0, 160).extract(U256::MAX), (U256::from(1) << 160) - U256::from(1));
+        assert_eq!(BitField::new(192, 256).extract(U256::MAX), U256::from(u64::MAX));
+        assert_eq!(BitField::new(0, 256).mask(), U256::MAX);
+        assert_eq!(BitField::new(256, 8).extract(U256::MAX), U256::ZERO);
+        assert_eq!(format_hex_masked(VALUE, BitField::new(248, 8).mask()), format!("0xaa{}", ".".repeat(62)));
+    }
+}
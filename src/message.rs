@@ -0,0 +1,282 @@
+//! Positional message templates for revert strings.
+//!
+//! `msgf` covers the common revert-message cases without pulling in
+//! `core::fmt` machinery: arguments are rendered with the crate's own
+//! converters and spliced into `{0}`, `{1}`, … placeholders.
+
+use alloy_primitives::{Address, U256};
+
+use crate::bidi::{isolate_bidi, BidiMode};
+use crate::{address_to_hex_string, to_hex_string, to_string};
+
+/// Template argument for `msgf`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arg<'a> {
+    /// Rendered in decimal, as by `to_string`
+    U(U256),
+    /// Rendered in hex, as by `to_hex_string`
+    Hex(U256),
+    /// Rendered as lowercase hex, as by `address_to_hex_string`
+    Address(Address),
+    /// Inserted verbatim
+    Str(&'a str),
+    /// Rendered as "true" or "false"
+    Bool(bool),
+}
+
+/// Error types for malformed templates or missing arguments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateError {
+    /// Placeholder refers to an argument that was not supplied
+    UnknownIndex(usize),
+    /// `{` without a closing `}`, or a lone `}`, at this byte offset
+    UnbalancedBrace(usize),
+    /// Placeholder at this byte offset is not a decimal index
+    InvalidPlaceholder(usize),
+}
+
+impl Arg<'_> {
+    fn push_to(&self, out: &mut String, bidi: BidiMode) {
+        match *self {
+            Arg::U(value) => out.push_str(&to_string(value)),
+            Arg::Hex(value) => out.push_str(&to_hex_string(value)),
+            Arg::Address(addr) => out.push_str(&address_to_hex_string(addr)),
+            Arg::Str(s) if bidi == BidiMode::Isolate => out.push_str(&isolate_bidi(s)),
+            Arg::Str(s) => out.push_str(s),
+            Arg::Bool(true) => out.push_str("true"),
+            Arg::Bool(false) => out.push_str("false"),
+        }
+    }
+}
+
+/// Formats `template`, replacing `{N}` with the N-th argument.
+///
+/// Placeholders may repeat and appear in any order; `{{` and `}}` produce
+/// literal braces. Arguments that are never referenced are ignored.
+pub fn msgf(template: &str, args: &[Arg]) -> Result<String, TemplateError> {
+    msgf_with(template, args, BidiMode::Passthrough)
+}
+
+/// Formats `template` like `msgf`, with `BidiMode::Isolate` wrapping
+/// `Arg::Str` arguments that contain right-to-left text via `isolate_bidi`.
+///
+/// Only `Arg::Str` is treated as user text; numbers, hex and addresses are
+/// never right-to-left.
+pub fn msgf_with(template: &str, args: &[Arg], bidi: BidiMode) -> Result<String, TemplateError> {
+    let bytes = template.as_bytes();
+    let mut result = String::with_capacity(template.len());
+    let mut literal_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' if bytes.get(i + 1) == Some(&b'{') => {
+                result.push_str(&template[literal_start..=i]);
+                i += 2;
+                literal_start = i;
+            }
+            b'}' if bytes.get(i + 1) == Some(&b'}') => {
+                result.push_str(&template[literal_start..=i]);
+                i += 2;
+                literal_start = i;
+            }
+            b'{' => {
+                result.push_str(&template[literal_start..i]);
+                let close = template[i..]
+                    .find('}')
+                    .map(|offset| i + offset)
+                    .ok_or(TemplateError::UnbalancedBrace(i))?;
+                let inner = &template[i + 1..close];
+                if inner.contains('{') {
+                    return Err(TemplateError::UnbalancedBrace(i));
+                }
+                let index = parse_index(inner).ok_or(TemplateError::InvalidPlaceholder(i))?;
+                args.get(index)
+                    .ok_or(TemplateError::UnknownIndex(index))?
+                    .push_to(&mut result, bidi);
+                i = close + 1;
+                literal_start = i;
+            }
+            b'}' => return Err(TemplateError::UnbalancedBrace(i)),
+            _ => i += 1,
+        }
+    }
+
+    result.push_str(&template[literal_start..]);
+    Ok(result)
+}
+
+/// Maps numeric error codes to message templates for readable reverts.
+///
+/// A registry can be built at compile time from a static table with
+/// `from_static`, so it can live in a `static`, and extended at runtime with
+/// `register`. Registering a code that is already present replaces its
+/// template; runtime registrations take precedence over the static table.
+#[derive(Debug, Clone, Default)]
+pub struct ErrorRegistry<'a> {
+    table: &'a [(U256, &'a str)],
+    entries: Vec<(U256, &'a str)>,
+}
+
+impl<'a> ErrorRegistry<'a> {
+    /// Creates an empty registry.
+    pub const fn new() -> Self {
+        Self { table: &[], entries: Vec::new() }
+    }
+
+    /// Creates a registry backed by a static table of codes and templates.
+    pub const fn from_static(table: &'a [(U256, &'a str)]) -> Self {
+        Self { table, entries: Vec::new() }
+    }
+
+    /// Registers `template` for `code`, replacing any existing template.
+    pub fn register(mut self, code: U256, template: &'a str) -> Self {
+        match self.entries.iter_mut().find(|(c, _)| *c == code) {
+            Some(entry) => entry.1 = template,
+            None => self.entries.push((code, template)),
+        }
+        self
+    }
+
+    /// Returns the template registered for `code`, if any.
+    pub fn template(&self, code: U256) -> Option<&'a str> {
+        self.entries
+            .iter()
+            .chain(self.table.iter())
+            .find(|(c, _)| *c == code)
+            .map(|&(_, template)| template)
+    }
+
+    /// Formats the message for `code` with `args`.
+    ///
+    /// Unregistered codes produce "unknown error 0x…". If the arguments do
+    /// not satisfy the template (e.g. too few), the template is returned
+    /// unformatted so the error remains recognizable.
+    pub fn describe(&self, code: U256, args: &[Arg]) -> String {
+        match self.template(code) {
+            Some(template) => msgf(template, args).unwrap_or_else(|_| template.to_string()),
+            None => {
+                let mut result = String::from("unknown error ");
+                result.push_str(&to_hex_string(code));
+                result
+            }
+        }
+    }
+}
+
+fn parse_index(s: &str) -> Option<usize> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_each_arg_variant() {
+        let addr = Address::from_slice(&[0xab; 20]);
+        assert_eq!(msgf("{0}", &[Arg::U(U256::from(1234))]).unwrap(), "1234");
+        assert_eq!(msgf("{0}", &[Arg::Hex(U256::from(255))]).unwrap(), "0xff");
+        assert_eq!(msgf("{0}", &[Arg::Address(addr)]).unwrap(), format!("0x{}", "ab".repeat(20)));
+        assert_eq!(msgf("{0}", &[Arg::Str("hi")]).unwrap(), "hi");
+        assert_eq!(msgf("{0}/{1}", &[Arg::Bool(true), Arg::Bool(false)]).unwrap(), "true/false");
+    }
+
+    #[test]
+    fn test_revert_message() {
+        let have = U256::from(5);
+        let need = U256::from(10);
+        assert_eq!(
+            msgf("insufficient balance: have {0}, need {1}", &[Arg::U(have), Arg::U(need)]).unwrap(),
+            "insufficient balance: have 5, need 10"
+        );
+    }
+
+    #[test]
+    fn test_repeated_and_reordered_placeholders() {
+        let args = [Arg::Str("a"), Arg::Str("b")];
+        assert_eq!(msgf("{1}{0}{1}{1}", &args).unwrap(), "babb");
+        assert_eq!(msgf("no placeholders", &args).unwrap(), "no placeholders");
+    }
+
+    #[test]
+    fn test_literal_braces() {
+        assert_eq!(msgf("{{0}} is {0}", &[Arg::U(U256::from(7))]).unwrap(), "{0} is 7");
+        assert_eq!(msgf("}}{{", &[]).unwrap(), "}{");
+    }
+
+    const INSUFFICIENT_BALANCE: U256 = U256::from_limbs([1, 0, 0, 0]);
+    const UNAUTHORIZED: U256 = U256::from_limbs([2, 0, 0, 0]);
+
+    static TABLE: [(U256, &str); 2] = [
+        (INSUFFICIENT_BALANCE, "insufficient balance: have {0}, need {1}"),
+        (UNAUTHORIZED, "unauthorized"),
+    ];
+
+    static REGISTRY: ErrorRegistry<'static> = ErrorRegistry::from_static(&TABLE);
+
+    #[test]
+    fn test_registry_describes_registered_code() {
+        let registry = ErrorRegistry::new().register(U256::from(7), "limit {0} exceeded");
+        assert_eq!(registry.describe(U256::from(7), &[Arg::U(U256::from(100))]), "limit 100 exceeded");
+    }
+
+    #[test]
+    fn test_registry_unknown_code() {
+        assert_eq!(ErrorRegistry::new().describe(U256::from(0x1f), &[]), "unknown error 0x1f");
+        assert_eq!(REGISTRY.describe(U256::from(99), &[]), "unknown error 0x63");
+    }
+
+    #[test]
+    fn test_registry_wrong_arg_count() {
+        let template = "insufficient balance: have {0}, need {1}";
+        assert_eq!(REGISTRY.describe(INSUFFICIENT_BALANCE, &[Arg::U(U256::from(1))]), template);
+        // Extra arguments are ignored
+        assert_eq!(REGISTRY.describe(UNAUTHORIZED, &[Arg::Bool(true)]), "unauthorized");
+    }
+
+    #[test]
+    fn test_registry_from_static_table() {
+        let args = [Arg::U(U256::from(5)), Arg::U(U256::from(10))];
+        assert_eq!(
+            REGISTRY.describe(INSUFFICIENT_BALANCE, &args),
+            "insufficient balance: have 5, need 10"
+        );
+        assert_eq!(REGISTRY.template(UNAUTHORIZED), Some("unauthorized"));
+    }
+
+    #[test]
+    fn test_registry_duplicate_replaces() {
+        let registry = ErrorRegistry::from_static(&TABLE)
+            .register(UNAUTHORIZED, "caller is not the owner")
+            .register(U256::from(3), "first")
+            .register(U256::from(3), "second");
+        assert_eq!(registry.describe(UNAUTHORIZED, &[]), "caller is not the owner");
+        assert_eq!(registry.describe(U256::from(3), &[]), "second");
+    }
+
+    #[test]
+    fn test_bidi_isolation() {
+        let template = "sent {0} to {1}.";
+        let args = [Arg::U(U256::from(5)), Arg::Str("علي")];
+        assert_eq!(msgf_with(template, &args, BidiMode::Isolate).unwrap(), "sent 5 to \u{2068}علي\u{2069}.");
+        assert_eq!(msgf_with(template, &args, BidiMode::Passthrough).unwrap(), "sent 5 to علي.");
+        assert_eq!(msgf(template, &args).unwrap(), "sent 5 to علي.");
+        let args = [Arg::U(U256::from(5)), Arg::Str("bob")];
+        assert_eq!(msgf_with(template, &args, BidiMode::Isolate).unwrap(), "sent 5 to bob.");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(msgf("{2}", &[Arg::Bool(true)]), Err(TemplateError::UnknownIndex(2)));
+        assert_eq!(msgf("value {0", &[Arg::Bool(true)]), Err(TemplateError::UnbalancedBrace(6)));
+        assert_eq!(msgf("a } b", &[]), Err(TemplateError::UnbalancedBrace(2)));
+        assert_eq!(msgf("{x}", &[]), Err(TemplateError::InvalidPlaceholder(0)));
+        assert_eq!(msgf("{}", &[]), Err(TemplateError::InvalidPlaceholder(0)));
+        assert_eq!(msgf("{{0}", &[]), Err(TemplateError::UnbalancedBrace(3)));
+        assert_eq!(msgf("{0{1}", &[]), Err(TemplateError::UnbalancedBrace(0)));
+    }
+}
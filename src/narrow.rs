@@ -0,0 +1,91 @@
+//! Decimal formatting of `U256` values that are expected to fit a native
+//! integer type.
+//!
+//! Narrowing first makes the expectation explicit, so an unexpectedly huge
+//! value fails loudly instead of printing 78 digits, and then lets the
+//! digits come from `core`'s native formatting. That path works on machine
+//! words two digits at a time, while `to_string` performs a full 256-bit
+//! division per digit, so for values known to be small (counters, token
+//! ids, timestamps) these are the cheaper choice in hot paths.
+
+use core::fmt;
+
+use alloy_primitives::U256;
+
+use crate::to_hex_string;
+
+/// A value did not fit the native type it was expected to fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeError {
+    /// The value that was out of range
+    pub value: U256,
+    /// Name of the target type, e.g. "u64"
+    pub target: &'static str,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value {} does not fit in {}", to_hex_string(self.value), self.target)
+    }
+}
+
+/// Formats `value` in decimal, failing if it exceeds `u32::MAX`.
+pub fn to_string_expect_u32(value: U256) -> Result<String, RangeError> {
+    u32::try_from(value)
+        .map(|v| v.to_string())
+        .map_err(|_| RangeError { value, target: "u32" })
+}
+
+/// Formats `value` in decimal, failing if it exceeds `u64::MAX`.
+pub fn to_string_expect_u64(value: U256) -> Result<String, RangeError> {
+    u64::try_from(value)
+        .map(|v| v.to_string())
+        .map_err(|_| RangeError { value, target: "u64" })
+}
+
+/// Formats `value` in decimal, failing if it exceeds `u128::MAX`.
+pub fn to_string_expect_u128(value: U256) -> Result<String, RangeError> {
+    u128::try_from(value)
+        .map(|v| v.to_string())
+        .map_err(|_| RangeError { value, target: "u128" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_string;
+
+    #[test]
+    fn test_boundaries() {
+        let one = U256::from(1);
+
+        let max = U256::from(u32::MAX);
+        assert_eq!(to_string_expect_u32(max).unwrap(), "4294967295");
+        assert_eq!(to_string_expect_u32(max + one), Err(RangeError { value: max + one, target: "u32" }));
+
+        let max = U256::from(u64::MAX);
+        assert_eq!(to_string_expect_u64(max).unwrap(), "18446744073709551615");
+        assert_eq!(to_string_expect_u64(max + one), Err(RangeError { value: max + one, target: "u64" }));
+
+        let max = U256::from(u128::MAX);
+        assert_eq!(to_string_expect_u128(max).unwrap(), "340282366920938463463374607431768211455");
+        assert_eq!(to_string_expect_u128(max + one), Err(RangeError { value: max + one, target: "u128" }));
+    }
+
+    #[test]
+    fn test_matches_general_path() {
+        for value in [U256::ZERO, U256::from(7), U256::from(1_000_000u64), U256::from(u32::MAX)] {
+            assert_eq!(to_string_expect_u32(value).unwrap(), to_string(value));
+            assert_eq!(to_string_expect_u64(value).unwrap(), to_string(value));
+            assert_eq!(to_string_expect_u128(value).unwrap(), to_string(value));
+        }
+    }
+
+    #[test]
+    fn test_error_shows_hex_value() {
+        let err = to_string_expect_u64(U256::from(u64::MAX) + U256::from(1)).unwrap_err();
+        assert_eq!(err.to_string(), "value 0x010000000000000000 does not fit in u64");
+        let err = to_string_expect_u32(U256::MAX).unwrap_err();
+        assert_eq!(err.to_string(), format!("value {} does not fit in u32", to_hex_string(U256::MAX)));
+    }
+}
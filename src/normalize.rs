@@ -0,0 +1,208 @@
+//! Configurable lenient parsing that re-emits canonical decimal strings.
+
+use alloy_primitives::U256;
+
+use crate::parse::ParseError;
+use crate::to_string;
+
+/// Reusable numeric string normalizer.
+///
+/// The default configuration is exactly as strict as `parse_decimal`. Each
+/// leniency is opt-in and they are applied in a fixed order:
+///
+/// 1. `trim_whitespace` strips leading and trailing whitespace.
+/// 2. `allow_hex` accepts a "0x"/"0X" prefix and parses the rest as hex.
+/// 3. `allow_underscores` accepts single underscores between digits.
+/// 4. `max_digits` limits the decimal digit count of the parsed value, so a
+///    hex input is judged by the size of its decimal output, not its length.
+///
+/// Error indices always refer to positions in the original input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Normalizer {
+    allow_hex: bool,
+    allow_underscores: bool,
+    trim_whitespace: bool,
+    max_digits: Option<usize>,
+}
+
+/// Builder for `Normalizer`, created by `Normalizer::new`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NormalizerBuilder {
+    config: Normalizer,
+}
+
+impl Normalizer {
+    /// Starts a builder with the strict default configuration.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> NormalizerBuilder {
+        NormalizerBuilder::default()
+    }
+
+    /// Parses `s` under the configured leniency and returns the canonical
+    /// decimal string.
+    pub fn normalize(&self, s: &str) -> Result<String, ParseError> {
+        self.normalize_to_u256(s).map(to_string)
+    }
+
+    /// Parses `s` under the configured leniency.
+    pub fn normalize_to_u256(&self, s: &str) -> Result<U256, ParseError> {
+        let mut offset = 0;
+        let mut input = s;
+        if self.trim_whitespace {
+            let trimmed = input.trim_start();
+            offset = input.len() - trimmed.len();
+            input = trimmed.trim_end();
+        }
+
+        let mut radix = 10;
+        if self.allow_hex {
+            if let Some(digits) = input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")) {
+                radix = 16;
+                offset += 2;
+                input = digits;
+            }
+        }
+
+        let value = self.parse_digits(input, offset, radix)?;
+
+        if let Some(max) = self.max_digits {
+            let found = to_string(value).len();
+            if found > max {
+                return Err(ParseError::TooManyDigits { max, found });
+            }
+        }
+        Ok(value)
+    }
+
+    fn parse_digits(&self, digits: &str, offset: usize, radix: u32) -> Result<U256, ParseError> {
+        if digits.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let base = U256::from(radix);
+        let mut value = U256::ZERO;
+        let mut previous_underscore = false;
+        for (i, c) in digits.char_indices() {
+            if c == '_' && self.allow_underscores {
+                // Only a single underscore directly between two digits
+                let at_edge = i == 0 || i + 1 == digits.len();
+                if at_edge || previous_underscore {
+                    return Err(ParseError::InvalidDigit { index: offset + i, found: c });
+                }
+                previous_underscore = true;
+                continue;
+            }
+            previous_underscore = false;
+
+            let digit = c
+                .to_digit(radix)
+                .ok_or(ParseError::InvalidDigit { index: offset + i, found: c })?;
+            value = value
+                .checked_mul(base)
+                .and_then(|v| v.checked_add(U256::from(digit)))
+                .ok_or(ParseError::Overflow)?;
+        }
+
+        Ok(value)
+    }
+}
+
+impl NormalizerBuilder {
+    /// Accepts "0x"/"0X"-prefixed hex input.
+    pub fn allow_hex(mut self) -> Self {
+        self.config.allow_hex = true;
+        self
+    }
+
+    /// Accepts single underscores between digits, e.g. "1_000_000".
+    pub fn allow_underscores(mut self) -> Self {
+        self.config.allow_underscores = true;
+        self
+    }
+
+    /// Ignores leading and trailing whitespace.
+    pub fn trim_whitespace(mut self) -> Self {
+        self.config.trim_whitespace = true;
+        self
+    }
+
+    /// Rejects values whose canonical decimal form exceeds `max` digits.
+    pub fn max_digits(mut self, max: usize) -> Self {
+        self.config.max_digits = Some(max);
+        self
+    }
+
+    /// Finishes the configuration.
+    pub fn build(self) -> Normalizer {
+        self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::parse_decimal;
+
+    #[test]
+    fn test_default_matches_strict_parser() {
+        let strict = Normalizer::new().build();
+        assert_eq!(strict, Normalizer::default());
+        for input in ["0", "007", "12345", "", " 1", "1_000", "0x10", "-1", "1.5"] {
+            assert_eq!(strict.normalize_to_u256(input), parse_decimal(input), "{:?}", input);
+        }
+        assert_eq!(strict.normalize("007").unwrap(), "7");
+    }
+
+    #[test]
+    fn test_allow_hex() {
+        let n = Normalizer::new().allow_hex().build();
+        assert_eq!(n.normalize("0xff").unwrap(), "255");
+        assert_eq!(n.normalize("0XFF").unwrap(), "255");
+        assert_eq!(n.normalize("255").unwrap(), "255");
+        assert_eq!(n.normalize("0x"), Err(ParseError::Empty));
+        assert_eq!(n.normalize("ff"), Err(ParseError::InvalidDigit { index: 0, found: 'f' }));
+    }
+
+    #[test]
+    fn test_allow_underscores() {
+        let n = Normalizer::new().allow_underscores().build();
+        assert_eq!(n.normalize("1_000_000").unwrap(), "1000000");
+        assert_eq!(n.normalize("_1"), Err(ParseError::InvalidDigit { index: 0, found: '_' }));
+        assert_eq!(n.normalize("1_"), Err(ParseError::InvalidDigit { index: 1, found: '_' }));
+        assert_eq!(n.normalize("1__0"), Err(ParseError::InvalidDigit { index: 2, found: '_' }));
+    }
+
+    #[test]
+    fn test_trim_whitespace() {
+        let n = Normalizer::new().trim_whitespace().build();
+        assert_eq!(n.normalize("  42\n").unwrap(), "42");
+        assert_eq!(n.normalize("   "), Err(ParseError::Empty));
+        assert_eq!(n.normalize(" 4 2"), Err(ParseError::InvalidDigit { index: 2, found: ' ' }));
+    }
+
+    #[test]
+    fn test_max_digits() {
+        let n = Normalizer::new().max_digits(3).build();
+        assert_eq!(n.normalize("999").unwrap(), "999");
+        assert_eq!(n.normalize("0001").unwrap(), "1");
+        assert_eq!(n.normalize("1000"), Err(ParseError::TooManyDigits { max: 3, found: 4 }));
+    }
+
+    #[test]
+    fn test_combinations() {
+        let n = Normalizer::new()
+            .allow_hex()
+            .allow_underscores()
+            .trim_whitespace()
+            .max_digits(40)
+            .build();
+        assert_eq!(n.normalize(" 0xdead_beef ").unwrap(), "3735928559");
+        assert_eq!(n.normalize("\t1_000 ").unwrap(), "1000");
+        assert_eq!(n.normalize_to_u256(" 0x_1"), Err(ParseError::InvalidDigit { index: 3, found: '_' }));
+
+        // max_digits applies to the decimal output: 0xff is 3 decimal digits
+        let short = Normalizer::new().allow_hex().max_digits(2).build();
+        assert_eq!(short.normalize("0xff"), Err(ParseError::TooManyDigits { max: 2, found: 3 }));
+        assert_eq!(short.normalize("0x0063").unwrap(), "99");
+    }
+}
@@ -0,0 +1,110 @@
+//! Order book price levels for integer-tick CLOBs.
+//!
+//! Prices are stored as `tick * tick_size`, a raw quote-token amount per
+//! whole base token. Only linear ticks are supported; log-tick schemes
+//! where prices below 1 need exponentiation are out of scope.
+
+use alloy_primitives::{I256, U256, U512};
+
+use crate::units::{format_grouped_digits, format_grouped_units, u512_to_string};
+
+/// Fractional digits always shown for prices, as in "3,120.50"
+const PRICE_MIN_DECIMALS: usize = 2;
+
+/// Tick size and token metadata of a market
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Market<'a> {
+    /// Raw quote-token amount of one tick
+    pub tick_size: U256,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+    pub base_symbol: &'a str,
+    pub quote_symbol: &'a str,
+}
+
+/// Formats the price of `tick` as a grouped quote-token amount, e.g.
+/// "3,120.50" or "-0.25".
+///
+/// At least two fractional digits are shown (fewer only if the quote token
+/// has fewer decimals); further trailing zeros are dropped. The price is
+/// computed in 512 bits, so every tick and tick size can be shown.
+pub fn format_tick_price(tick: I256, tick_size: U256, quote_decimals: u8) -> String {
+    let price = U512::from(tick.unsigned_abs()) * U512::from(tick_size);
+    let formatted = format_grouped_digits(&u512_to_string(price), quote_decimals, PRICE_MIN_DECIMALS);
+    if tick.is_negative() && !price.is_zero() {
+        format!("-{}", formatted)
+    } else {
+        formatted
+    }
+}
+
+/// Formats a price level as "<size> <base> @ <price> <quote>", e.g.
+/// "0.0542 ETH @ 3,120.50 USDC".
+pub fn format_price_level(market: &Market, price_tick: I256, size: U256) -> String {
+    format!(
+        "{} {} @ {} {}",
+        format_grouped_units(size, market.base_decimals, 0),
+        market.base_symbol,
+        format_tick_price(price_tick, market.tick_size, market.quote_decimals),
+        market.quote_symbol
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ETH/USDC market with a tick of 0.01 USDC
+    const ETH_USDC: Market<'static> = Market {
+        tick_size: U256::from_limbs([10_000, 0, 0, 0]),
+        base_decimals: 18,
+        quote_decimals: 6,
+        base_symbol: "ETH",
+        quote_symbol: "USDC",
+    };
+
+    fn tick(value: i64) -> I256 {
+        I256::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn test_format_tick_price() {
+        assert_eq!(format_tick_price(tick(312_050), ETH_USDC.tick_size, 6), "3,120.50");
+        assert_eq!(format_tick_price(tick(312_055), U256::from(1_000), 6), "312.055");
+        assert_eq!(format_tick_price(tick(100_000_000), ETH_USDC.tick_size, 6), "1,000,000.00");
+        assert_eq!(format_tick_price(tick(7), U256::from(1), 0), "7");
+        assert_eq!(format_tick_price(tick(75), U256::from(1), 1), "7.5");
+    }
+
+    #[test]
+    fn test_format_tick_price_signed() {
+        assert_eq!(format_tick_price(tick(-25), ETH_USDC.tick_size, 6), "-0.25");
+        assert_eq!(format_tick_price(tick(-312_050), ETH_USDC.tick_size, 6), "-3,120.50");
+        assert_eq!(format_tick_price(I256::ZERO, ETH_USDC.tick_size, 6), "0.00");
+        assert_eq!(format_tick_price(tick(-5), U256::ZERO, 6), "0.00");
+    }
+
+    #[test]
+    fn test_format_tick_price_beyond_u256() {
+        // I256::MAX whole tokens
+        let whole = U256::from(10).pow(U256::from(18));
+        let max = "57,896,044,618,658,097,711,785,492,504,343,953,926,634,992,332,820,282,019,728,792,003,956,564,819,967";
+        assert_eq!(format_tick_price(I256::MAX, whole, 18), format!("{}.00", max));
+        // 2^255 * (2^256 - 1)
+        let min = "6,703,903,964,971,298,549,787,012,499,102,923,063,739,682,910,296,196,688,861,780,721,860,882,015,036,715,592,356,318,490,985,739,928,352,511,585,139,316,390,434,544,121,123,953,556,181,212,867,938,222,080";
+        assert_eq!(format_tick_price(I256::MIN, U256::MAX, 0), format!("-{}", min));
+    }
+
+    #[test]
+    fn test_format_price_level() {
+        let size = U256::from(54_200_000_000_000_000u64);
+        assert_eq!(format_price_level(&ETH_USDC, tick(312_050), size), "0.0542 ETH @ 3,120.50 USDC");
+        let size = U256::from(1_250u64) * U256::from(10u64).pow(U256::from(18));
+        assert_eq!(format_price_level(&ETH_USDC, tick(312_000), size), "1,250 ETH @ 3,120.00 USDC");
+    }
+
+    #[test]
+    fn test_zero_size_level() {
+        assert_eq!(format_price_level(&ETH_USDC, tick(312_050), U256::ZERO), "0 ETH @ 3,120.50 USDC");
+    }
+}
@@ -0,0 +1,128 @@
+//! Decoding ERC721A-style packed ownership slots.
+
+use alloy_primitives::{Address, U256};
+
+use crate::mask::BitField;
+use crate::table::render_table;
+use crate::time::timestamp_label;
+use crate::address_to_checksum_hex_string;
+
+/// Bit positions of the fields in a packed ownership word
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnershipLayout {
+    /// Owner address, normally 160 bits
+    pub owner: BitField,
+    /// Timestamp of the last transfer, in seconds
+    pub start_timestamp: BitField,
+    /// Set once the token is burned
+    pub burned: BitField,
+    /// Set when the next token's slot has been initialized
+    pub next_initialized: BitField,
+}
+
+impl OwnershipLayout {
+    /// ERC721A's `_packedOwnerships` layout: owner in bits [0, 160),
+    /// start timestamp in [160, 224), burned at bit 224 and next-initialized
+    /// at bit 225.
+    pub const ERC721A: Self = Self {
+        owner: BitField::new(0, 160),
+        start_timestamp: BitField::new(160, 64),
+        burned: BitField::new(224, 1),
+        next_initialized: BitField::new(225, 1),
+    };
+}
+
+impl Default for OwnershipLayout {
+    fn default() -> Self {
+        Self::ERC721A
+    }
+}
+
+/// Renders a packed ownership word as an aligned table of its fields:
+///
+/// ```text
+/// owner             0x52908400098527886E0F7030069857D2E4169EE7
+/// start timestamp                         2024-07-02T12:00:00Z
+/// burned                                                 false
+/// next initialized                                        true
+/// ```
+///
+/// The owner is checksummed from the field's low 160 bits, the timestamp
+/// is ISO-8601 (or "timestamp N" past year 9999), and a flag is true if
+/// any of its bits are set. Bits outside the layout are ignored.
+pub fn format_packed_ownership(word: U256, layout: OwnershipLayout) -> String {
+    let owner = layout.owner.extract(word).to_be_bytes::<32>();
+    let flag = |field: BitField| if field.extract(word).is_zero() { "false" } else { "true" };
+    let rows = [
+        vec!["owner".to_string(), address_to_checksum_hex_string(Address::from_slice(&owner[12..]))],
+        vec!["start timestamp".to_string(), timestamp_label(layout.start_timestamp.extract(word))],
+        vec!["burned".to_string(), flag(layout.burned).to_string()],
+        vec!["next initialized".to_string(), flag(layout.next_initialized).to_string()],
+    ];
+    render_table(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: [u8; 20] = [
+        0x52, 0x90, 0x84, 0x00, 0x09, 0x85, 0x27, 0x88, 0x6e, 0x0f,
+        0x70, 0x30, 0x06, 0x98, 0x57, 0xd2, 0xe4, 0x16, 0x9e, 0xe7,
+    ];
+
+    /// 2024-07-02T12:00:00Z
+    const TIMESTAMP: u64 = 1_719_921_600;
+
+    fn pack(burned: bool, next_initialized: bool) -> U256 {
+        U256::from_be_slice(&OWNER)
+            | (U256::from(TIMESTAMP) << 160)
+            | (U256::from(burned as u8) << 224)
+            | (U256::from(next_initialized as u8) << 225)
+    }
+
+    #[test]
+    fn test_default_layout_constants() {
+        let layout = OwnershipLayout::default();
+        assert_eq!(layout, OwnershipLayout::ERC721A);
+        assert_eq!(layout.owner.mask(), (U256::from(1) << 160) - U256::from(1));
+        assert_eq!(layout.start_timestamp.mask(), U256::from(u64::MAX) << 160);
+        assert_eq!(layout.burned.mask(), U256::from(1) << 224);
+        assert_eq!(layout.next_initialized.mask(), U256::from(1) << 225);
+    }
+
+    #[test]
+    fn test_format_packed_ownership() {
+        // Upper bits (ERC721A's extraData) are ignored
+        let word = pack(false, true) | (U256::from(0xabcdef) << 232);
+        assert_eq!(
+            format_packed_ownership(word, OwnershipLayout::ERC721A),
+            "owner             0x52908400098527886E0F7030069857D2E4169EE7\n\
+             start timestamp                         2024-07-02T12:00:00Z\n\
+             burned                                                 false\n\
+             next initialized                                        true"
+        );
+    }
+
+    #[test]
+    fn test_burned_token() {
+        let rendered = format_packed_ownership(pack(true, false), OwnershipLayout::default());
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert!(lines[2].ends_with(" true"));
+        assert!(lines[3].ends_with(" false"));
+    }
+
+    #[test]
+    fn test_custom_layout() {
+        // Timestamp first, then the owner
+        let layout = OwnershipLayout {
+            owner: BitField::new(64, 160),
+            start_timestamp: BitField::new(0, 64),
+            burned: BitField::new(255, 1),
+            next_initialized: BitField::new(254, 1),
+        };
+        let word = (U256::from_be_slice(&OWNER) << 64) | U256::from(TIMESTAMP) | (U256::from(1) << 255);
+        let expected = format_packed_ownership(pack(true, false), OwnershipLayout::ERC721A);
+        assert_eq!(format_packed_ownership(word, layout), expected);
+    }
+}
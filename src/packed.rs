@@ -0,0 +1,162 @@
+//! `abi.encodePacked` mirror for recreating Solidity commitment hashes.
+
+use alloy_primitives::{keccak256, Address, B256, U256};
+
+use crate::bytes_to_hex_string;
+
+/// Error types for packing values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackError {
+    /// Bit width is not a multiple of 8 between 8 and 256
+    InvalidBits(usize),
+    /// Value does not fit in the requested `uintN`
+    ValueTooWide { value: U256, bits: usize },
+}
+
+/// Builds the tightly packed encoding produced by `abi.encodePacked`.
+///
+/// Each value takes exactly its type's width with no padding: `uintN` is
+/// N/8 big-endian bytes, `address` 20 bytes, and `bytes` and `string` their
+/// raw contents with no length prefix. As in Solidity, adjacent dynamic
+/// values can collide, e.g. ("a", "bc") and ("ab", "c").
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EncodePacked {
+    buffer: Vec<u8>,
+}
+
+impl EncodePacked {
+    /// Creates an empty encoder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a `uint256` as 32 bytes.
+    pub fn push_u256(mut self, value: U256) -> Self {
+        self.buffer.extend_from_slice(&value.to_be_bytes::<32>());
+        self
+    }
+
+    /// Appends a `uintN` as `bits / 8` bytes, rejecting values that do not
+    /// fit rather than truncating them.
+    pub fn push_uint(mut self, value: U256, bits: usize) -> Result<Self, PackError> {
+        if bits == 0 || bits > 256 || !bits.is_multiple_of(8) {
+            return Err(PackError::InvalidBits(bits));
+        }
+        if value.bit_len() > bits {
+            return Err(PackError::ValueTooWide { value, bits });
+        }
+        self.buffer.extend_from_slice(&value.to_be_bytes::<32>()[32 - bits / 8..]);
+        Ok(self)
+    }
+
+    /// Appends an `address` as 20 bytes.
+    pub fn push_address(mut self, addr: Address) -> Self {
+        self.buffer.extend_from_slice(addr.as_slice());
+        self
+    }
+
+    /// Appends `bytes` (or any `bytesN`) as its raw contents.
+    pub fn push_bytes(mut self, bytes: &[u8]) -> Self {
+        self.buffer.extend_from_slice(bytes);
+        self
+    }
+
+    /// Appends a `string` as its UTF-8 bytes.
+    pub fn push_str(self, s: &str) -> Self {
+        self.push_bytes(s.as_bytes())
+    }
+
+    /// Returns the packed bytes.
+    pub fn finish(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// Returns the packed bytes as "0x"-prefixed lowercase hex.
+    pub fn finish_hex(self) -> String {
+        bytes_to_hex_string(&self.buffer)
+    }
+
+    /// Returns `keccak256(abi.encodePacked(...))`.
+    pub fn finish_keccak(self) -> B256 {
+        keccak256(&self.buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(hash: B256) -> String {
+        bytes_to_hex_string(hash.as_slice())
+    }
+
+    #[test]
+    fn test_packing_widths() {
+        let addr = Address::from([0x11; 20]);
+        let packed = EncodePacked::new()
+            .push_uint(U256::from(1), 8)
+            .unwrap()
+            .push_uint(U256::from(0x0203), 16)
+            .unwrap()
+            .push_address(addr)
+            .push_str("ab")
+            .finish();
+        assert_eq!(packed.len(), 1 + 2 + 20 + 2);
+        assert_eq!(packed[..3], [0x01, 0x02, 0x03]);
+        assert_eq!(packed[3..23], [0x11; 20]);
+        assert_eq!(packed[23..], *b"ab");
+        assert_eq!(EncodePacked::new().push_u256(U256::from(1)).finish().len(), 32);
+    }
+
+    #[test]
+    fn test_keccak_of_string() {
+        // keccak256(abi.encodePacked("hello")) == keccak256("hello")
+        assert_eq!(
+            hex(EncodePacked::new().push_str("hello").finish_keccak()),
+            "0x1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8"
+        );
+    }
+
+    #[test]
+    fn test_keccak_of_mixed_sequence() {
+        // keccak256(abi.encodePacked(uint256(1), "transfer"))
+        assert_eq!(
+            hex(EncodePacked::new().push_u256(U256::from(1)).push_str("transfer").finish_keccak()),
+            "0xfad0e2778d1c96f5bc942e6abf37ffb03f4ed245062d8cf34ba86c53159c4ede"
+        );
+        // keccak256(abi.encodePacked(
+        //     address(0x5B38Da6a701c568545dCfcB03FcB875f56beddC4), uint8(7), hex"deadbeef"))
+        let addr: Address = "0x5B38Da6a701c568545dCfcB03FcB875f56beddC4".parse().unwrap();
+        let packed = EncodePacked::new().push_address(addr).push_uint(U256::from(7), 8).unwrap();
+        assert_eq!(
+            hex(packed.push_bytes(&[0xde, 0xad, 0xbe, 0xef]).finish_keccak()),
+            "0x3a5eb98bfc64aff64396db6ade36fd5ec35feb3953a93dce6dedf6c4dbdc9e1f"
+        );
+        // uint8(1) packs to a single byte, unlike uint256(1)
+        let narrow = EncodePacked::new().push_uint(U256::from(1), 8).unwrap().push_str("transfer");
+        assert_eq!(narrow.finish_hex(), "0x017472616e73666572");
+    }
+
+    #[test]
+    fn test_value_too_wide() {
+        assert_eq!(
+            EncodePacked::new().push_uint(U256::from(256), 8),
+            Err(PackError::ValueTooWide { value: U256::from(256), bits: 8 })
+        );
+        assert!(EncodePacked::new().push_uint(U256::from(255), 8).is_ok());
+        assert!(EncodePacked::new().push_uint(U256::MAX, 256).is_ok());
+        assert_eq!(EncodePacked::new().push_uint(U256::ZERO, 12), Err(PackError::InvalidBits(12)));
+        assert_eq!(EncodePacked::new().push_uint(U256::ZERO, 0), Err(PackError::InvalidBits(0)));
+        assert_eq!(EncodePacked::new().push_uint(U256::ZERO, 264), Err(PackError::InvalidBits(264)));
+    }
+
+    #[test]
+    fn test_empty_builder() {
+        assert_eq!(EncodePacked::new().finish(), Vec::<u8>::new());
+        assert_eq!(EncodePacked::new().finish_hex(), "0x");
+        assert_eq!(
+            hex(EncodePacked::new().finish_keccak()),
+            "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+    }
+}
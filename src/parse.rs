@@ -0,0 +1,321 @@
+//! Parsing of decimal and hexadecimal strings back into `U256` and byte values.
+//!
+//! These are the inverse of the formatting functions in the crate root and are
+//! deliberately strict: no whitespace, no signs, no digit separators.
+
+use alloy_primitives::{Address, FixedBytes, U256};
+
+/// Error types for string-to-number parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// Input contained no digits
+    Empty,
+    /// Hex input did not start with "0x"
+    MissingHexPrefix,
+    /// Character at `index` is not a valid digit for the expected radix
+    InvalidDigit { index: usize, found: char },
+    /// Value does not fit in 256 bits
+    Overflow,
+    /// Fixed-length input had the wrong number of hex digits
+    InvalidLength { expected: usize, found: usize },
+    /// Value has more decimal digits than a configured maximum
+    TooManyDigits { max: usize, found: usize },
+    /// Delimited input had the wrong number of segments
+    WrongSegmentCount { expected: usize, found: usize },
+    /// Byte-oriented hex input had an odd number of digits
+    OddLength { found: usize },
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty numeric string"),
+            ParseError::MissingHexPrefix => write!(f, "hex string must start with 0x"),
+            ParseError::InvalidDigit { index, found } => {
+                write!(f, "invalid digit {:?} at index {}", found, index)
+            }
+            ParseError::Overflow => write!(f, "value does not fit in 256 bits"),
+            ParseError::InvalidLength { expected, found } => {
+                write!(f, "expected {} hex digits, found {}", expected, found)
+            }
+            ParseError::TooManyDigits { max, found } => {
+                write!(f, "value has {} digits, more than the maximum of {}", found, max)
+            }
+            ParseError::WrongSegmentCount { expected, found } => {
+                write!(f, "expected {} segments, found {}", expected, found)
+            }
+            ParseError::OddLength { found } => {
+                write!(f, "expected an even number of hex digits, found {}", found)
+            }
+        }
+    }
+}
+
+/// Parses a strict decimal string (ASCII digits only) into a U256.
+///
+/// This is the inverse of `to_string`. Leading zeros are accepted.
+pub fn parse_decimal(s: &str) -> Result<U256, ParseError> {
+    parse_digits(s, 0, 10)
+}
+
+/// Parses a "0x"-prefixed hexadecimal string into a U256.
+///
+/// This is the inverse of `to_hex_string`. Digits may be either case.
+pub fn parse_hex(s: &str) -> Result<U256, ParseError> {
+    let digits = s.strip_prefix("0x").ok_or(ParseError::MissingHexPrefix)?;
+    parse_digits(digits, 2, 16)
+}
+
+/// Parses a string as hex if it starts with "0x", otherwise as decimal.
+pub fn parse_u256(s: &str) -> Result<U256, ParseError> {
+    if s.starts_with("0x") {
+        parse_hex(s)
+    } else {
+        parse_decimal(s)
+    }
+}
+
+/// Fallible `&str` to `U256` conversion, standing in for `TryFrom<&str>`
+/// which cannot be implemented on the foreign `U256` type.
+///
+/// Accepts both decimal and "0x"-prefixed hex input, like `parse_u256`.
+pub fn try_u256_from_str(s: &str) -> Result<U256, ParseError> {
+    parse_u256(s)
+}
+
+/// Types that `hex_decode_to` can produce from decoded bytes.
+///
+/// Implement this for your own wrapper types to decode them directly.
+pub trait TryFromHexBytes: Sized {
+    /// Exact number of bytes required, or `None` to accept any length.
+    const BYTE_LEN: Option<usize>;
+
+    /// Builds the value from bytes whose length already satisfies
+    /// `BYTE_LEN`.
+    fn try_from_hex_bytes(bytes: &[u8]) -> Result<Self, ParseError>;
+}
+
+impl TryFromHexBytes for Vec<u8> {
+    const BYTE_LEN: Option<usize> = None;
+
+    fn try_from_hex_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        Ok(bytes.to_vec())
+    }
+}
+
+impl<const N: usize> TryFromHexBytes for [u8; N] {
+    const BYTE_LEN: Option<usize> = Some(N);
+
+    fn try_from_hex_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        let mut result = [0u8; N];
+        result.copy_from_slice(bytes);
+        Ok(result)
+    }
+}
+
+impl<const N: usize> TryFromHexBytes for FixedBytes<N> {
+    const BYTE_LEN: Option<usize> = Some(N);
+
+    fn try_from_hex_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        <[u8; N]>::try_from_hex_bytes(bytes).map(FixedBytes::from)
+    }
+}
+
+impl TryFromHexBytes for Address {
+    const BYTE_LEN: Option<usize> = Some(20);
+
+    fn try_from_hex_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        Ok(Address::from_slice(bytes))
+    }
+}
+
+/// Numeric: any number of leading zero bytes is accepted.
+impl TryFromHexBytes for U256 {
+    const BYTE_LEN: Option<usize> = None;
+
+    fn try_from_hex_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        if bytes.is_empty() {
+            return Err(ParseError::Empty);
+        }
+        let first = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+        if bytes.len() - first > 32 {
+            return Err(ParseError::Overflow);
+        }
+        Ok(U256::from_be_slice(&bytes[first..]))
+    }
+}
+
+/// Decodes "0x"-prefixed hex into `T`, the inverse of `hex_encode`.
+///
+/// Digits may be either case and must come in pairs. For fixed-size types
+/// a wrong digit count is reported as `InvalidLength` in hex digits, e.g.
+/// "expected 40 hex digits, found 39", before parity or digits are checked.
+/// Use `parse_hex` for numbers written with an odd number of digits.
+pub fn hex_decode_to<T: TryFromHexBytes>(s: &str) -> Result<T, ParseError> {
+    let digits = s.strip_prefix("0x").ok_or(ParseError::MissingHexPrefix)?;
+    if let Some(len) = T::BYTE_LEN {
+        if digits.len() != 2 * len {
+            return Err(ParseError::InvalidLength { expected: 2 * len, found: digits.len() });
+        }
+    }
+    if digits.len() % 2 != 0 {
+        return Err(ParseError::OddLength { found: digits.len() });
+    }
+
+    let mut bytes = Vec::with_capacity(digits.len() / 2);
+    let mut high = 0;
+    for (i, c) in digits.char_indices() {
+        let nibble = c.to_digit(16).ok_or(ParseError::InvalidDigit { index: 2 + i, found: c })? as u8;
+        if i % 2 == 0 {
+            high = nibble << 4;
+        } else {
+            bytes.push(high | nibble);
+        }
+    }
+    T::try_from_hex_bytes(&bytes)
+}
+
+/// Accumulates `digits` in the given radix, reporting error indices relative
+/// to the original input (`offset` accounts for a stripped prefix).
+pub(crate) fn parse_digits(digits: &str, offset: usize, radix: u32) -> Result<U256, ParseError> {
+    if digits.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let base = U256::from(radix);
+    let mut value = U256::ZERO;
+    for (i, c) in digits.char_indices() {
+        let digit = c
+            .to_digit(radix)
+            .ok_or(ParseError::InvalidDigit { index: offset + i, found: c })?;
+        value = value
+            .checked_mul(base)
+            .and_then(|v| v.checked_add(U256::from(digit)))
+            .ok_or(ParseError::Overflow)?;
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_decimal("0").unwrap(), U256::ZERO);
+        assert_eq!(parse_decimal("12345").unwrap(), U256::from(12345));
+        assert_eq!(parse_decimal("007").unwrap(), U256::from(7));
+        assert_eq!(parse_decimal(&U256::MAX.to_string()).unwrap(), U256::MAX);
+    }
+
+    #[test]
+    fn test_parse_decimal_errors() {
+        assert_eq!(parse_decimal(""), Err(ParseError::Empty));
+        assert_eq!(parse_decimal("12a"), Err(ParseError::InvalidDigit { index: 2, found: 'a' }));
+        assert_eq!(parse_decimal(" 1"), Err(ParseError::InvalidDigit { index: 0, found: ' ' }));
+        assert_eq!(parse_decimal("-1"), Err(ParseError::InvalidDigit { index: 0, found: '-' }));
+        // U256::MAX + 1
+        let too_big = "115792089237316195423570985008687907853269984665640564039457584007913129639936";
+        assert_eq!(parse_decimal(too_big), Err(ParseError::Overflow));
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("0x00").unwrap(), U256::ZERO);
+        assert_eq!(parse_hex("0xff").unwrap(), U256::from(255));
+        assert_eq!(parse_hex("0xFF").unwrap(), U256::from(255));
+        assert_eq!(parse_hex(&format!("0x{}", "f".repeat(64))).unwrap(), U256::MAX);
+    }
+
+    #[test]
+    fn test_parse_hex_errors() {
+        assert_eq!(parse_hex("ff"), Err(ParseError::MissingHexPrefix));
+        assert_eq!(parse_hex("0x"), Err(ParseError::Empty));
+        assert_eq!(parse_hex("0xfg"), Err(ParseError::InvalidDigit { index: 3, found: 'g' }));
+        assert_eq!(parse_hex(&format!("0x1{}", "0".repeat(64))), Err(ParseError::Overflow));
+    }
+
+    #[test]
+    fn test_hex_decode_to_happy_paths() {
+        use alloy_primitives::B256;
+
+        assert_eq!(hex_decode_to::<Vec<u8>>("0x00ab0C").unwrap(), vec![0x00, 0xab, 0x0c]);
+        assert_eq!(hex_decode_to::<Vec<u8>>("0x").unwrap(), Vec::<u8>::new());
+        assert_eq!(hex_decode_to::<[u8; 2]>("0xBEEF").unwrap(), [0xbe, 0xef]);
+        assert_eq!(hex_decode_to::<FixedBytes<4>>("0xa9059cbb").unwrap(), FixedBytes::from([0xa9, 0x05, 0x9c, 0xbb]));
+
+        let addr = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let decoded: Address = hex_decode_to(addr).unwrap();
+        assert_eq!(crate::address_to_checksum_hex_string(decoded), addr);
+
+        let word = format!("0x{}2a", "0".repeat(62));
+        assert_eq!(hex_decode_to::<B256>(&word).unwrap(), B256::from(U256::from(42).to_be_bytes::<32>()));
+        assert_eq!(hex_decode_to::<U256>(&word).unwrap(), U256::from(42));
+    }
+
+    #[test]
+    fn test_hex_decode_to_round_trips_hex_encode() {
+        use crate::{hex_encode, HexOptions};
+
+        let bytes = [0u8, 1, 0x7f, 0x80, 0xff];
+        assert_eq!(hex_decode_to::<[u8; 5]>(&hex_encode(bytes, HexOptions::new())).unwrap(), bytes);
+        let value = U256::from(0x1234_5678u64);
+        assert_eq!(hex_decode_to::<U256>(&crate::to_hex_string(value)).unwrap(), value);
+        assert_eq!(hex_decode_to::<U256>(&crate::to_hex_string(U256::MAX)).unwrap(), U256::MAX);
+    }
+
+    #[test]
+    fn test_hex_decode_to_length_mismatches() {
+        let short = format!("0x{}", "a".repeat(39));
+        let err = hex_decode_to::<Address>(&short).unwrap_err();
+        assert_eq!(err, ParseError::InvalidLength { expected: 40, found: 39 });
+        assert_eq!(err.to_string(), "expected 40 hex digits, found 39");
+        assert_eq!(
+            hex_decode_to::<alloy_primitives::B256>("0x00"),
+            Err(ParseError::InvalidLength { expected: 64, found: 2 })
+        );
+        assert_eq!(hex_decode_to::<[u8; 2]>("0xabcdef"), Err(ParseError::InvalidLength { expected: 4, found: 6 }));
+        assert_eq!(hex_decode_to::<FixedBytes<1>>("0x"), Err(ParseError::InvalidLength { expected: 2, found: 0 }));
+    }
+
+    #[test]
+    fn test_hex_decode_to_odd_and_invalid() {
+        let err = hex_decode_to::<Vec<u8>>("0xabc").unwrap_err();
+        assert_eq!(err, ParseError::OddLength { found: 3 });
+        assert_eq!(err.to_string(), "expected an even number of hex digits, found 3");
+        assert_eq!(hex_decode_to::<U256>("0xf"), Err(ParseError::OddLength { found: 1 }));
+        assert_eq!(hex_decode_to::<Vec<u8>>("abcd"), Err(ParseError::MissingHexPrefix));
+        assert_eq!(hex_decode_to::<[u8; 2]>("0xabzd"), Err(ParseError::InvalidDigit { index: 4, found: 'z' }));
+        assert_eq!(hex_decode_to::<U256>("0x"), Err(ParseError::Empty));
+        // Leading zeros are fine for numbers, but the value must fit
+        assert_eq!(hex_decode_to::<U256>(&format!("0x{}01", "00".repeat(40))).unwrap(), U256::from(1));
+        assert_eq!(hex_decode_to::<U256>(&format!("0x01{}", "00".repeat(32))), Err(ParseError::Overflow));
+    }
+
+    /// A user-defined wrapper decoding through the public trait
+    #[derive(Debug, PartialEq)]
+    struct Selector(u32);
+
+    impl TryFromHexBytes for Selector {
+        const BYTE_LEN: Option<usize> = Some(4);
+
+        fn try_from_hex_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+            Ok(Selector(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])))
+        }
+    }
+
+    #[test]
+    fn test_hex_decode_to_user_type() {
+        assert_eq!(hex_decode_to::<Selector>("0xa9059cbb").unwrap(), Selector(0xa905_9cbb));
+        assert_eq!(hex_decode_to::<Selector>("0xa9059c"), Err(ParseError::InvalidLength { expected: 8, found: 6 }));
+    }
+
+    #[test]
+    fn test_parse_u256_detects_prefix() {
+        assert_eq!(parse_u256("10").unwrap(), U256::from(10));
+        assert_eq!(parse_u256("0x10").unwrap(), U256::from(16));
+        assert_eq!(try_u256_from_str("0x10").unwrap(), U256::from(16));
+        assert_eq!(try_u256_from_str("x10"), Err(ParseError::InvalidDigit { index: 0, found: 'x' }));
+    }
+}
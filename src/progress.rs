@@ -0,0 +1,113 @@
+//! Funding progress for crowdsales, as text and as a bar.
+
+use alloy_primitives::{U256, U512};
+
+use crate::units::format_grouped_units;
+use crate::{bar, to_string};
+
+/// Formats campaign progress, e.g. "raised 45,200 / 100,000 USDC (45.2%)".
+///
+/// The percentage is truncated to one decimal place, so a campaign shows
+/// 100% only once the goal is actually met, and is not capped: an
+/// overfunded campaign shows its true value, e.g. "123%". With a zero goal
+/// there is no meaningful percentage and "(no goal)" is shown instead.
+pub fn format_progress(raised: U256, goal: U256, decimals: u8, symbol: &str) -> String {
+    let amounts = format!(
+        "raised {} / {} {}",
+        format_grouped_units(raised, decimals, 0),
+        format_grouped_units(goal, decimals, 0),
+        symbol
+    );
+    if goal.is_zero() {
+        return format!("{} (no goal)", amounts);
+    }
+    format!("{} ({})", amounts, format_percent(raised, goal))
+}
+
+/// Formats `part / whole` as a percentage truncated to one decimal place,
+/// e.g. "45.2%" or "123%"; a zero tenth is omitted. `whole` must be nonzero.
+pub(crate) fn format_percent(part: U256, whole: U256) -> String {
+    // Whole multiples, then per-mille of the remainder; U512 so
+    // `remainder * 1000` cannot overflow
+    let multiples = part / whole;
+    let per_mille = (U512::from(part % whole) * U512::from(1000) / U512::from(whole)).to::<u16>();
+    let percent = if multiples.is_zero() {
+        (per_mille / 10).to_string()
+    } else {
+        format!("{}{:02}", to_string(multiples), per_mille / 10)
+    };
+    match per_mille % 10 {
+        0 => format!("{}%", percent),
+        tenths => format!("{}.{}%", percent, tenths),
+    }
+}
+
+/// Renders campaign progress as a `bar` of `width` cells.
+///
+/// Filled cells round half up like `bar`, so unlike the truncated
+/// percentage of `format_progress` the bar can fill just before the goal,
+/// e.g. at 99.5% with a width of 100; it is always full once the goal is
+/// reached. A zero goal renders a full bar once anything is raised.
+pub fn format_progress_bar(raised: U256, goal: U256, width: usize) -> String {
+    bar(raised, goal, width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usdc(whole: u64) -> U256 {
+        U256::from(whole) * U256::from(1_000_000)
+    }
+
+    #[test]
+    fn test_format_progress() {
+        assert_eq!(
+            format_progress(usdc(45_200), usdc(100_000), 6, "USDC"),
+            "raised 45,200 / 100,000 USDC (45.2%)"
+        );
+        assert_eq!(format_progress(U256::ZERO, usdc(100), 6, "USDC"), "raised 0 / 100 USDC (0%)");
+        assert_eq!(format_progress(usdc(100), usdc(100), 6, "USDC"), "raised 100 / 100 USDC (100%)");
+        // Truncated, never rounded up to 100%
+        assert_eq!(
+            format_progress(U256::from(99_999), U256::from(100_000), 0, "DAI"),
+            "raised 99,999 / 100,000 DAI (99.9%)"
+        );
+    }
+
+    #[test]
+    fn test_format_progress_overfunded() {
+        assert_eq!(format_progress(usdc(123), usdc(100), 6, "USDC"), "raised 123 / 100 USDC (123%)");
+        let progress = format_progress(U256::MAX, U256::from(1), 0, "WEI");
+        assert!(progress.ends_with(&format!("({}00%)", to_string(U256::MAX))));
+    }
+
+    #[test]
+    fn test_format_progress_zero_goal() {
+        assert_eq!(format_progress(usdc(5), U256::ZERO, 6, "USDC"), "raised 5 / 0 USDC (no goal)");
+    }
+
+    #[test]
+    fn test_format_progress_bar() {
+        assert_eq!(format_progress_bar(usdc(45), usdc(100), 10), "█████░░░░░");
+        assert_eq!(format_progress_bar(U256::ZERO, usdc(100), 4), "░░░░");
+        assert_eq!(format_progress_bar(usdc(150), usdc(100), 4), "████");
+        assert_eq!(format_progress_bar(U256::ZERO, U256::ZERO, 4), "░░░░");
+        assert_eq!(format_progress_bar(U256::from(1), U256::ZERO, 4), "████");
+    }
+
+    #[test]
+    fn test_width_one_bar() {
+        assert_eq!(format_progress_bar(U256::from(49), U256::from(100), 1), "░");
+        assert_eq!(format_progress_bar(U256::from(50), U256::from(100), 1), "█");
+        assert_eq!(format_progress_bar(U256::from(100), U256::from(100), 1), "█");
+    }
+
+    #[test]
+    fn test_bar_just_below_goal() {
+        let goal = U256::from(1_000);
+        assert_eq!(format_progress_bar(U256::from(995), goal, 100), "█".repeat(100));
+        assert_eq!(format_progress_bar(U256::from(994), goal, 100), "█".repeat(99) + "░");
+        assert_eq!(format_progress(U256::from(995), goal, 0, "DAI"), "raised 995 / 1,000 DAI (99.5%)");
+    }
+}
@@ -0,0 +1,983 @@
+//! QR codes (ISO/IEC 18004) for short payloads such as EIP-681 payment
+//! URIs, rendered as SVG rectangles or terminal text.
+//!
+//! Only byte mode and versions 1–10 (21×21 to 57×57 modules) are
+//! supported, which holds up to 271 bytes at the lowest error correction
+//! level. Matrices are indexed `[row][column]`, with `true` for a dark
+//! module.
+
+use core::fmt;
+
+/// Largest supported version
+const MAX_VERSION: usize = 10;
+
+/// Error correction codewords per block, by version and level (L, M, Q, H)
+const EC_PER_BLOCK: [[usize; 4]; MAX_VERSION] = [
+    [7, 10, 13, 17],
+    [10, 16, 22, 28],
+    [15, 26, 18, 22],
+    [20, 18, 26, 16],
+    [26, 24, 18, 22],
+    [18, 16, 24, 28],
+    [20, 18, 18, 26],
+    [24, 22, 22, 26],
+    [30, 22, 20, 24],
+    [18, 26, 24, 28],
+];
+
+/// Error correction blocks, by version and level (L, M, Q, H)
+const BLOCKS: [[usize; 4]; MAX_VERSION] = [
+    [1, 1, 1, 1],
+    [1, 1, 1, 1],
+    [1, 1, 2, 2],
+    [1, 2, 2, 4],
+    [1, 2, 4, 4],
+    [2, 4, 4, 4],
+    [2, 4, 6, 5],
+    [2, 4, 6, 6],
+    [2, 5, 8, 8],
+    [4, 5, 8, 8],
+];
+
+/// Alignment pattern center coordinates, by version
+const ALIGNMENT: [&[usize]; MAX_VERSION] = [
+    &[],
+    &[6, 18],
+    &[6, 22],
+    &[6, 26],
+    &[6, 30],
+    &[6, 34],
+    &[6, 22, 38],
+    &[6, 24, 42],
+    &[6, 26, 46],
+    &[6, 28, 50],
+];
+
+/// Mode indicator for byte mode
+const BYTE_MODE: u32 = 0b0100;
+
+/// Pad codewords appended alternately after the data
+const PAD_BYTES: [u8; 2] = [0xEC, 0x11];
+
+/// Penalty weights for mask selection, rules 1 to 4
+const PENALTY_RUN: usize = 3;
+const PENALTY_BLOCK: usize = 3;
+const PENALTY_FINDER: usize = 40;
+const PENALTY_BALANCE: usize = 10;
+
+/// Error correction level: the share of codewords that can be restored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EcLevel {
+    /// About 7%
+    Low,
+    /// About 15%
+    Medium,
+    /// About 25%
+    Quartile,
+    /// About 30%
+    High,
+}
+
+impl EcLevel {
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The two format bits, which do not follow the declaration order
+    fn format_bits(self) -> u32 {
+        match self {
+            EcLevel::Low => 0b01,
+            EcLevel::Medium => 0b00,
+            EcLevel::Quartile => 0b11,
+            EcLevel::High => 0b10,
+        }
+    }
+}
+
+/// Error types for QR encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrError {
+    /// Payload is longer than the `max` bytes version 10 holds at the level
+    DataTooLong { max: usize, found: usize },
+}
+
+impl fmt::Display for QrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QrError::DataTooLong { max, found } => {
+                write!(f, "payload of {} bytes exceeds the QR capacity of {} bytes", found, max)
+            }
+        }
+    }
+}
+
+/// Encodes `data` as a QR code in byte mode and returns its module matrix.
+///
+/// The smallest version from 1 to 10 that fits is used, and of the eight
+/// masks the one with the lowest penalty score is applied. The matrix has
+/// no quiet zone; leave four light modules around it when rendering.
+pub fn qr_matrix(data: &str, ec: EcLevel) -> Result<Vec<Vec<bool>>, QrError> {
+    let bytes = data.as_bytes();
+    let version = (1..=MAX_VERSION)
+        .find(|&version| byte_capacity(version, ec) >= bytes.len())
+        .ok_or(QrError::DataTooLong { max: byte_capacity(MAX_VERSION, ec), found: bytes.len() })?;
+
+    let codewords = add_error_correction(&data_codewords(bytes, version, ec), version, ec);
+    let mut grid = Grid::new(version);
+    grid.draw_function_patterns(version, ec);
+    grid.draw_codewords(&codewords);
+
+    let mut best = (usize::MAX, 0);
+    for mask in 0..8 {
+        grid.apply_mask(mask);
+        grid.draw_format(ec, mask);
+        best = best.min((grid.penalty(), mask));
+        grid.apply_mask(mask);
+    }
+    grid.apply_mask(best.1);
+    grid.draw_format(ec, best.1);
+    Ok(grid.dark)
+}
+
+/// Renders a matrix as SVG `<rect>` elements, one per horizontal run of
+/// dark modules, each module `module_px` units square.
+///
+/// Coordinates start at 0 with no quiet zone and no fill is set, so the
+/// rectangles take the fill of the enclosing element.
+pub fn qr_to_svg_rects(matrix: &[Vec<bool>], module_px: u32) -> String {
+    let mut result = String::new();
+    for (row, modules) in matrix.iter().enumerate() {
+        let mut col = 0;
+        while col < modules.len() {
+            if !modules[col] {
+                col += 1;
+                continue;
+            }
+            let start = col;
+            while col < modules.len() && modules[col] {
+                col += 1;
+            }
+            result.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+                start as u32 * module_px,
+                row as u32 * module_px,
+                (col - start) as u32 * module_px,
+                module_px
+            ));
+        }
+    }
+    result
+}
+
+/// Renders a matrix as lines of half-block characters, two module rows
+/// per line, for debugging in a terminal.
+///
+/// Dark modules are drawn, so the code scans when shown dark on light; on
+/// a dark terminal theme it appears inverted. There is no quiet zone.
+pub fn qr_to_unicode(matrix: &[Vec<bool>]) -> String {
+    let width = matrix.iter().map(Vec::len).max().unwrap_or(0);
+    let module = |row: usize, col: usize| matrix.get(row).and_then(|r| r.get(col)).copied().unwrap_or(false);
+    (0..matrix.len())
+        .step_by(2)
+        .map(|row| {
+            (0..width)
+                .map(|col| match (module(row, col), module(row + 1, col)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Bytes of payload a version holds at `ec` in byte mode
+fn byte_capacity(version: usize, ec: EcLevel) -> usize {
+    (num_data_codewords(version, ec) * 8 - 4 - char_count_bits(version)) / 8
+}
+
+/// Width of the character count field in byte mode
+fn char_count_bits(version: usize) -> usize {
+    if version < 10 { 8 } else { 16 }
+}
+
+/// Modules available for codewords once function patterns are placed
+fn num_raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let alignments = version / 7 + 2;
+        result -= (25 * alignments - 10) * alignments - 55;
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+fn num_data_codewords(version: usize, ec: EcLevel) -> usize {
+    let ecc = EC_PER_BLOCK[version - 1][ec.index()] * BLOCKS[version - 1][ec.index()];
+    num_raw_data_modules(version) / 8 - ecc
+}
+
+/// Builds the data codewords: mode, count, payload, terminator and padding
+fn data_codewords(data: &[u8], version: usize, ec: EcLevel) -> Vec<u8> {
+    let capacity = num_data_codewords(version, ec) * 8;
+    let mut bits = BitBuffer::default();
+    bits.push(BYTE_MODE, 4);
+    bits.push(data.len() as u32, char_count_bits(version));
+    for &byte in data {
+        bits.push(u32::from(byte), 8);
+    }
+    bits.push(0, (capacity - bits.len).min(4));
+    bits.push(0, (8 - bits.len % 8) % 8);
+
+    let mut codewords = bits.bytes;
+    for pad in PAD_BYTES.iter().cycle().take(capacity / 8 - codewords.len()) {
+        codewords.push(*pad);
+    }
+    codewords
+}
+
+/// Splits data into blocks, appends Reed–Solomon codewords to each and
+/// interleaves the result
+fn add_error_correction(data: &[u8], version: usize, ec: EcLevel) -> Vec<u8> {
+    let num_blocks = BLOCKS[version - 1][ec.index()];
+    let ec_len = EC_PER_BLOCK[version - 1][ec.index()];
+    let short_len = data.len() / num_blocks;
+    let num_short = num_blocks - data.len() % num_blocks;
+    let divisor = rs_divisor(ec_len);
+
+    let mut blocks = Vec::with_capacity(num_blocks);
+    let mut start = 0;
+    for i in 0..num_blocks {
+        let len = short_len + usize::from(i >= num_short);
+        let block = &data[start..start + len];
+        blocks.push((block, rs_remainder(block, &divisor)));
+        start += len;
+    }
+
+    let mut result = Vec::with_capacity(num_raw_data_modules(version) / 8);
+    for i in 0..=short_len {
+        for (block, _) in &blocks {
+            if let Some(&byte) = block.get(i) {
+                result.push(byte);
+            }
+        }
+    }
+    for i in 0..ec_len {
+        for (_, ecc) in &blocks {
+            result.push(ecc[i]);
+        }
+    }
+    result
+}
+
+/// Multiplies in GF(256) modulo x^8 + x^4 + x^3 + x^2 + 1
+fn gf_mul(x: u8, y: u8) -> u8 {
+    let mut product = 0u16;
+    for i in (0..8).rev() {
+        product = (product << 1) ^ ((product >> 7) * 0x11D);
+        product ^= ((y >> i) & 1) as u16 * x as u16;
+    }
+    product as u8
+}
+
+/// Coefficients of the generator polynomial (x - α^0)…(x - α^(degree-1)),
+/// highest power first and the leading 1 omitted
+fn rs_divisor(degree: usize) -> Vec<u8> {
+    let mut divisor = vec![0u8; degree];
+    divisor[degree - 1] = 1;
+    let mut root = 1u8;
+    for _ in 0..degree {
+        for j in 0..degree {
+            divisor[j] = gf_mul(divisor[j], root);
+            if j + 1 < degree {
+                divisor[j] ^= divisor[j + 1];
+            }
+        }
+        root = gf_mul(root, 0x02);
+    }
+    divisor
+}
+
+/// Remainder of data × x^degree divided by the generator polynomial
+fn rs_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0u8; divisor.len()];
+    for &byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (r, &d) in result.iter_mut().zip(divisor) {
+            *r ^= gf_mul(d, factor);
+        }
+    }
+    result
+}
+
+/// Fifteen format bits: level and mask protected by a BCH(15, 5) code
+fn format_bits(ec: EcLevel, mask: usize) -> u32 {
+    let data = ec.format_bits() << 3 | mask as u32;
+    let mut rem = data;
+    for _ in 0..10 {
+        rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+    }
+    (data << 10 | rem) ^ 0x5412
+}
+
+/// Eighteen version bits: the version protected by a BCH(18, 6) code
+fn version_bits(version: usize) -> u32 {
+    let mut rem = version as u32;
+    for _ in 0..12 {
+        rem = (rem << 1) ^ ((rem >> 11) * 0x1F25);
+    }
+    (version as u32) << 12 | rem
+}
+
+fn mask_applies(mask: usize, row: usize, col: usize) -> bool {
+    match mask {
+        0 => (row + col) % 2 == 0,
+        1 => row % 2 == 0,
+        2 => col % 3 == 0,
+        3 => (row + col) % 3 == 0,
+        4 => (row / 2 + col / 3) % 2 == 0,
+        5 => row * col % 2 + row * col % 3 == 0,
+        6 => (row * col % 2 + row * col % 3) % 2 == 0,
+        _ => ((row + col) % 2 + row * col % 3) % 2 == 0,
+    }
+}
+
+/// Bits appended most significant first, packed into bytes
+#[derive(Default)]
+struct BitBuffer {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitBuffer {
+    fn push(&mut self, value: u32, count: usize) {
+        for i in (0..count).rev() {
+            if self.len % 8 == 0 {
+                self.bytes.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                *self.bytes.last_mut().expect("byte pushed above") |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+}
+
+/// Module colors plus which modules belong to function patterns
+struct Grid {
+    size: usize,
+    dark: Vec<Vec<bool>>,
+    function: Vec<Vec<bool>>,
+}
+
+impl Grid {
+    fn new(version: usize) -> Self {
+        let size = 17 + 4 * version;
+        Self { size, dark: vec![vec![false; size]; size], function: vec![vec![false; size]; size] }
+    }
+
+    fn set_function(&mut self, row: usize, col: usize, dark: bool) {
+        self.dark[row][col] = dark;
+        self.function[row][col] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize, ec: EcLevel) {
+        let size = self.size;
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        for (row, col) in [(3, 3), (3, size - 4), (size - 4, 3)] {
+            for dr in -4isize..=4 {
+                for dc in -4isize..=4 {
+                    let (r, c) = (row as isize + dr, col as isize + dc);
+                    if (0..size as isize).contains(&r) && (0..size as isize).contains(&c) {
+                        let ring = dr.abs().max(dc.abs());
+                        self.set_function(r as usize, c as usize, ring != 2 && ring != 4);
+                    }
+                }
+            }
+        }
+
+        let centers = ALIGNMENT[version - 1];
+        for (i, &row) in centers.iter().enumerate() {
+            for (j, &col) in centers.iter().enumerate() {
+                // Skip the three corners occupied by finder patterns
+                let last = centers.len() - 1;
+                let finder_corner = (i == 0 && (j == 0 || j == last)) || (i == last && j == 0);
+                if finder_corner {
+                    continue;
+                }
+                for dr in -2isize..=2 {
+                    for dc in -2isize..=2 {
+                        let (r, c) = ((row as isize + dr) as usize, (col as isize + dc) as usize);
+                        self.set_function(r, c, dr.abs().max(dc.abs()) != 1);
+                    }
+                }
+            }
+        }
+
+        // Reserve the format areas; the real mask is drawn later
+        self.draw_format(ec, 0);
+        if version >= 7 {
+            let bits = version_bits(version);
+            for i in 0..18 {
+                let dark = (bits >> i) & 1 == 1;
+                let (a, b) = (size - 11 + i % 3, i / 3);
+                self.set_function(b, a, dark);
+                self.set_function(a, b, dark);
+            }
+        }
+    }
+
+    fn draw_format(&mut self, ec: EcLevel, mask: usize) {
+        let bits = format_bits(ec, mask);
+        let bit = |i: usize| (bits >> i) & 1 == 1;
+        let size = self.size;
+
+        // Around the top left finder
+        for i in 0..=5 {
+            self.set_function(i, 8, bit(i));
+        }
+        self.set_function(7, 8, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(8, 7, bit(8));
+        for i in 9..15 {
+            self.set_function(8, 14 - i, bit(i));
+        }
+
+        // Split between the other two finders
+        for i in 0..8 {
+            self.set_function(8, size - 1 - i, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(size - 15 + i, 8, bit(i));
+        }
+        self.set_function(size - 8, 8, true);
+    }
+
+    /// Places codeword bits in the two-column zigzag from the bottom right,
+    /// skipping function modules and the vertical timing column
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size;
+        let mut index = 0;
+        let mut right = size - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            let upward = (right + 1) & 2 == 0;
+            for vert in 0..size {
+                let row = if upward { size - 1 - vert } else { vert };
+                for col in [right, right - 1] {
+                    if !self.function[row][col] && index < codewords.len() * 8 {
+                        self.dark[row][col] = (codewords[index / 8] >> (7 - index % 8)) & 1 == 1;
+                        index += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    /// Toggles the mask; applying the same mask twice restores the grid
+    fn apply_mask(&mut self, mask: usize) {
+        for row in 0..self.size {
+            for col in 0..self.size {
+                if !self.function[row][col] && mask_applies(mask, row, col) {
+                    self.dark[row][col] ^= true;
+                }
+            }
+        }
+    }
+
+    /// Scores the four penalty rules of the standard; lower is better
+    fn penalty(&self) -> usize {
+        let size = self.size;
+        let cols: Vec<Vec<bool>> = (0..size).map(|c| (0..size).map(|r| self.dark[r][c]).collect()).collect();
+        let finder = [true, false, true, true, true, false, true, false, false, false, false];
+        let mut finder_reversed = finder;
+        finder_reversed.reverse();
+
+        let mut result = 0;
+        for line in self.dark.iter().chain(cols.iter()) {
+            // Rule 1: runs of five or more modules of one color
+            let mut run = 1;
+            for i in 1..=size {
+                if i < size && line[i] == line[i - 1] {
+                    run += 1;
+                } else {
+                    if run >= 5 {
+                        result += PENALTY_RUN + run - 5;
+                    }
+                    run = 1;
+                }
+            }
+            // Rule 3: patterns resembling a finder
+            for window in line.windows(finder.len()) {
+                if window == finder || window == finder_reversed {
+                    result += PENALTY_FINDER;
+                }
+            }
+        }
+
+        // Rule 2: 2×2 blocks of one color
+        for r in 0..size - 1 {
+            for c in 0..size - 1 {
+                let color = self.dark[r][c];
+                if self.dark[r][c + 1] == color && self.dark[r + 1][c] == color && self.dark[r + 1][c + 1] == color {
+                    result += PENALTY_BLOCK;
+                }
+            }
+        }
+
+        // Rule 4: deviation of the dark share from 50%, in steps of 5%
+        let dark = self.dark.iter().flatten().filter(|&&d| d).count();
+        let percent = dark * 100 / (size * size);
+        result + percent.abs_diff(50) / 5 * PENALTY_BALANCE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Version 1-M matrix of "ETH" (mask 2) from Nayuki's `qrcodegen` 1.8.0,
+    /// and identical from the `qrcode` crate 0.14.1 with the mask forced
+    const ETH_1M: [&str; 21] = [
+        "#######...##..#######",
+        "#.....#...#...#.....#",
+        "#.###.#.#####.#.###.#",
+        "#.###.#.#..##.#.###.#",
+        "#.###.#.##..#.#.###.#",
+        "#.....#.####..#.....#",
+        "#######.#.#.#.#######",
+        "........###..........",
+        "#.#####...##..#####..",
+        ".#.....#...####..###.",
+        "##...###....#.##..##.",
+        ".###.#.##..####..###.",
+        ".####.##.#..#..#..##.",
+        "........#...#..#.....",
+        "#######..###.#..#.##.",
+        "#.....#.#......##.###",
+        "#.###.#.#..#.#..#.#..",
+        "#.###.#.#######..#...",
+        "#.###.#.###.#.##.....",
+        "#.....#....####..#...",
+        "#######.#...#..#..##.",
+    ];
+
+    /// Payment URI used by the fixtures below
+    const PAYMENT_URI: &str = "ethereum:0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359@1?value=2.014e18";
+
+    /// Version 8-H matrix of `PAYMENT_URI` (mask 2), which has version
+    /// information, six alignment patterns and blocks of unequal length.
+    /// Generated like `ETH_1M`; each row is packed most significant bit
+    /// first, with the last byte padded by light modules.
+    const PAYMENT_URI_8H: [[u8; 7]; 49] = [
+        [0xfe, 0xb0, 0xcb, 0xa9, 0x88, 0xbf, 0x80],
+        [0x82, 0xb0, 0x3c, 0xdf, 0x7f, 0xa0, 0x80],
+        [0xba, 0xb9, 0x32, 0xfb, 0x51, 0xae, 0x80],
+        [0xba, 0x07, 0x3a, 0xc5, 0xb9, 0x2e, 0x80],
+        [0xba, 0x66, 0x1b, 0xfb, 0x8c, 0x2e, 0x80],
+        [0x82, 0xfb, 0xb2, 0x20, 0xee, 0x20, 0x80],
+        [0xfe, 0xaa, 0xaa, 0xaa, 0xaa, 0xbf, 0x80],
+        [0x00, 0x80, 0xde, 0x39, 0x5d, 0x80, 0x00],
+        [0x3a, 0xb5, 0x77, 0xf2, 0x49, 0xf3, 0x80],
+        [0xb1, 0xf7, 0x05, 0x40, 0xd8, 0x7c, 0x00],
+        [0x63, 0x64, 0xcf, 0xe6, 0x2e, 0x11, 0x80],
+        [0x48, 0xb5, 0x69, 0xb2, 0x86, 0x68, 0x80],
+        [0x97, 0xc6, 0xac, 0x3d, 0x1a, 0xf7, 0x80],
+        [0x69, 0xf9, 0xd8, 0xf4, 0xc8, 0x32, 0x00],
+        [0x96, 0x65, 0x7f, 0xb4, 0x3e, 0x4f, 0x80],
+        [0xdc, 0x97, 0xdc, 0x5e, 0x2e, 0x3a, 0x00],
+        [0x1a, 0xff, 0x1d, 0x89, 0xfa, 0x64, 0x80],
+        [0x25, 0x3e, 0xd8, 0x87, 0x3f, 0x29, 0x00],
+        [0xf6, 0xa4, 0x4d, 0x39, 0xc0, 0x70, 0x80],
+        [0x0c, 0x12, 0x37, 0x78, 0xf5, 0x68, 0x00],
+        [0x0e, 0x52, 0x2c, 0xab, 0x4d, 0xf3, 0x80],
+        [0xe8, 0x7b, 0x6e, 0x75, 0x9d, 0x66, 0x00],
+        [0xcf, 0xc8, 0xd7, 0xfb, 0x7b, 0xf9, 0x80],
+        [0x08, 0x8b, 0x96, 0x21, 0x00, 0x8c, 0x80],
+        [0x4a, 0xfe, 0x7a, 0xa4, 0x7f, 0xab, 0x80],
+        [0x48, 0xf8, 0xf2, 0x23, 0x5d, 0x8d, 0x80],
+        [0x6f, 0xcf, 0x27, 0xea, 0x2b, 0xfe, 0x80],
+        [0x45, 0xdd, 0xe1, 0xca, 0x10, 0xcd, 0x80],
+        [0xd3, 0x83, 0x49, 0x1d, 0x8b, 0xa2, 0x80],
+        [0xb8, 0x9e, 0x53, 0xe8, 0x85, 0xbc, 0x00],
+        [0xef, 0x76, 0x4f, 0x65, 0xe3, 0xab, 0x80],
+        [0x54, 0x9a, 0xe7, 0x46, 0x48, 0x5c, 0x00],
+        [0x92, 0x93, 0x8f, 0x17, 0x97, 0xab, 0x80],
+        [0xa9, 0x29, 0x1d, 0x26, 0xc1, 0xd3, 0x00],
+        [0x22, 0x82, 0xc1, 0x25, 0xb7, 0xf4, 0x80],
+        [0xdd, 0xc7, 0xe9, 0xdc, 0x50, 0x11, 0x00],
+        [0x6e, 0x32, 0x61, 0x12, 0x09, 0xbb, 0x80],
+        [0xec, 0xc3, 0xe5, 0xb0, 0x59, 0x44, 0x00],
+        [0x46, 0xbf, 0xea, 0x06, 0xae, 0x39, 0x80],
+        [0x70, 0xd9, 0xbb, 0xb3, 0xd0, 0xc4, 0x80],
+        [0xe3, 0xeb, 0xfb, 0xfb, 0x0d, 0xf8, 0x80],
+        [0x00, 0xfa, 0x42, 0x2f, 0xd9, 0x89, 0x00],
+        [0xfe, 0x65, 0x6a, 0xb5, 0x9c, 0xac, 0x80],
+        [0x82, 0x07, 0xae, 0x3f, 0x87, 0x88, 0x00],
+        [0xba, 0xdc, 0xe7, 0xe4, 0x7d, 0xfe, 0x80],
+        [0xba, 0xa8, 0x23, 0x13, 0x11, 0x5c, 0x80],
+        [0xba, 0xca, 0x72, 0x12, 0x6e, 0xe1, 0x80],
+        [0x82, 0x5c, 0x14, 0x7f, 0x91, 0xbc, 0x80],
+        [0xfe, 0x52, 0x35, 0x0e, 0x6c, 0xd3, 0x80],
+    ];
+
+    fn render(matrix: &[Vec<bool>]) -> Vec<String> {
+        matrix.iter().map(|row| row.iter().map(|&d| if d { '#' } else { '.' }).collect()).collect()
+    }
+
+    fn pack(matrix: &[Vec<bool>]) -> Vec<Vec<u8>> {
+        let byte = |chunk: &[bool]| chunk.iter().enumerate().fold(0u8, |acc, (i, &d)| acc | (d as u8) << (7 - i));
+        matrix.iter().map(|row| row.chunks(8).map(byte).collect()).collect()
+    }
+
+    /// Format information for levels L, M, Q, H and masks 0–7, after the
+    /// 0x5412 XOR, as listed in ISO/IEC 18004 Annex C
+    const FORMAT_INFO: [[u32; 8]; 4] = [
+        [0x77C4, 0x72F3, 0x7DAA, 0x789D, 0x662F, 0x6318, 0x6C41, 0x6976],
+        [0x5412, 0x5125, 0x5E7C, 0x5B4B, 0x45F9, 0x40CE, 0x4F97, 0x4AA0],
+        [0x355F, 0x3068, 0x3F31, 0x3A06, 0x24B4, 0x2183, 0x2EDA, 0x2BED],
+        [0x1689, 0x13BE, 0x1CE7, 0x19D0, 0x0762, 0x0255, 0x0D0C, 0x083B],
+    ];
+
+    /// Version information for versions 7–10, ISO/IEC 18004 Annex D
+    const VERSION_INFO: [u32; 4] = [0x07C94, 0x085BC, 0x09A99, 0x0A4D3];
+
+    /// Total error correction codewords and number of blocks by version and
+    /// level (L, M, Q, H), as listed in ISO/IEC 18004 Table 9
+    const EC_TABLE: [[(usize, usize); 4]; MAX_VERSION] = [
+        [(7, 1), (10, 1), (13, 1), (17, 1)],
+        [(10, 1), (16, 1), (22, 1), (28, 1)],
+        [(15, 1), (26, 1), (36, 2), (44, 2)],
+        [(20, 1), (36, 2), (52, 2), (64, 4)],
+        [(26, 1), (48, 2), (72, 4), (88, 4)],
+        [(36, 2), (64, 4), (96, 4), (112, 4)],
+        [(40, 2), (72, 4), (108, 6), (130, 5)],
+        [(48, 2), (88, 4), (132, 6), (156, 6)],
+        [(60, 2), (110, 5), (160, 8), (192, 8)],
+        [(72, 4), (130, 5), (192, 8), (224, 8)],
+    ];
+
+    /// Multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1, shift and
+    /// add rather than the encoder's table-free reduction
+    fn gf_mul_ref(x: u8, y: u8) -> u8 {
+        let (mut a, mut b, mut product) = (x as u16, y, 0u16);
+        while b != 0 {
+            if b & 1 == 1 {
+                product ^= a;
+            }
+            a <<= 1;
+            if a & 0x100 != 0 {
+                a ^= 0x11D;
+            }
+            b >>= 1;
+        }
+        product as u8
+    }
+
+    /// Evaluates a codeword block at α^i for each i; all zero if the block is
+    /// a valid Reed–Solomon codeword
+    fn syndromes(block: &[u8], ec_len: usize) -> Vec<u8> {
+        let mut alpha = 1u8;
+        let mut result = Vec::new();
+        for _ in 0..ec_len {
+            let mut value = 0u8;
+            for &c in block {
+                value = gf_mul_ref(value, alpha) ^ c;
+            }
+            result.push(value);
+            alpha = gf_mul_ref(alpha, 2);
+        }
+        result
+    }
+
+    /// Reads a matrix back into its payload without any of the encoder's
+    /// code or tables: format and version information, block layout and
+    /// alignment positions come from the standard, and every block must
+    /// pass a Reed–Solomon syndrome check
+    fn decode(matrix: &[Vec<bool>]) -> (EcLevel, String) {
+        let size = matrix.len();
+        let version = (size - 17) / 4;
+        let read = |positions: &[(usize, usize)]| {
+            positions.iter().enumerate().fold(0u32, |acc, (i, &(r, c))| acc | (matrix[r][c] as u32) << i)
+        };
+
+        let mut first = Vec::new();
+        first.extend((0..=5).map(|i| (i, 8)));
+        first.extend([(7, 8), (8, 8), (8, 7)]);
+        first.extend((9..15).map(|i| (8, 14 - i)));
+        let mut second: Vec<(usize, usize)> = (0..8).map(|i| (8, size - 1 - i)).collect();
+        second.extend((8..15).map(|i| (size - 15 + i, 8)));
+        let format = read(&first);
+        assert_eq!(format, read(&second), "format copies differ");
+        let (level, mask) = (0..32)
+            .map(|i| (i / 8, i % 8))
+            .find(|&(level, mask)| FORMAT_INFO[level][mask] == format)
+            .expect("valid format bits");
+        let ec = [EcLevel::Low, EcLevel::Medium, EcLevel::Quartile, EcLevel::High][level];
+
+        if version >= 7 {
+            // Bit i sits at row size - 11 + i % 3, column i / 3, and transposed
+            let below: Vec<(usize, usize)> = (0..18).map(|i| (size - 11 + i % 3, i / 3)).collect();
+            let right: Vec<(usize, usize)> = below.iter().map(|&(r, c)| (c, r)).collect();
+            assert_eq!(read(&below), VERSION_INFO[version - 7], "version information");
+            assert_eq!(read(&right), VERSION_INFO[version - 7], "version information copy");
+        }
+
+        // Alignment centers: 6, then evenly spaced ending at size - 7
+        let mut centers = Vec::new();
+        if version >= 2 {
+            let count = version / 7 + 2;
+            let step = (version * 4 + count * 2 + 1) / (count * 2 - 2) * 2;
+            centers.push(6);
+            centers.extend((0..count - 1).rev().map(|k| size - 7 - k * step));
+        }
+
+        // Function modules, rebuilt from the standard's layout
+        let mut reserved = vec![vec![false; size]; size];
+        reserved[6].fill(true);
+        for row in reserved.iter_mut() {
+            row[6] = true;
+        }
+        for r in 0..9 {
+            for c in 0..9 {
+                reserved[r][c] = true;
+                if c < 8 {
+                    reserved[r][size - 1 - c] = true;
+                }
+                if r < 8 {
+                    reserved[size - 1 - r][c] = true;
+                }
+            }
+        }
+        for &r in &centers {
+            for &c in &centers {
+                let in_finder = (r < 9 || r > size - 9) && (c < 9 || c > size - 9) && !(r > size - 9 && c > size - 9);
+                if !in_finder {
+                    for row in reserved.iter_mut().skip(r - 2).take(5) {
+                        row[c - 2..=c + 2].fill(true);
+                    }
+                }
+            }
+        }
+        if version >= 7 {
+            for (a, b) in (size - 11..size - 8).flat_map(|a| (0..6).map(move |b| (a, b))) {
+                reserved[a][b] = true;
+                reserved[b][a] = true;
+            }
+        }
+
+        // Mask conditions from the standard, i = row and j = column
+        let masked = |i: usize, j: usize| match mask {
+            0 => (i + j) % 2 == 0,
+            1 => i % 2 == 0,
+            2 => j % 3 == 0,
+            3 => (i + j) % 3 == 0,
+            4 => (i / 2 + j / 3) % 2 == 0,
+            5 => (i * j) % 2 + (i * j) % 3 == 0,
+            6 => ((i * j) % 2 + (i * j) % 3) % 2 == 0,
+            _ => ((i + j) % 2 + (i * j) % 3) % 2 == 0,
+        };
+
+        // Column pairs right to left, alternating up and down
+        let mut bits = Vec::new();
+        let mut columns: Vec<usize> = (7..size).rev().step_by(2).collect();
+        columns.extend([5, 3, 1]);
+        for (n, &right) in columns.iter().enumerate() {
+            let rows: Vec<usize> = if n % 2 == 0 { (0..size).rev().collect() } else { (0..size).collect() };
+            for r in rows {
+                for c in [right, right - 1] {
+                    if !reserved[r][c] {
+                        bits.push(matrix[r][c] ^ masked(r, c));
+                    }
+                }
+            }
+        }
+        let codewords: Vec<u8> =
+            bits.chunks(8).filter(|c| c.len() == 8).map(|c| c.iter().fold(0, |acc, &b| acc << 1 | b as u8)).collect();
+
+        let (ec_total, num_blocks) = EC_TABLE[version - 1][level];
+        let ec_len = ec_total / num_blocks;
+        let data_len = codewords.len() - ec_total;
+        let mut blocks: Vec<Vec<u8>> = vec![Vec::new(); num_blocks];
+        let short_len = data_len / num_blocks;
+        let num_short = num_blocks - data_len % num_blocks;
+        let mut k = 0;
+        for i in 0..=short_len {
+            for (b, block) in blocks.iter_mut().enumerate() {
+                if i < short_len || b >= num_short {
+                    block.push(codewords[k]);
+                    k += 1;
+                }
+            }
+        }
+        for _ in 0..ec_len {
+            for block in blocks.iter_mut() {
+                block.push(codewords[k]);
+                k += 1;
+            }
+        }
+        let mut data = Vec::new();
+        for block in &blocks {
+            assert!(syndromes(block, ec_len).iter().all(|&s| s == 0), "block fails Reed–Solomon check");
+            data.extend_from_slice(&block[..block.len() - ec_len]);
+        }
+
+        let bit = |i: usize| (data[i / 8] >> (7 - i % 8)) & 1;
+        let field = |start: usize, len: usize| (start..start + len).fold(0usize, |acc, i| acc << 1 | bit(i) as usize);
+        assert_eq!(field(0, 4), 0b0100, "byte mode");
+        let count_bits = if version < 10 { 8 } else { 16 };
+        let len = field(4, count_bits);
+        let payload: Vec<u8> = (0..len).map(|i| field(4 + count_bits + 8 * i, 8) as u8).collect();
+        (ec, String::from_utf8(payload).unwrap())
+    }
+
+    #[test]
+    fn test_reed_solomon_reference_vector() {
+        // "HELLO WORLD" 1-M data codewords and their published EC codewords
+        let data = [32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17];
+        assert_eq!(rs_remainder(&data, &rs_divisor(10)), [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]);
+    }
+
+    #[test]
+    fn test_format_and_version_bits() {
+        assert_eq!(format_bits(EcLevel::Low, 0), 0b111011111000100);
+        assert_eq!(format_bits(EcLevel::Medium, 0), 0b101010000010010);
+        assert_eq!(format_bits(EcLevel::Quartile, 0), 0b011010101011111);
+        assert_eq!(format_bits(EcLevel::High, 0), 0b001011010001001);
+        assert_eq!(format_bits(EcLevel::Low, 7), 0b110100101110110);
+        assert_eq!(version_bits(7), 0x07C94);
+        assert_eq!(version_bits(8), 0x085BC);
+        assert_eq!(version_bits(9), 0x09A99);
+        assert_eq!(version_bits(10), 0x0A4D3);
+    }
+
+    #[test]
+    fn test_tables_match_module_counts() {
+        let totals = [26, 44, 70, 100, 134, 172, 196, 242, 292, 346];
+        for version in 1..=MAX_VERSION {
+            assert_eq!(num_raw_data_modules(version) / 8, totals[version - 1]);
+        }
+        // Published byte-mode capacities for versions 1 and 10
+        let levels = [EcLevel::Low, EcLevel::Medium, EcLevel::Quartile, EcLevel::High];
+        assert_eq!(levels.map(|ec| byte_capacity(1, ec)), [17, 14, 11, 7]);
+        assert_eq!(levels.map(|ec| byte_capacity(10, ec)), [271, 213, 151, 119]);
+    }
+
+    #[test]
+    fn test_reference_matrix() {
+        let matrix = qr_matrix("ETH", EcLevel::Medium).unwrap();
+        assert_eq!(render(&matrix), ETH_1M);
+        assert_eq!(decode(&matrix), (EcLevel::Medium, "ETH".to_string()));
+
+        let matrix = qr_matrix(PAYMENT_URI, EcLevel::High).unwrap();
+        assert_eq!(pack(&matrix), PAYMENT_URI_8H);
+    }
+
+    #[test]
+    fn test_reference_fixtures_decode() {
+        let eth: Vec<Vec<bool>> = ETH_1M.iter().map(|row| row.chars().map(|c| c == '#').collect()).collect();
+        assert_eq!(decode(&eth), (EcLevel::Medium, "ETH".to_string()));
+        let uri: Vec<Vec<bool>> =
+            PAYMENT_URI_8H.iter().map(|row| (0..49).map(|i| row[i / 8] >> (7 - i % 8) & 1 == 1).collect()).collect();
+        assert_eq!(decode(&uri), (EcLevel::High, PAYMENT_URI.to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Reed–Solomon")]
+    fn test_decoder_rejects_corrupted_module() {
+        let mut matrix = qr_matrix("ETH", EcLevel::Medium).unwrap();
+        matrix[20][20] = !matrix[20][20];
+        decode(&matrix);
+    }
+
+    #[test]
+    fn test_payment_uri_round_trips() {
+        for ec in [EcLevel::Low, EcLevel::Medium, EcLevel::Quartile, EcLevel::High] {
+            let matrix = qr_matrix(PAYMENT_URI, ec).unwrap();
+            assert_eq!(decode(&matrix), (ec, PAYMENT_URI.to_string()));
+        }
+        // Every version and a multi-block layout with uneven block lengths
+        for len in [1, 17, 18, 40, 100, 150, 200, 271] {
+            let payload = "x".repeat(len);
+            let matrix = qr_matrix(&payload, EcLevel::Low).unwrap();
+            assert_eq!(decode(&matrix).1, payload);
+        }
+        let matrix = qr_matrix(&"é".repeat(50), EcLevel::High).unwrap();
+        assert_eq!(matrix.len(), 57);
+        assert_eq!(decode(&matrix).1, "é".repeat(50));
+    }
+
+    #[test]
+    fn test_finder_and_timing_patterns() {
+        let matrix = qr_matrix("https://example.com/", EcLevel::Low).unwrap();
+        let size = matrix.len();
+        assert_eq!(size, 25);
+        let finder = ["#######", "#.....#", "#.###.#", "#.###.#", "#.###.#", "#.....#", "#######"];
+        let rows = render(&matrix);
+        for (i, line) in finder.iter().enumerate() {
+            assert_eq!(&rows[i][..7], *line);
+            assert_eq!(&rows[i][size - 7..], *line);
+            assert_eq!(&rows[size - 7 + i][..7], *line);
+        }
+        assert_eq!(&rows[6][8..size - 8], "#.#.#.#.#");
+        assert!(matrix[size - 8][8], "dark module");
+    }
+
+    #[test]
+    fn test_capacity_exceeded() {
+        assert!(qr_matrix(&"a".repeat(271), EcLevel::Low).is_ok());
+        assert_eq!(
+            qr_matrix(&"a".repeat(272), EcLevel::Low),
+            Err(QrError::DataTooLong { max: 271, found: 272 })
+        );
+        assert_eq!(
+            qr_matrix(&"a".repeat(120), EcLevel::High),
+            Err(QrError::DataTooLong { max: 119, found: 120 })
+        );
+        assert_eq!(
+            QrError::DataTooLong { max: 119, found: 120 }.to_string(),
+            "payload of 120 bytes exceeds the QR capacity of 119 bytes"
+        );
+    }
+
+    #[test]
+    fn test_svg_rects() {
+        let matrix = vec![vec![true, true, false], vec![false, true, false], vec![true, false, true]];
+        assert_eq!(
+            qr_to_svg_rects(&matrix, 4),
+            "<rect x=\"0\" y=\"0\" width=\"8\" height=\"4\"/>\
+             <rect x=\"4\" y=\"4\" width=\"4\" height=\"4\"/>\
+             <rect x=\"0\" y=\"8\" width=\"4\" height=\"4\"/>\
+             <rect x=\"8\" y=\"8\" width=\"4\" height=\"4\"/>"
+        );
+        assert_eq!(qr_to_svg_rects(&[], 4), "");
+        let matrix = qr_matrix("ETH", EcLevel::Medium).unwrap();
+        assert!(qr_to_svg_rects(&matrix, 10).starts_with("<rect x=\"0\" y=\"0\" width=\"70\" height=\"10\"/>"));
+    }
+
+    #[test]
+    fn test_unicode() {
+        let matrix = vec![vec![true, true, false], vec![false, true, false], vec![true, false, true]];
+        assert_eq!(qr_to_unicode(&matrix), "▀█ \n▀ ▀");
+        assert_eq!(qr_to_unicode(&[]), "");
+        let matrix = qr_matrix("ETH", EcLevel::Medium).unwrap();
+        let text = qr_to_unicode(&matrix);
+        assert_eq!(text.lines().count(), 11);
+        assert_eq!(text.lines().next(), Some("█▀▀▀▀▀█   █▀  █▀▀▀▀▀█"));
+    }
+}
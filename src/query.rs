@@ -0,0 +1,163 @@
+//! Parsing of `key=value&key=value` query strings with percent-decoding.
+
+use alloy_primitives::U256;
+
+use crate::parse::{parse_u256, ParseError};
+
+/// How `+` is decoded by `parse_query_with`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueryMode {
+    /// `+` is a literal plus sign, as in RFC 3986
+    #[default]
+    Strict,
+    /// `+` is a space, as in HTML form encoding
+    Lenient,
+}
+
+/// Error types for query string parsing and lookup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryError {
+    /// `%` at this byte offset is not followed by two hex digits
+    InvalidPercent(usize),
+    /// Key or value starting at this byte offset decodes to invalid UTF-8
+    InvalidUtf8(usize),
+    /// Requested key is not present
+    MissingKey,
+    /// Value for the requested key did not parse
+    InvalidValue(ParseError),
+}
+
+/// Parses a query string in `QueryMode::Strict`.
+pub fn parse_query(s: &str) -> Result<Vec<(String, String)>, QueryError> {
+    parse_query_with(s, QueryMode::Strict)
+}
+
+/// Parses `s` into percent-decoded `(key, value)` pairs in input order.
+///
+/// Pairs are separated by `&` and empty pairs are skipped, so "" and "&"
+/// give no pairs. A pair without `=` has an empty value, and repeated keys
+/// are all kept. Errors carry byte offsets into `s`.
+pub fn parse_query_with(s: &str, mode: QueryMode) -> Result<Vec<(String, String)>, QueryError> {
+    let mut pairs = Vec::new();
+    let mut offset = 0;
+    for pair in s.split('&') {
+        let start = offset;
+        offset += pair.len() + 1;
+        if pair.is_empty() {
+            continue;
+        }
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value_start = start + key.len() + 1;
+        pairs.push((decode(key, start, mode)?, decode(value, value_start, mode)?));
+    }
+    Ok(pairs)
+}
+
+/// Returns the first value for `key` parsed by `parse_u256` (decimal or
+/// "0x" hex).
+pub fn query_get_u256(pairs: &[(String, String)], key: &str) -> Result<U256, QueryError> {
+    let value = pairs
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.as_str())
+        .ok_or(QueryError::MissingKey)?;
+    parse_u256(value).map_err(QueryError::InvalidValue)
+}
+
+/// Percent-decodes one key or value found at byte `offset` of the input.
+fn decode(component: &str, offset: usize, mode: QueryMode) -> Result<String, QueryError> {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = component
+                    .get(i + 1..i + 3)
+                    .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .ok_or(QueryError::InvalidPercent(offset + i))?;
+                decoded.push(u8::from_str_radix(hex, 16).expect("checked hex digits"));
+                i += 3;
+            }
+            b'+' if mode == QueryMode::Lenient => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            b => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| QueryError::InvalidUtf8(offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(list: &[(&str, &str)]) -> Vec<(String, String)> {
+        list.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_example_payload() {
+        assert_eq!(
+            parse_query("tier=gold&qty=3&note=hi%20there").unwrap(),
+            pairs(&[("tier", "gold"), ("qty", "3"), ("note", "hi there")])
+        );
+    }
+
+    #[test]
+    fn test_invalid_percent() {
+        assert_eq!(parse_query("a=1&note=%zz"), Err(QueryError::InvalidPercent(9)));
+        assert_eq!(parse_query("a=%2"), Err(QueryError::InvalidPercent(2)));
+        assert_eq!(parse_query("k%=1"), Err(QueryError::InvalidPercent(1)));
+        // Multibyte text after `%` must not panic on a char boundary
+        assert_eq!(parse_query("a=%é"), Err(QueryError::InvalidPercent(2)));
+    }
+
+    #[test]
+    fn test_plus_modes() {
+        assert_eq!(parse_query("q=a+b%2Bc").unwrap(), pairs(&[("q", "a+b+c")]));
+        assert_eq!(parse_query_with("q=a+b%2Bc", QueryMode::Lenient).unwrap(), pairs(&[("q", "a b+c")]));
+        assert_eq!(parse_query_with("first+name=x", QueryMode::Lenient).unwrap(), pairs(&[("first name", "x")]));
+    }
+
+    #[test]
+    fn test_duplicates_and_missing_values() {
+        assert_eq!(
+            parse_query("tag=a&flag&tag=b&empty=&tag=a").unwrap(),
+            pairs(&[("tag", "a"), ("flag", ""), ("tag", "b"), ("empty", ""), ("tag", "a")])
+        );
+        assert_eq!(parse_query("a==b").unwrap(), pairs(&[("a", "=b")]));
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(parse_query("").unwrap(), pairs(&[]));
+        assert_eq!(parse_query("&&").unwrap(), pairs(&[]));
+        assert_eq!(parse_query("&a=1&").unwrap(), pairs(&[("a", "1")]));
+    }
+
+    #[test]
+    fn test_multibyte_percent_sequence() {
+        assert_eq!(parse_query("name=caf%C3%A9&sym=%E2%9C%93").unwrap(), pairs(&[("name", "café"), ("sym", "✓")]));
+        // Raw UTF-8 passes through unchanged
+        assert_eq!(parse_query("name=café").unwrap(), pairs(&[("name", "café")]));
+        // A truncated sequence is rejected at the start of the value
+        assert_eq!(parse_query("x=1&name=caf%C3"), Err(QueryError::InvalidUtf8(9)));
+    }
+
+    #[test]
+    fn test_query_get_u256() {
+        let query = parse_query("qty=3&qty=4&id=0x2a&bad=3x").unwrap();
+        assert_eq!(query_get_u256(&query, "qty"), Ok(U256::from(3)));
+        assert_eq!(query_get_u256(&query, "id"), Ok(U256::from(42)));
+        assert_eq!(query_get_u256(&query, "missing"), Err(QueryError::MissingKey));
+        assert_eq!(
+            query_get_u256(&query, "bad"),
+            Err(QueryError::InvalidValue(ParseError::InvalidDigit { index: 1, found: 'x' }))
+        );
+    }
+}
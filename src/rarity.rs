@@ -0,0 +1,80 @@
+//! NFT trait rarity, rarity scores and ranks for metadata attributes.
+//!
+//! The crate has no metadata builder, so there is no attribute value type
+//! with a percent variant; these formatters return plain strings to be
+//! used as attribute values.
+
+use alloy_primitives::{U256, U512};
+
+use crate::progress::format_percent;
+use crate::units::{format_fixed_hundredths, format_grouped_units};
+
+/// Formats how common a trait is, e.g. "3.2% (412 of 12,800)".
+///
+/// The percentage is truncated to one decimal place as in
+/// `format_progress`. With a zero supply it is "n/a".
+pub fn format_trait_rarity(trait_count: U256, total_supply: U256) -> String {
+    let share = if total_supply.is_zero() {
+        "n/a".to_string()
+    } else {
+        format_percent(trait_count, total_supply)
+    };
+    format!(
+        "{} ({} of {})",
+        share,
+        format_grouped_units(trait_count, 0, 0),
+        format_grouped_units(total_supply, 0, 0)
+    )
+}
+
+/// Formats a fixed-point rarity score rounded half up to two decimal
+/// places, e.g. "1,234.57".
+pub fn format_rarity_score(score_fixed: U256, decimals: u8) -> String {
+    format_fixed_hundredths(U512::from(score_fixed), decimals)
+}
+
+/// Formats a rarity rank, e.g. "#42 / 10,000".
+pub fn format_rank(rank: U256, total: U256) -> String {
+    format!("#{} / {}", format_grouped_units(rank, 0, 0), format_grouped_units(total, 0, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(value: u64) -> U256 {
+        U256::from(value)
+    }
+
+    #[test]
+    fn test_format_trait_rarity() {
+        assert_eq!(format_trait_rarity(n(412), n(12_800)), "3.2% (412 of 12,800)");
+        assert_eq!(format_trait_rarity(n(10_000), n(10_000)), "100% (10,000 of 10,000)");
+        assert_eq!(format_trait_rarity(n(0), n(10_000)), "0% (0 of 10,000)");
+    }
+
+    #[test]
+    fn test_format_trait_rarity_zero_supply() {
+        assert_eq!(format_trait_rarity(n(0), n(0)), "n/a (0 of 0)");
+        assert_eq!(format_trait_rarity(n(3), n(0)), "n/a (3 of 0)");
+    }
+
+    #[test]
+    fn test_format_rarity_score() {
+        assert_eq!(format_rarity_score(n(1_234_567), 3), "1,234.57");
+        assert_eq!(format_rarity_score(n(42), 0), "42");
+        assert_eq!(format_rarity_score(n(999_999), 6), "1");
+        assert_eq!(
+            format_rarity_score(U256::MAX, 18),
+            "115,792,089,237,316,195,423,570,985,008,687,907,853,269,984,665,640,564,039,457.58"
+        );
+        assert_eq!(format_rarity_score(U256::MAX, 255), "0");
+        assert_eq!(format_rarity_score(U256::MAX, 77), "1.16");
+    }
+
+    #[test]
+    fn test_format_rank() {
+        assert_eq!(format_rank(n(42), n(10_000)), "#42 / 10,000");
+        assert_eq!(format_rank(n(1), n(1)), "#1 / 1");
+    }
+}
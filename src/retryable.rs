@@ -0,0 +1,157 @@
+//! Arbitrum retryable tickets and L1-to-L2 address aliasing.
+
+use alloy_primitives::{Address, B256, U256};
+
+use crate::fluent::group_digits;
+use crate::table::render_table;
+use crate::{
+    address_to_checksum_hex_string, bytes_to_hex_string, format_ether_in, format_ether_smart, to_string,
+    EtherUnit,
+};
+
+/// Offset added to an L1 contract address to form its L2 alias
+const ALIAS_OFFSET: [u8; 20] = [
+    0x11, 0x11, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x11, 0x11,
+];
+
+/// Summarizes a retryable ticket as an aligned table of its ID, values in
+/// ETH, gas limit, fee cap in gwei and the maximum total cost the sender
+/// can be charged, `gas_limit × max_fee_per_gas + max_submission_cost`.
+///
+/// The total is computed with checked arithmetic and shown as
+/// "overflows uint256" when it does not fit.
+pub fn format_retryable(
+    ticket_id: B256,
+    l2_call_value: U256,
+    max_submission_cost: U256,
+    gas_limit: U256,
+    max_fee_per_gas: U256,
+) -> String {
+    let max_total = gas_limit
+        .checked_mul(max_fee_per_gas)
+        .and_then(|gas_cost| gas_cost.checked_add(max_submission_cost))
+        .map_or_else(|| "overflows uint256".to_string(), format_ether_smart);
+
+    let rows = [
+        vec!["ticket".to_string(), bytes_to_hex_string(ticket_id.as_slice())],
+        vec!["l2 call value".to_string(), format_ether_smart(l2_call_value)],
+        vec!["max submission cost".to_string(), format_ether_smart(max_submission_cost)],
+        vec!["gas limit".to_string(), group_digits(&to_string(gas_limit), 3, ',')],
+        vec!["max fee per gas".to_string(), format_ether_in(max_fee_per_gas, EtherUnit::Gwei)],
+        vec!["max total cost".to_string(), max_total],
+    ];
+    render_table(&rows)
+}
+
+/// Applies Arbitrum's L1-to-L2 alias, adding
+/// 0x1111000000000000000000000000000000001111 modulo 2^160.
+pub fn l1_to_l2_alias(l1_address: Address) -> Address {
+    let mut result = [0u8; 20];
+    let mut carry = 0u16;
+    for i in (0..20).rev() {
+        let sum = l1_address[i] as u16 + ALIAS_OFFSET[i] as u16 + carry;
+        result[i] = sum as u8;
+        carry = sum >> 8;
+    }
+    Address::from(result)
+}
+
+/// Shows an L1 address with its L2 alias, both checksummed, e.g.
+/// "0x0000…0000 (L2 alias 0x1111…1111)" with full addresses.
+pub fn format_aliased(addr: Address) -> String {
+    format!(
+        "{} (L2 alias {})",
+        address_to_checksum_hex_string(addr),
+        address_to_checksum_hex_string(l1_to_l2_alias(addr))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GWEI: u64 = 1_000_000_000;
+
+    #[test]
+    fn test_alias_of_zero_is_offset() {
+        assert_eq!(l1_to_l2_alias(Address::ZERO), Address::from(ALIAS_OFFSET));
+    }
+
+    #[test]
+    fn test_alias_carries_between_bytes() {
+        let mut l1 = [0u8; 20];
+        l1[18] = 0xff;
+        l1[19] = 0xff;
+        let mut expected = ALIAS_OFFSET;
+        expected[17] = 0x01;
+        expected[18] = 0x11;
+        expected[19] = 0x10;
+        assert_eq!(l1_to_l2_alias(Address::from(l1)), Address::from(expected));
+    }
+
+    #[test]
+    fn test_alias_wraps_mod_2_160() {
+        // 2^160 - 1 wraps to one below the offset
+        let alias = l1_to_l2_alias(Address::from([0xff; 20]));
+        let mut expected = ALIAS_OFFSET;
+        expected[19] = 0x10;
+        assert_eq!(alias, Address::from(expected));
+
+        let mut top = [0u8; 20];
+        top[0] = 0xf0;
+        let mut wrapped = ALIAS_OFFSET;
+        wrapped[0] = 0x01;
+        assert_eq!(l1_to_l2_alias(Address::from(top)), Address::from(wrapped));
+    }
+
+    #[test]
+    fn test_alias_vectors() {
+        // (L1 address, L2 alias), computed as (address + offset) mod 2^160
+        let cases = [
+            ("0x5b38da6a701c568545dcfcb03fcb875f56beddc4", "0x6c49da6a701c568545dcfcb03fcb875f56beeed5"),
+            ("0xeeef000000000000000000000000000000001234", "0x0000000000000000000000000000000000002345"),
+            ("0xfedcba9876543210fedcba9876543210fedcba98", "0x0fedba9876543210fedcba9876543210fedccba9"),
+        ];
+        for (l1, l2) in cases {
+            let l1: Address = l1.parse().unwrap();
+            assert_eq!(l1_to_l2_alias(l1), l2.parse::<Address>().unwrap(), "{}", l1);
+        }
+    }
+
+    #[test]
+    fn test_format_aliased() {
+        assert_eq!(
+            format_aliased(Address::ZERO),
+            "0x0000000000000000000000000000000000000000 (L2 alias 0x1111000000000000000000000000000000001111)"
+        );
+    }
+
+    #[test]
+    fn test_format_retryable() {
+        let summary = format_retryable(
+            B256::from([0xab; 32]),
+            U256::from(10_000_000_000_000_000u64),
+            U256::from(300_000 * GWEI),
+            U256::from(100_000),
+            U256::from(GWEI / 10),
+        );
+        let id = format!("0x{}", "ab".repeat(32));
+        let expected = [
+            format!("ticket               {}", id),
+            format!("l2 call value        {:>66}", "0.01 ETH"),
+            format!("max submission cost  {:>66}", "300,000 gwei"),
+            format!("gas limit            {:>66}", "100,000"),
+            format!("max fee per gas      {:>66}", "0.1 gwei"),
+            format!("max total cost       {:>66}", "310,000 gwei"),
+        ];
+        assert_eq!(summary, expected.join("\n"));
+    }
+
+    #[test]
+    fn test_format_retryable_total_overflow() {
+        let summary = format_retryable(B256::ZERO, U256::ZERO, U256::from(1), U256::MAX, U256::from(1));
+        assert!(summary.ends_with(" overflows uint256"));
+        let summary = format_retryable(B256::ZERO, U256::ZERO, U256::ZERO, U256::MAX, U256::from(2));
+        assert!(summary.ends_with(" overflows uint256"));
+    }
+}
@@ -0,0 +1,71 @@
+//! Human-readable descriptions of Solidity `Panic(uint256)` codes.
+
+use alloy_primitives::U256;
+
+use crate::to_hex_string;
+
+/// Solidity panic codes and their descriptions
+const PANIC_CODES: [(u64, &str); 9] = [
+    (0x01, "assertion failed"),
+    (0x11, "arithmetic overflow or underflow"),
+    (0x12, "division or modulo by zero"),
+    (0x21, "invalid enum conversion"),
+    (0x22, "incorrectly encoded storage byte array"),
+    (0x31, "pop on empty array"),
+    (0x32, "array index out of bounds"),
+    (0x41, "out of memory or allocation too large"),
+    (0x51, "call to zero-initialized function pointer"),
+];
+
+/// Returns the description of a Solidity panic code, if it is a known one.
+pub fn panic_code_name(code: U256) -> Option<&'static str> {
+    PANIC_CODES
+        .iter()
+        .find(|&&(known, _)| U256::from(known) == code)
+        .map(|&(_, name)| name)
+}
+
+/// Formats a panic code as "Panic(0x11): arithmetic overflow or underflow",
+/// or just "Panic(0x…)" for unknown codes.
+pub fn format_panic(code: U256) -> String {
+    let mut result = String::from("Panic(");
+    result.push_str(&to_hex_string(code));
+    result.push(')');
+    if let Some(name) = panic_code_name(code) {
+        result.push_str(": ");
+        result.push_str(name);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_panic_table() {
+        let expected = [
+            (0x01, "Panic(0x01): assertion failed"),
+            (0x11, "Panic(0x11): arithmetic overflow or underflow"),
+            (0x12, "Panic(0x12): division or modulo by zero"),
+            (0x21, "Panic(0x21): invalid enum conversion"),
+            (0x22, "Panic(0x22): incorrectly encoded storage byte array"),
+            (0x31, "Panic(0x31): pop on empty array"),
+            (0x32, "Panic(0x32): array index out of bounds"),
+            (0x41, "Panic(0x41): out of memory or allocation too large"),
+            (0x51, "Panic(0x51): call to zero-initialized function pointer"),
+        ];
+        for (code, formatted) in expected {
+            assert!(panic_code_name(U256::from(code)).is_some());
+            assert_eq!(format_panic(U256::from(code)), formatted);
+        }
+    }
+
+    #[test]
+    fn test_unknown_panic_code() {
+        assert_eq!(panic_code_name(U256::from(0x99)), None);
+        assert_eq!(panic_code_name(U256::ZERO), None);
+        assert_eq!(format_panic(U256::from(0x99)), "Panic(0x99)");
+        assert_eq!(format_panic(U256::from(0x1234)), "Panic(0x1234)");
+    }
+}
@@ -0,0 +1,250 @@
+//! Cleaning up copy-pasted amounts such as "1,000.5 ETH" or "1 000,50 €".
+
+use crate::ParseError;
+
+/// Which character separates the integer and fractional digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalSeparator {
+    /// Inferred from the input; see `sanitize_amount_input`
+    Auto,
+    /// `.` is decimal and `,` groups digits, as in "1,000.5"
+    Dot,
+    /// `,` is decimal and `.` groups digits, as in "1.000,5"
+    Comma,
+}
+
+/// Cleans a pasted amount into plain digits with an optional `.` decimal
+/// point, e.g. "$1,000.5" → "1000.5", inferring the decimal separator.
+///
+/// Currency symbols and a leading or trailing unit or ticker such as "ETH"
+/// or "USD" are removed. Letters between digits, as in "1e18", and a single
+/// letter next to the number, as in "2.5M" or "1k", are errors rather than
+/// being dropped, since that would silently change the amount's magnitude.
+/// Spaces (including no-break spaces), `'` and `_` only ever group digits. Whether `.` or `,` is the
+/// decimal separator is decided as follows:
+///
+/// | input       | rule                                        | result    |
+/// |-------------|---------------------------------------------|-----------|
+/// | "1.000,50"  | both present: the last one is decimal       | "1000.50" |
+/// | "1,000,000" | one kind, repeated: grouping                | "1000000" |
+/// | "1,50"      | single, not followed by 3 digits: decimal   | "1.50"    |
+/// | "1,000"     | single, followed by exactly 3 digits: group | "1000"    |
+/// | "0,001"     | …unless the integer part is 0: decimal      | "0.001"   |
+///
+/// An input like "1,000" is genuinely ambiguous; use
+/// `sanitize_amount_input_with` to force a separator.
+///
+/// Grouped digits must form groups of three after the first; other
+/// characters, misplaced separators and inputs without digits are errors,
+/// with indices referring to the original input.
+pub fn sanitize_amount_input(s: &str) -> Result<String, ParseError> {
+    sanitize_amount_input_with(s, DecimalSeparator::Auto)
+}
+
+/// Like `sanitize_amount_input`, with the decimal separator given explicitly.
+pub fn sanitize_amount_input_with(s: &str, separator: DecimalSeparator) -> Result<String, ParseError> {
+    check_letter_runs(s)?;
+
+    // Keep digits and separators with their byte offsets
+    let mut chars = Vec::with_capacity(s.len());
+    for (index, c) in s.char_indices() {
+        if c.is_ascii_digit() || matches!(c, '.' | ',') || is_group_space(c) {
+            chars.push((index, c));
+        } else if !is_strippable(c) {
+            return Err(ParseError::InvalidDigit { index, found: c });
+        }
+    }
+    while chars.last().is_some_and(|&(_, c)| is_group_space(c)) {
+        chars.pop();
+    }
+    let start = chars.iter().position(|&(_, c)| !is_group_space(c)).unwrap_or(chars.len());
+    let chars = &chars[start..];
+    if !chars.iter().any(|(_, c)| c.is_ascii_digit()) {
+        return Err(ParseError::Empty);
+    }
+
+    let decimal = match separator {
+        DecimalSeparator::Dot => Some('.'),
+        DecimalSeparator::Comma => Some(','),
+        DecimalSeparator::Auto => infer_decimal(chars),
+    };
+    let point = decimal.and_then(|d| chars.iter().rposition(|&(_, c)| c == d));
+    let (integer, fraction) = match point {
+        Some(point) => (&chars[..point], &chars[point + 1..]),
+        None => (chars, &chars[..0]),
+    };
+
+    let mut result = String::with_capacity(chars.len());
+    push_grouped_integer(integer, decimal, &mut result)?;
+    if integer.is_empty() {
+        result.push('0');
+    }
+    if let Some(&(index, found)) = fraction.iter().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(ParseError::InvalidDigit { index, found });
+    }
+    if !fraction.is_empty() {
+        result.push('.');
+        result.extend(fraction.iter().map(|&(_, c)| c));
+    }
+    Ok(result)
+}
+
+/// Applies the `Auto` rules to pick the decimal separator, if any.
+fn infer_decimal(chars: &[(usize, char)]) -> Option<char> {
+    let last = chars.iter().rposition(|&(_, c)| matches!(c, '.' | ','))?;
+    let candidate = chars[last].1;
+    let other = if candidate == '.' { ',' } else { '.' };
+    if chars.iter().any(|&(_, c)| c == other) {
+        return Some(candidate);
+    }
+    if chars.iter().filter(|&&(_, c)| c == candidate).count() > 1 {
+        return None;
+    }
+    let trailing = chars[last + 1..].iter().filter(|(_, c)| c.is_ascii_digit()).count();
+    let integer_is_zero = chars[..last].iter().all(|&(_, c)| c == '0' || is_group_space(c));
+    if trailing != 3 || integer_is_zero {
+        Some(candidate)
+    } else {
+        None
+    }
+}
+
+/// Appends the integer digits, checking that grouping separators split them
+/// into groups of three after the first.
+fn push_grouped_integer(integer: &[(usize, char)], decimal: Option<char>, out: &mut String) -> Result<(), ParseError> {
+    let mut group_len = 0;
+    let mut grouped = false;
+    for (position, &(index, c)) in integer.iter().enumerate() {
+        if c.is_ascii_digit() {
+            out.push(c);
+            group_len += 1;
+            continue;
+        }
+        // A grouping separator must follow a full group and precede a digit
+        let valid_group = if grouped { group_len == 3 } else { (1..=3).contains(&group_len) };
+        let next_is_digit = integer.get(position + 1).is_some_and(|(_, n)| n.is_ascii_digit());
+        if Some(c) == decimal || !valid_group || !next_is_digit {
+            return Err(ParseError::InvalidDigit { index, found: c });
+        }
+        grouped = true;
+        group_len = 0;
+    }
+    if grouped && group_len != 3 {
+        let &(index, found) = integer.last().expect("grouped integer is not empty");
+        return Err(ParseError::InvalidDigit { index, found });
+    }
+    Ok(())
+}
+
+/// Accepts letters only as units or tickers: runs of two or more letters
+/// entirely before the first or after the last digit or separator.
+fn check_letter_runs(s: &str) -> Result<(), ParseError> {
+    let is_numeric = |c: char| c.is_ascii_digit() || matches!(c, '.' | ',');
+    let first = s.find(is_numeric).unwrap_or(s.len());
+    let last = s.rfind(is_numeric).unwrap_or(0);
+
+    let mut chars = s.char_indices().peekable();
+    while let Some((index, found)) = chars.next() {
+        if !found.is_alphabetic() {
+            continue;
+        }
+        let mut len = 1;
+        while chars.next_if(|&(_, c)| c.is_alphabetic()).is_some() {
+            len += 1;
+        }
+        if (index > first && index < last) || len < 2 {
+            return Err(ParseError::InvalidDigit { index, found });
+        }
+    }
+    Ok(())
+}
+
+/// Characters that only ever group digits
+fn is_group_space(c: char) -> bool {
+    matches!(c, ' ' | '\u{a0}' | '\u{202f}' | '\'' | '_')
+}
+
+/// Letters and currency symbols, which are dropped
+fn is_strippable(c: char) -> bool {
+    c.is_alphabetic() || matches!(c, '$' | '¢' | '£' | '¤' | '¥' | '\u{20a0}'..='\u{20cf}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clean(s: &str) -> Result<String, ParseError> {
+        sanitize_amount_input(s)
+    }
+
+    #[test]
+    fn test_us_format() {
+        assert_eq!(clean("1,000.5 ETH").unwrap(), "1000.5");
+        assert_eq!(clean("$1000").unwrap(), "1000");
+        assert_eq!(clean("$1,234,567.89").unwrap(), "1234567.89");
+        assert_eq!(clean(".5").unwrap(), "0.5");
+        assert_eq!(clean("12.").unwrap(), "12");
+    }
+
+    #[test]
+    fn test_eu_format() {
+        assert_eq!(clean("1.000,50 €").unwrap(), "1000.50");
+        assert_eq!(clean("1.234.567").unwrap(), "1234567");
+        assert_eq!(clean("EUR 12,5").unwrap(), "12.5");
+    }
+
+    #[test]
+    fn test_space_grouped_format() {
+        assert_eq!(clean("1 000,50").unwrap(), "1000.50");
+        assert_eq!(clean("1\u{a0}000\u{a0}000").unwrap(), "1000000");
+        assert_eq!(clean("1'000.25 CHF").unwrap(), "1000.25");
+        assert_eq!(clean("  42  ").unwrap(), "42");
+    }
+
+    #[test]
+    fn test_ambiguous_inputs() {
+        assert_eq!(clean("1,000").unwrap(), "1000");
+        assert_eq!(clean("1.000").unwrap(), "1000");
+        assert_eq!(clean("1,50").unwrap(), "1.50");
+        assert_eq!(clean("1.5").unwrap(), "1.5");
+        assert_eq!(clean("1,2345").unwrap(), "1.2345");
+        assert_eq!(clean("0,001").unwrap(), "0.001");
+        assert_eq!(clean("0.001 ETH").unwrap(), "0.001");
+    }
+
+    #[test]
+    fn test_forced_separator() {
+        assert_eq!(sanitize_amount_input_with("1,000", DecimalSeparator::Comma).unwrap(), "1.000");
+        assert_eq!(sanitize_amount_input_with("1.000", DecimalSeparator::Dot).unwrap(), "1.000");
+        assert_eq!(sanitize_amount_input_with("1,000", DecimalSeparator::Dot).unwrap(), "1000");
+        assert_eq!(
+            sanitize_amount_input_with("1.000.000", DecimalSeparator::Dot),
+            Err(ParseError::InvalidDigit { index: 1, found: '.' })
+        );
+    }
+
+    #[test]
+    fn test_garbage_input() {
+        assert_eq!(clean(""), Err(ParseError::Empty));
+        assert_eq!(clean("ETH"), Err(ParseError::Empty));
+        assert_eq!(clean("$ ,"), Err(ParseError::Empty));
+        assert_eq!(clean("-5"), Err(ParseError::InvalidDigit { index: 0, found: '-' }));
+        assert_eq!(clean("1+1"), Err(ParseError::InvalidDigit { index: 1, found: '+' }));
+        // Groups after the first must have three digits
+        assert_eq!(clean("1,00,000"), Err(ParseError::InvalidDigit { index: 4, found: ',' }));
+        assert_eq!(clean("1.2.3"), Err(ParseError::InvalidDigit { index: 3, found: '.' }));
+        assert_eq!(clean("1,000.5.0"), Err(ParseError::InvalidDigit { index: 5, found: '.' }));
+        assert_eq!(clean("1 0"), Err(ParseError::InvalidDigit { index: 2, found: '0' }));
+    }
+
+    #[test]
+    fn test_letters_that_change_magnitude() {
+        assert_eq!(clean("1e18"), Err(ParseError::InvalidDigit { index: 1, found: 'e' }));
+        assert_eq!(clean("2.5M"), Err(ParseError::InvalidDigit { index: 3, found: 'M' }));
+        assert_eq!(clean("1k5"), Err(ParseError::InvalidDigit { index: 1, found: 'k' }));
+        assert_eq!(clean("10 k"), Err(ParseError::InvalidDigit { index: 3, found: 'k' }));
+        assert_eq!(clean("1 ETH 000"), Err(ParseError::InvalidDigit { index: 2, found: 'E' }));
+        assert_eq!(clean("2.5ETH").unwrap(), "2.5");
+        assert_eq!(clean("USD5").unwrap(), "5");
+    }
+}
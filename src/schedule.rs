@@ -0,0 +1,167 @@
+//! Descriptions of recurring periods for subscriptions and payouts.
+
+use alloy_primitives::U256;
+
+use crate::time::{format_duration_exact, timestamp_label};
+use crate::to_string;
+
+const HOUR: u64 = 3_600;
+const DAY: u64 = 86_400;
+const WEEK: u64 = 7 * DAY;
+
+/// Periods with a name, matched by `describe_period` in order.
+///
+/// | period   | length   | tolerance | description         |
+/// |----------|----------|-----------|---------------------|
+/// | hourly   | 1 hour   | exact     | "hourly"            |
+/// | daily    | 1 day    | exact     | "daily"             |
+/// | weekly   | 7 days   | exact     | "weekly"            |
+/// | biweekly | 14 days  | exact     | "every 2 weeks"     |
+/// | monthly  | 30 days  | ±1 day    | "monthly (30 days)" |
+/// | yearly   | 365 days | ±1 day    | "yearly (365 days)" |
+///
+/// Calendar months and years vary in length, so those two match a range
+/// and state the actual length in parentheses.
+const NAMED_PERIODS: [(u64, u64, &str); 6] = [
+    (HOUR, 0, "hourly"),
+    (DAY, 0, "daily"),
+    (WEEK, 0, "weekly"),
+    (2 * WEEK, 0, "every 2 weeks"),
+    (30 * DAY, DAY, "monthly"),
+    (365 * DAY, DAY, "yearly"),
+];
+
+/// Describes a recurring period given in seconds.
+///
+/// Periods in the `NAMED_PERIODS` table get their name, e.g. "weekly" or
+/// "monthly (31 days)". Any other whole number of weeks, days or hours is
+/// "every N weeks", "every N days" or "every N hours", checked in that
+/// order, and anything else is "every " followed by every non-zero unit,
+/// e.g. "every 1h 30m" or "every 7d 1s", so an irregular period is never
+/// shown as a regular one. A zero period is "never".
+pub fn describe_period(seconds: U256) -> String {
+    if seconds.is_zero() {
+        return "never".to_string();
+    }
+    let named = NAMED_PERIODS.iter().find(|&&(length, tolerance, _)| {
+        seconds >= U256::from(length - tolerance) && seconds <= U256::from(length + tolerance)
+    });
+    if let Some(&(_, tolerance, name)) = named {
+        if tolerance == 0 {
+            return name.to_string();
+        }
+        let length = whole_units(seconds, DAY, "days").unwrap_or_else(|| format_duration_exact(seconds));
+        return format!("{} ({})", name, length);
+    }
+    [(WEEK, "weeks"), (DAY, "days"), (HOUR, "hours")]
+        .iter()
+        .find_map(|&(length, unit)| whole_units(seconds, length, unit))
+        .map(|units| format!("every {}", units))
+        .unwrap_or_else(|| format!("every {}", format_duration_exact(seconds)))
+}
+
+/// Formats the first occurrence of a recurring schedule strictly after
+/// `now` as ISO-8601, or "timestamp N" past year 9999.
+///
+/// Occurrences are at `start + k * period` for k ≥ 0, so before `start` the
+/// next one is `start` itself, and at an exact occurrence it is the one
+/// after. Returns "never" if there is none: a zero period whose only
+/// occurrence has passed, or a next occurrence beyond `U256::MAX`.
+pub fn next_occurrence_string(start: U256, period: U256, now: U256) -> String {
+    if now < start {
+        return timestamp_label(start);
+    }
+    if period.is_zero() {
+        return "never".to_string();
+    }
+    // Smallest k with start + k * period > now is ceil((now - start + 1) / period),
+    // which for a non-negative numerator is floor((now - start) / period) + 1
+    let next = ((now - start) / period)
+        .checked_add(U256::from(1))
+        .and_then(|k| k.checked_mul(period))
+        .and_then(|offset| start.checked_add(offset));
+    match next {
+        Some(next) => timestamp_label(next),
+        None => "never".to_string(),
+    }
+}
+
+/// "N <unit>" if `seconds` is a whole number, at least 2, of `length`
+fn whole_units(seconds: U256, length: u64, unit: &str) -> Option<String> {
+    let length = U256::from(length);
+    (seconds % length == U256::ZERO && seconds >= length * U256::from(2))
+        .then(|| format!("{} {}", to_string(seconds / length), unit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(value: u64) -> U256 {
+        U256::from(value)
+    }
+
+    #[test]
+    fn test_named_periods() {
+        assert_eq!(describe_period(secs(3_600)), "hourly");
+        assert_eq!(describe_period(secs(86_400)), "daily");
+        assert_eq!(describe_period(secs(604_800)), "weekly");
+        assert_eq!(describe_period(secs(1_209_600)), "every 2 weeks");
+        assert_eq!(describe_period(secs(2_592_000)), "monthly (30 days)");
+        assert_eq!(describe_period(secs(31_536_000)), "yearly (365 days)");
+        assert_eq!(describe_period(secs(31_622_400)), "yearly (366 days)");
+    }
+
+    #[test]
+    fn test_tolerance_edges() {
+        assert_eq!(describe_period(secs(29 * DAY)), "monthly (29 days)");
+        assert_eq!(describe_period(secs(31 * DAY)), "monthly (31 days)");
+        assert_eq!(describe_period(secs(30 * DAY + 43_200)), "monthly (30d 12h)");
+        assert_eq!(describe_period(secs(30 * DAY + 43_201)), "monthly (30d 12h 1s)");
+        assert_eq!(describe_period(secs(29 * DAY - 1)), "every 28d 23h 59m 59s");
+        assert_eq!(describe_period(secs(31 * DAY + 1)), "every 31d 1s");
+        assert_eq!(describe_period(secs(32 * DAY)), "every 32 days");
+        // Exact names have no tolerance
+        assert_eq!(describe_period(secs(WEEK + 1)), "every 7d 1s");
+        assert_eq!(describe_period(secs(DAY - 1)), "every 23h 59m 59s");
+    }
+
+    #[test]
+    fn test_irregular_periods() {
+        assert_eq!(describe_period(secs(90 * DAY)), "every 90 days");
+        assert_eq!(describe_period(secs(4 * WEEK)), "every 4 weeks");
+        assert_eq!(describe_period(secs(6 * HOUR)), "every 6 hours");
+        assert_eq!(describe_period(secs(5_400)), "every 1h 30m");
+        assert_eq!(describe_period(secs(45)), "every 45s");
+        assert_eq!(describe_period(U256::ZERO), "never");
+        assert!(describe_period(U256::MAX).starts_with("every "));
+    }
+
+    #[test]
+    fn test_next_occurrence() {
+        let start = secs(1_717_200_000);
+        // Before the start, the start itself
+        assert_eq!(next_occurrence_string(start, secs(DAY), secs(0)), "2024-06-01T00:00:00Z");
+        assert_eq!(next_occurrence_string(start, secs(DAY), start - secs(1)), "2024-06-01T00:00:00Z");
+        // At an occurrence, the one after
+        assert_eq!(next_occurrence_string(start, secs(DAY), start), "2024-06-02T00:00:00Z");
+        assert_eq!(next_occurrence_string(start, secs(DAY), start + secs(DAY)), "2024-06-03T00:00:00Z");
+        // Between occurrences, the next one
+        assert_eq!(next_occurrence_string(start, secs(DAY), start + secs(DAY - 1)), "2024-06-02T00:00:00Z");
+        assert_eq!(next_occurrence_string(start, secs(DAY), start + secs(DAY + 1)), "2024-06-03T00:00:00Z");
+        assert_eq!(next_occurrence_string(start, secs(30 * DAY), start + secs(45 * DAY)), "2024-07-31T00:00:00Z");
+    }
+
+    #[test]
+    fn test_next_occurrence_degenerate() {
+        assert_eq!(next_occurrence_string(secs(100), U256::ZERO, secs(50)), "1970-01-01T00:01:40Z");
+        assert_eq!(next_occurrence_string(secs(100), U256::ZERO, secs(100)), "never");
+        assert_eq!(
+            next_occurrence_string(U256::ZERO, U256::MAX, U256::MAX - secs(1)),
+            format!("timestamp {}", to_string(U256::MAX))
+        );
+        assert_eq!(next_occurrence_string(secs(1), U256::MAX, secs(1)), "never");
+        assert_eq!(next_occurrence_string(U256::ZERO, U256::MAX, U256::MAX), "never");
+        assert_eq!(next_occurrence_string(U256::ZERO, secs(1), U256::MAX), "never");
+    }
+}
@@ -0,0 +1,139 @@
+//! Substring search and replacement with explicit edge-case policies.
+//!
+//! Indices are byte offsets, as in `str::find`. An empty needle is an
+//! error rather than matching everywhere, since that is almost always a
+//! bug in the caller.
+
+/// Error types for substring search and replacement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchError {
+    /// Needle was empty
+    EmptyNeedle,
+    /// Start offset is inside a multibyte character
+    NotCharBoundary(usize),
+    /// More than the allowed number of replacements would be made
+    TooManyReplacements { max: usize },
+}
+
+/// Returns the byte index of the first `needle` at or after `from`.
+///
+/// `from` must lie on a character boundary; past the end there is no match.
+pub fn index_of(haystack: &str, needle: &str, from: usize) -> Result<Option<usize>, SearchError> {
+    check_needle(needle)?;
+    if from > haystack.len() {
+        return Ok(None);
+    }
+    if !haystack.is_char_boundary(from) {
+        return Err(SearchError::NotCharBoundary(from));
+    }
+    Ok(haystack[from..].find(needle).map(|index| from + index))
+}
+
+/// Returns the byte index of the last `needle` in `haystack`.
+pub fn last_index_of(haystack: &str, needle: &str) -> Result<Option<usize>, SearchError> {
+    check_needle(needle)?;
+    Ok(haystack.rfind(needle))
+}
+
+/// Counts non-overlapping occurrences of `needle`, scanning left to right,
+/// so "aaa" contains "aa" once.
+pub fn count_occurrences(haystack: &str, needle: &str) -> Result<usize, SearchError> {
+    check_needle(needle)?;
+    Ok(haystack.matches(needle).count())
+}
+
+/// Replaces the first occurrence of `needle`, if any.
+pub fn replace_first(haystack: &str, needle: &str, replacement: &str) -> Result<String, SearchError> {
+    check_needle(needle)?;
+    Ok(haystack.replacen(needle, replacement, 1))
+}
+
+/// Replaces every non-overlapping occurrence of `needle`.
+///
+/// To bound the work done on untrusted input, finding more than
+/// `max_replacements` occurrences is an error; scanning stops at the first
+/// occurrence past the limit.
+pub fn replace_all(
+    haystack: &str,
+    needle: &str,
+    replacement: &str,
+    max_replacements: usize,
+) -> Result<String, SearchError> {
+    check_needle(needle)?;
+    let mut result = String::with_capacity(haystack.len());
+    let mut rest = haystack;
+    let mut replaced = 0;
+    while let Some(index) = rest.find(needle) {
+        if replaced == max_replacements {
+            return Err(SearchError::TooManyReplacements { max: max_replacements });
+        }
+        result.push_str(&rest[..index]);
+        result.push_str(replacement);
+        rest = &rest[index + needle.len()..];
+        replaced += 1;
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn check_needle(needle: &str) -> Result<(), SearchError> {
+    if needle.is_empty() {
+        Err(SearchError::EmptyNeedle)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_needle_is_error() {
+        assert_eq!(index_of("abc", "", 0), Err(SearchError::EmptyNeedle));
+        assert_eq!(last_index_of("abc", ""), Err(SearchError::EmptyNeedle));
+        assert_eq!(count_occurrences("abc", ""), Err(SearchError::EmptyNeedle));
+        assert_eq!(replace_first("abc", "", "x"), Err(SearchError::EmptyNeedle));
+        assert_eq!(replace_all("abc", "", "x", 10), Err(SearchError::EmptyNeedle));
+    }
+
+    #[test]
+    fn test_needle_at_start_and_end() {
+        assert_eq!(index_of("abcab", "ab", 0), Ok(Some(0)));
+        assert_eq!(index_of("abcab", "ab", 1), Ok(Some(3)));
+        assert_eq!(index_of("abcab", "ab", 4), Ok(None));
+        assert_eq!(index_of("abcab", "ab", 5), Ok(None));
+        assert_eq!(index_of("abcab", "ab", 99), Ok(None));
+        assert_eq!(last_index_of("abcab", "ab"), Ok(Some(3)));
+        assert_eq!(last_index_of("abc", "x"), Ok(None));
+    }
+
+    #[test]
+    fn test_overlapping_patterns() {
+        assert_eq!(count_occurrences("aaa", "aa"), Ok(1));
+        assert_eq!(count_occurrences("aaaa", "aa"), Ok(2));
+        assert_eq!(replace_all("aaa", "aa", "b", 10).unwrap(), "ba");
+        assert_eq!(last_index_of("aaa", "aa"), Ok(Some(1)));
+    }
+
+    #[test]
+    fn test_multibyte() {
+        let s = "héllo wörld";
+        assert_eq!(index_of(s, "ö", 0), Ok(Some(8)));
+        assert_eq!(index_of(s, "l", 2), Err(SearchError::NotCharBoundary(2)));
+        assert_eq!(index_of(s, "l", 3), Ok(Some(3)));
+        assert_eq!(count_occurrences("€€€", "€"), Ok(3));
+        assert_eq!(replace_first(s, "é", "e").unwrap(), "hello wörld");
+    }
+
+    #[test]
+    fn test_replace() {
+        assert_eq!(replace_first("{id}/{id}", "{id}", "42").unwrap(), "42/{id}");
+        assert_eq!(replace_all("{id}/{id}", "{id}", "42", 2).unwrap(), "42/42");
+        assert_eq!(replace_all("none", "{id}", "42", 0).unwrap(), "none");
+        assert_eq!(
+            replace_all("{id}/{id}/{id}", "{id}", "42", 2),
+            Err(SearchError::TooManyReplacements { max: 2 })
+        );
+    }
+}
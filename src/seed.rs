@@ -0,0 +1,185 @@
+//! Deterministic trait selection from a random seed, e.g.
+//! `keccak256(abi.encodePacked(tokenId, salt))`.
+//!
+//! The same seed always selects the same traits, so metadata can be
+//! recomputed off chain. Different slots read different seed bytes, giving
+//! each trait category an independent choice.
+
+use alloy_primitives::{keccak256, B256};
+
+/// Returns the option chosen by seed byte `slot`, i.e.
+/// `options[seed[slot] % options.len()]`.
+///
+/// Unless the option count divides 256 this has modulo bias: with 3
+/// options, bytes 0–254 are spread evenly but 255 adds one more vote for
+/// the first option, so it is picked with probability 86/256 against 85/256
+/// for the others. The bias grows with the count: with 129 options most
+/// are twice as likely as the last two. Use `trait_from_seed_unbiased`
+/// when that matters.
+///
+/// Panics if `options` is empty or has more than 256 entries, or if `slot`
+/// is not below 32.
+pub fn trait_from_seed<'a>(seed: B256, slot: u8, options: &[&'a str]) -> &'a str {
+    check_options(options.len());
+    options[seed_byte(seed, slot) as usize % options.len()]
+}
+
+/// Returns an option chosen uniformly by rejection sampling, starting at
+/// seed byte `slot`.
+///
+/// A byte is accepted only if it is below the largest multiple of the
+/// option count that fits in a byte; otherwise the next byte is tried.
+/// After the last seed byte, bytes continue from `keccak256(seed)`, then
+/// from the hash of that, and so on. Each byte is rejected with probability
+/// below one half, so more than a few extra bytes are rarely needed.
+///
+/// Panics like `trait_from_seed`.
+pub fn trait_from_seed_unbiased<'a>(seed: B256, slot: u8, options: &[&'a str]) -> &'a str {
+    check_options(options.len());
+    let count = options.len();
+    let limit = 256 - 256 % count;
+    let mut block = seed;
+    let mut index = seed_byte_index(slot);
+    loop {
+        for &byte in &block[index..] {
+            if (byte as usize) < limit {
+                return options[byte as usize % count];
+            }
+        }
+        block = keccak256(block);
+        index = 0;
+    }
+}
+
+/// Returns an option chosen with probability proportional to its weight,
+/// reading seed bytes `slot..slot + 4` as a big-endian `u32` roll.
+///
+/// The roll modulo the total weight picks the first option whose
+/// cumulative weight exceeds it, so zero-weight options are never chosen.
+/// The modulo bias is at most one part in `2^32 / total`, below 0.01% for
+/// any total under 400,000.
+///
+/// Panics if the total weight is zero or `slot` is above 28.
+pub fn weighted_trait_from_seed<'a>(seed: B256, slot: u8, options: &[(&'a str, u16)]) -> &'a str {
+    let start = slot as usize;
+    assert!(start + 4 <= 32, "slot must be at most 28");
+    let total: u64 = options.iter().map(|&(_, weight)| u64::from(weight)).sum();
+    assert!(total > 0, "total weight must be non-zero");
+
+    let bytes: [u8; 4] = seed[start..start + 4].try_into().expect("four bytes");
+    let roll = u64::from(u32::from_be_bytes(bytes)) % total;
+    let mut cumulative = 0;
+    for &(option, weight) in options {
+        cumulative += u64::from(weight);
+        if roll < cumulative {
+            return option;
+        }
+    }
+    unreachable!("roll is below the total weight")
+}
+
+fn check_options(count: usize) {
+    assert!((1..=256).contains(&count), "need between 1 and 256 options");
+}
+
+fn seed_byte_index(slot: u8) -> usize {
+    assert!(slot < 32, "slot must be below 32");
+    slot as usize
+}
+
+fn seed_byte(seed: B256, slot: u8) -> u8 {
+    seed[seed_byte_index(slot)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BACKGROUNDS: [&str; 3] = ["Red", "Green", "Blue"];
+
+    fn seed(bytes: &[u8]) -> B256 {
+        let mut seed = [0u8; 32];
+        seed[..bytes.len()].copy_from_slice(bytes);
+        B256::from(seed)
+    }
+
+    #[test]
+    fn test_pinned_seeds() {
+        let s = keccak256(b"token 1");
+        assert_eq!(trait_from_seed(s, 0, &BACKGROUNDS), BACKGROUNDS[s[0] as usize % 3]);
+        assert_eq!(trait_from_seed(seed(&[7, 8]), 0, &BACKGROUNDS), "Green");
+        assert_eq!(trait_from_seed(seed(&[7, 8]), 1, &BACKGROUNDS), "Blue");
+        assert_eq!(trait_from_seed(seed(&[255]), 0, &BACKGROUNDS), "Red");
+        // Deterministic across calls
+        for slot in 0..32 {
+            assert_eq!(trait_from_seed(s, slot, &BACKGROUNDS), trait_from_seed(s, slot, &BACKGROUNDS));
+        }
+    }
+
+    #[test]
+    fn test_unbiased_rejects_overflow_bytes() {
+        // 255 is at or above 255 (= 256 - 256 % 3), so the next byte decides
+        assert_eq!(trait_from_seed_unbiased(seed(&[255, 4]), 0, &BACKGROUNDS), "Green");
+        assert_eq!(trait_from_seed_unbiased(seed(&[254]), 0, &BACKGROUNDS), "Blue");
+        // All remaining seed bytes rejected: continue into keccak256(seed)
+        let all_high = B256::from([255u8; 32]);
+        let next = keccak256(all_high);
+        let first = next.iter().find(|&&b| b < 255).unwrap();
+        assert_eq!(trait_from_seed_unbiased(all_high, 31, &BACKGROUNDS), BACKGROUNDS[*first as usize % 3]);
+        // Counts dividing 256 never reject
+        assert_eq!(trait_from_seed_unbiased(seed(&[255]), 0, &["a", "b"]), "b");
+    }
+
+    #[test]
+    fn test_single_option() {
+        assert_eq!(trait_from_seed(keccak256(b"x"), 5, &["Only"]), "Only");
+        assert_eq!(trait_from_seed_unbiased(B256::from([255u8; 32]), 0, &["Only"]), "Only");
+        assert_eq!(weighted_trait_from_seed(keccak256(b"x"), 28, &[("Only", 1)]), "Only");
+    }
+
+    #[test]
+    fn test_weight_boundaries() {
+        let options = [("Common", 70), ("Rare", 25), ("None", 0), ("Legendary", 5)];
+        let roll = |r: u32| seed(&r.to_be_bytes());
+        assert_eq!(weighted_trait_from_seed(roll(0), 0, &options), "Common");
+        assert_eq!(weighted_trait_from_seed(roll(69), 0, &options), "Common");
+        assert_eq!(weighted_trait_from_seed(roll(70), 0, &options), "Rare");
+        assert_eq!(weighted_trait_from_seed(roll(94), 0, &options), "Rare");
+        assert_eq!(weighted_trait_from_seed(roll(95), 0, &options), "Legendary");
+        assert_eq!(weighted_trait_from_seed(roll(99), 0, &options), "Legendary");
+        assert_eq!(weighted_trait_from_seed(roll(100), 0, &options), "Common");
+        // Slot selects which four bytes are read
+        let shifted = seed(&[0, 0, 0, 0, 0, 0, 0, 95]);
+        assert_eq!(weighted_trait_from_seed(shifted, 4, &options), "Legendary");
+    }
+
+    #[test]
+    #[should_panic(expected = "total weight must be non-zero")]
+    fn test_zero_total_weight() {
+        weighted_trait_from_seed(B256::ZERO, 0, &[("a", 0), ("b", 0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "need between 1 and 256 options")]
+    fn test_no_options() {
+        trait_from_seed(B256::ZERO, 0, &[]);
+    }
+
+    #[test]
+    fn test_unbiased_distribution() {
+        // With 129 options, byte-modulo selection makes options 0..=126 twice
+        // as likely as 127 and 128; rejection sampling evens them out
+        let options: Vec<String> = (0..129).map(|i| i.to_string()).collect();
+        let options: Vec<&str> = options.iter().map(String::as_str).collect();
+        let mut biased = [0u32; 129];
+        let mut unbiased = [0u32; 129];
+        for i in 0u32..64_500 {
+            let s = keccak256(i.to_be_bytes());
+            biased[trait_from_seed(s, 0, &options).parse::<usize>().unwrap()] += 1;
+            unbiased[trait_from_seed_unbiased(s, 0, &options).parse::<usize>().unwrap()] += 1;
+        }
+        // Expected 500 per option when uniform
+        assert!(unbiased.iter().all(|&n| (400..600).contains(&n)), "{:?}", unbiased);
+        assert!(biased[0] > 400 && biased[128] < 350, "{} {}", biased[0], biased[128]);
+    }
+}
@@ -0,0 +1,159 @@
+//! Function and error selector lookup for decoding calls and reverts.
+
+use alloy_primitives::keccak256;
+
+use crate::bytes_to_hex_string;
+
+/// Selectors of common ERC-20, ERC-721, ERC-1155, access control and error
+/// signatures
+pub const COMMON_SELECTORS: &[([u8; 4], &str)] = &[
+    ([0xa9, 0x05, 0x9c, 0xbb], "transfer(address,uint256)"),
+    ([0x23, 0xb8, 0x72, 0xdd], "transferFrom(address,address,uint256)"),
+    ([0x09, 0x5e, 0xa7, 0xb3], "approve(address,uint256)"),
+    ([0x70, 0xa0, 0x82, 0x31], "balanceOf(address)"),
+    ([0xdd, 0x62, 0xed, 0x3e], "allowance(address,address)"),
+    ([0x18, 0x16, 0x0d, 0xdd], "totalSupply()"),
+    ([0x06, 0xfd, 0xde, 0x03], "name()"),
+    ([0x95, 0xd8, 0x9b, 0x41], "symbol()"),
+    ([0x31, 0x3c, 0xe5, 0x67], "decimals()"),
+    ([0x39, 0x50, 0x93, 0x51], "increaseAllowance(address,uint256)"),
+    ([0xa4, 0x57, 0xc2, 0xd7], "decreaseAllowance(address,uint256)"),
+    ([0xd5, 0x05, 0xac, 0xcf], "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)"),
+    ([0x7e, 0xce, 0xbe, 0x00], "nonces(address)"),
+    ([0x36, 0x44, 0xe5, 0x15], "DOMAIN_SEPARATOR()"),
+    ([0x63, 0x52, 0x21, 0x1e], "ownerOf(uint256)"),
+    ([0x42, 0x84, 0x2e, 0x0e], "safeTransferFrom(address,address,uint256)"),
+    ([0xb8, 0x8d, 0x4f, 0xde], "safeTransferFrom(address,address,uint256,bytes)"),
+    ([0xa2, 0x2c, 0xb4, 0x65], "setApprovalForAll(address,bool)"),
+    ([0x08, 0x18, 0x12, 0xfc], "getApproved(uint256)"),
+    ([0xe9, 0x85, 0xe9, 0xc5], "isApprovedForAll(address,address)"),
+    ([0xc8, 0x7b, 0x56, 0xdd], "tokenURI(uint256)"),
+    ([0x01, 0xff, 0xc9, 0xa7], "supportsInterface(bytes4)"),
+    ([0x15, 0x0b, 0x7a, 0x02], "onERC721Received(address,address,uint256,bytes)"),
+    ([0x00, 0xfd, 0xd5, 0x8e], "balanceOf(address,uint256)"),
+    ([0x4e, 0x12, 0x73, 0xf4], "balanceOfBatch(address[],uint256[])"),
+    ([0xf2, 0x42, 0x43, 0x2a], "safeTransferFrom(address,address,uint256,uint256,bytes)"),
+    ([0x2e, 0xb2, 0xc2, 0xd6], "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)"),
+    ([0x0e, 0x89, 0x34, 0x1c], "uri(uint256)"),
+    ([0xf2, 0x3a, 0x6e, 0x61], "onERC1155Received(address,address,uint256,uint256,bytes)"),
+    ([0xbc, 0x19, 0x7c, 0x81], "onERC1155BatchReceived(address,address,uint256[],uint256[],bytes)"),
+    ([0x8d, 0xa5, 0xcb, 0x5b], "owner()"),
+    ([0xf2, 0xfd, 0xe3, 0x8b], "transferOwnership(address)"),
+    ([0x71, 0x50, 0x18, 0xa6], "renounceOwnership()"),
+    ([0x91, 0xd1, 0x48, 0x54], "hasRole(bytes32,address)"),
+    ([0x2f, 0x2f, 0xf1, 0x5d], "grantRole(bytes32,address)"),
+    ([0xd5, 0x47, 0x74, 0x1f], "revokeRole(bytes32,address)"),
+    ([0x36, 0x56, 0x8a, 0xbe], "renounceRole(bytes32,address)"),
+    ([0x24, 0x8a, 0x9c, 0xa3], "getRoleAdmin(bytes32)"),
+    ([0x40, 0xc1, 0x0f, 0x19], "mint(address,uint256)"),
+    ([0x42, 0x96, 0x6c, 0x68], "burn(uint256)"),
+    ([0x9d, 0xc2, 0x9f, 0xac], "burn(address,uint256)"),
+    ([0x84, 0x56, 0xcb, 0x59], "pause()"),
+    ([0x3f, 0x4b, 0xa8, 0x3a], "unpause()"),
+    ([0x5c, 0x97, 0x5a, 0xbb], "paused()"),
+    ([0xac, 0x96, 0x50, 0xd8], "multicall(bytes[])"),
+    ([0x08, 0xc3, 0x79, 0xa0], "Error(string)"),
+    ([0x4e, 0x48, 0x7b, 0x71], "Panic(uint256)"),
+    ([0xf4, 0xd6, 0x78, 0xb8], "InsufficientBalance()"),
+    ([0x82, 0xb4, 0x29, 0x00], "Unauthorized()"),
+    ([0xd9, 0x2e, 0x23, 0x3d], "ZeroAddress()"),
+    ([0x11, 0x8c, 0xda, 0xa7], "OwnableUnauthorizedAccount(address)"),
+    ([0x1e, 0x4f, 0xbd, 0xf7], "OwnableInvalidOwner(address)"),
+    ([0xe4, 0x50, 0xd3, 0x8c], "ERC20InsufficientBalance(address,uint256,uint256)"),
+    ([0xfb, 0x8f, 0x41, 0xb2], "ERC20InsufficientAllowance(address,uint256,uint256)"),
+    ([0x96, 0xc6, 0xfd, 0x1e], "ERC20InvalidSender(address)"),
+    ([0xec, 0x44, 0x2f, 0x05], "ERC20InvalidReceiver(address)"),
+    ([0x7e, 0x27, 0x32, 0x89], "ERC721NonexistentToken(uint256)"),
+    ([0x64, 0x28, 0x3d, 0x7b], "ERC721IncorrectOwner(address,uint256,address)"),
+    ([0x17, 0x7e, 0x80, 0x2f], "ERC721InsufficientApproval(address,uint256)"),
+    ([0x03, 0xde, 0xe4, 0xc5], "ERC1155InsufficientBalance(address,uint256,uint256,uint256)"),
+    ([0xe2, 0x51, 0x7d, 0x3f], "AccessControlUnauthorizedAccount(address,bytes32)"),
+    ([0xd9, 0x3c, 0x06, 0x65], "EnforcedPause()"),
+    ([0x8d, 0xfc, 0x20, 0x2b], "ExpectedPause()"),
+    ([0x3e, 0xe5, 0xae, 0xb5], "ReentrancyGuardReentrantCall()"),
+];
+
+/// Computes the 4-byte selector of a canonical signature such as
+/// "transfer(address,uint256)".
+pub fn compute_selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Looks up the signature of a selector, searching `registry` before
+/// `COMMON_SELECTORS`.
+pub fn selector_signature<'a>(sel: [u8; 4], registry: &[([u8; 4], &'a str)]) -> Option<&'a str> {
+    registry
+        .iter()
+        .chain(COMMON_SELECTORS)
+        .find(|(known, _)| *known == sel)
+        .map(|&(_, signature)| signature)
+}
+
+/// Formats a selector as "transfer(address,uint256) [0xa9059cbb]" when its
+/// signature is known, or as bare hex otherwise.
+///
+/// `registry` is searched before `COMMON_SELECTORS`, so user entries shadow
+/// the built-in table.
+pub fn format_selector(sel: [u8; 4], registry: &[([u8; 4], &str)]) -> String {
+    let hex = bytes_to_hex_string(&sel);
+    match selector_signature(sel, registry) {
+        Some(signature) => {
+            let mut result = String::with_capacity(signature.len() + hex.len() + 3);
+            result.push_str(signature);
+            result.push_str(" [");
+            result.push_str(&hex);
+            result.push(']');
+            result
+        }
+        None => hex,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_selector() {
+        assert_eq!(compute_selector("transfer(address,uint256)"), [0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(compute_selector("Error(string)"), [0x08, 0xc3, 0x79, 0xa0]);
+    }
+
+    #[test]
+    fn test_builtin_table_is_consistent() {
+        for (selector, signature) in COMMON_SELECTORS {
+            assert_eq!(compute_selector(signature), *selector, "{}", signature);
+        }
+    }
+
+    #[test]
+    fn test_format_known_selector() {
+        assert_eq!(
+            format_selector([0xa9, 0x05, 0x9c, 0xbb], &[]),
+            "transfer(address,uint256) [0xa9059cbb]"
+        );
+        let sel = compute_selector("Unauthorized()");
+        assert!(format_selector(sel, &[]).starts_with("Unauthorized() [0x"));
+    }
+
+    #[test]
+    fn test_user_registry_shadows_builtin() {
+        let registry = [([0xa9, 0x05, 0x9c, 0xbb], "myTransfer(address,uint256)")];
+        assert_eq!(
+            format_selector([0xa9, 0x05, 0x9c, 0xbb], &registry),
+            "myTransfer(address,uint256) [0xa9059cbb]"
+        );
+        let custom = compute_selector("claim(uint256)");
+        assert_eq!(
+            format_selector(custom, &[(custom, "claim(uint256)")]),
+            format!("claim(uint256) [{}]", format_selector(custom, &[]))
+        );
+    }
+
+    #[test]
+    fn test_unknown_selector() {
+        assert_eq!(selector_signature([0xde, 0xad, 0xbe, 0xef], &[]), None);
+        assert_eq!(format_selector([0xde, 0xad, 0xbe, 0xef], &[]), "0xdeadbeef");
+    }
+}
@@ -0,0 +1,119 @@
+//! Semantic versions packed as `major << 32 | minor << 16 | patch`.
+
+use core::cmp::Ordering;
+
+use alloy_primitives::U256;
+
+use crate::parse::parse_digits;
+use crate::{to_string, ParseError};
+
+/// Largest value of a single version component
+const COMPONENT_MAX: u64 = 0xffff;
+
+/// Formats a packed version as "major.minor.patch", e.g. "2.5.13".
+pub fn format_semver(packed: U256) -> String {
+    let (major, minor, patch) = unpack(packed);
+    format!("{}.{}.{}", to_string(major), minor, patch)
+}
+
+/// Parses "major.minor.patch", with an optional leading `v`, into a packed
+/// version.
+///
+/// Parsing is strict: exactly three decimal components, each below 2^16
+/// and without leading zeros. Pre-release and build suffixes such as
+/// "-rc.1" or "+build" are rejected at their first character.
+pub fn parse_semver(s: &str) -> Result<U256, ParseError> {
+    let offset = usize::from(s.starts_with('v'));
+    let version = &s[offset..];
+    if let Some(index) = version.find(['-', '+']) {
+        let found = version[index..].chars().next().unwrap_or_default();
+        return Err(ParseError::InvalidDigit { index: offset + index, found });
+    }
+
+    let found = version.split('.').count();
+    if found != 3 {
+        return Err(ParseError::WrongSegmentCount { expected: 3, found });
+    }
+    let mut packed = U256::ZERO;
+    let mut start = offset;
+    for component in version.split('.') {
+        if component.len() > 1 && component.starts_with('0') {
+            return Err(ParseError::InvalidDigit { index: start, found: '0' });
+        }
+        let value = parse_digits(component, start, 10)?;
+        if value > U256::from(COMPONENT_MAX) {
+            return Err(ParseError::Overflow);
+        }
+        packed = (packed << 16usize) | value;
+        start += component.len() + 1;
+    }
+    Ok(packed)
+}
+
+/// Compares two packed versions by major, then minor, then patch.
+pub fn compare_semver(a: U256, b: U256) -> Ordering {
+    unpack(a).cmp(&unpack(b))
+}
+
+fn unpack(packed: U256) -> (U256, u64, u64) {
+    let mask = U256::from(COMPONENT_MAX);
+    let minor = ((packed >> 16usize) & mask).to::<u64>();
+    let patch = (packed & mask).to::<u64>();
+    (packed >> 32usize, minor, patch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pack(major: u64, minor: u64, patch: u64) -> U256 {
+        U256::from(major << 32 | minor << 16 | patch)
+    }
+
+    #[test]
+    fn test_format_semver() {
+        assert_eq!(format_semver(pack(2, 5, 13)), "2.5.13");
+        assert_eq!(format_semver(U256::ZERO), "0.0.0");
+        assert_eq!(format_semver(pack(1, 0xffff, 0xffff)), "1.65535.65535");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for version in ["0.0.0", "1.2.3", "2.5.13", "65535.65535.65535", "10.0.1"] {
+            assert_eq!(format_semver(parse_semver(version).unwrap()), version);
+        }
+        assert_eq!(parse_semver("2.5.13").unwrap(), pack(2, 5, 13));
+    }
+
+    #[test]
+    fn test_leading_v() {
+        assert_eq!(parse_semver("v1.2.3").unwrap(), pack(1, 2, 3));
+        assert_eq!(parse_semver("vv1.2.3"), Err(ParseError::InvalidDigit { index: 1, found: 'v' }));
+        assert_eq!(parse_semver("V1.2.3"), Err(ParseError::InvalidDigit { index: 0, found: 'V' }));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_semver("1.2"), Err(ParseError::WrongSegmentCount { expected: 3, found: 2 }));
+        assert_eq!(parse_semver("1.2.3.4"), Err(ParseError::WrongSegmentCount { expected: 3, found: 4 }));
+        assert_eq!(parse_semver("1.65536.0"), Err(ParseError::Overflow));
+        assert_eq!(parse_semver("1.02.3"), Err(ParseError::InvalidDigit { index: 2, found: '0' }));
+        assert_eq!(parse_semver("1..3"), Err(ParseError::Empty));
+        assert_eq!(parse_semver("1.2.x"), Err(ParseError::InvalidDigit { index: 4, found: 'x' }));
+    }
+
+    #[test]
+    fn test_prerelease_rejected() {
+        assert_eq!(parse_semver("1.2.3-rc.1"), Err(ParseError::InvalidDigit { index: 5, found: '-' }));
+        assert_eq!(parse_semver("v1.2.3+build"), Err(ParseError::InvalidDigit { index: 6, found: '+' }));
+    }
+
+    #[test]
+    fn test_compare_semver() {
+        assert_eq!(compare_semver(pack(1, 2, 3), pack(1, 2, 3)), Ordering::Equal);
+        assert_eq!(compare_semver(pack(1, 2, 3), pack(1, 2, 4)), Ordering::Less);
+        assert_eq!(compare_semver(pack(1, 3, 0), pack(1, 2, 9)), Ordering::Greater);
+        assert_eq!(compare_semver(pack(2, 0, 0), pack(1, 65535, 65535)), Ordering::Greater);
+        assert_eq!(compare_semver(pack(0, 9, 9), pack(1, 0, 0)), Ordering::Less);
+    }
+}
@@ -0,0 +1,279 @@
+//! Summaries, percentiles and histograms of batches of fixed-point values.
+
+use alloy_primitives::{U256, U512};
+
+use crate::chart::labeled_bars;
+use crate::fluent::group_digits;
+use crate::to_string;
+use crate::units::{format_grouped_units, u512_to_string};
+
+/// Summarizes fixed-point values with `decimals` decimals, e.g.
+/// "count 4, min 1, max 1,000, sum 1,006, mean 251.5, median 2.5".
+///
+/// Values are comma-grouped with trailing fractional zeros dropped. The
+/// sum is accumulated in 512 bits and shown as "overflow" if it does not fit
+/// in a `U256`; the mean is still exact. The mean is rounded down to one
+/// digit past `decimals`, and the median of an even count is the mean of
+/// the two middle values, which needs at most that one extra digit. An
+/// empty slice is "count 0".
+pub fn format_stats(values: &[U256], decimals: u8) -> String {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return "count 0".to_string();
+    };
+    let count = values.len();
+    let sum = values.iter().fold(U512::ZERO, |sum, &value| sum + U512::from(value));
+    let sum_text = if sum > U512::from(U256::MAX) {
+        "overflow".to_string()
+    } else {
+        format_grouped_units(U256::from(sum), decimals, 0)
+    };
+    let ten = U512::from(10);
+    let mean = sum * ten / U512::from(count);
+
+    format!(
+        "count {}, min {}, max {}, sum {}, mean {}, median {}",
+        group_digits(&count.to_string(), 3, ','),
+        format_grouped_units(min, decimals, 0),
+        format_grouped_units(max, decimals, 0),
+        sum_text,
+        format_tenths(mean, decimals),
+        format_tenths(median_tenths(values), decimals)
+    )
+}
+
+/// Formats nearest-rank percentiles of fixed-point values with `decimals`
+/// decimals, e.g. "p50: 1,200 | p90: 4,800 | p99: 12,000".
+///
+/// `values` is sorted in place rather than copied; pass a copy if its order
+/// matters. The p-th percentile is the smallest value with at least p% of
+/// the values at or below it, so p0 is the minimum and p100 the maximum.
+/// Percentiles above 100 are treated as 100. With no values every
+/// percentile is "n/a".
+pub fn format_percentiles(values: &mut [U256], percentiles: &[u8], decimals: u8) -> String {
+    values.sort_unstable();
+    percentiles
+        .iter()
+        .map(|&percentile| {
+            let value = match values.len() {
+                0 => "n/a".to_string(),
+                len => {
+                    let rank = (usize::from(percentile.min(100)) * len).div_ceil(100).max(1);
+                    format_grouped_units(values[rank - 1], decimals, 0)
+                }
+            };
+            format!("p{}: {}", percentile, value)
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Renders a histogram of `values` as `labeled_bars`, one line per bucket
+/// labeled with its inclusive range, e.g. "28-51 ███░░░░░░░ 2".
+///
+/// The range from min to max is split into `buckets` buckets of equal width,
+/// each starting at a whole value, so widths differ by at most one value
+/// when the range does not divide evenly. There are never more buckets than
+/// distinct values in the range, so all-equal values give a single bucket
+/// labeled with that value. Empty input or zero buckets render "".
+pub fn format_histogram(values: &[U256], buckets: usize, width: usize) -> String {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    // Values in the range, which is 2^256 when it spans all of U256
+    let span = U512::from(max - min) + U512::from(1);
+    let buckets = U512::from(buckets).min(span);
+    if buckets.is_zero() {
+        return String::new();
+    }
+    // Bucket i holds the values v with floor((v - min) * buckets / span) == i,
+    // which start at min + ceil(i * span / buckets)
+    let start = |i: U512| min + U256::from((i * span + buckets - U512::from(1)) / buckets);
+
+    let count = buckets.to::<usize>();
+    let mut counts = vec![U256::ZERO; count];
+    for &value in values {
+        let index = U512::from(value - min) * buckets / span;
+        counts[index.to::<usize>()] += U256::from(1);
+    }
+    let labels: Vec<String> = (0..count)
+        .map(|i| {
+            let low = start(U512::from(i));
+            let high = if i + 1 == count { max } else { start(U512::from(i + 1)) - U256::from(1) };
+            if low == high {
+                group_digits(&to_string(low), 3, ',')
+            } else {
+                format!("{}-{}", group_digits(&to_string(low), 3, ','), group_digits(&to_string(high), 3, ','))
+            }
+        })
+        .collect();
+    let entries: Vec<(&str, U256)> = labels.iter().map(String::as_str).zip(counts).collect();
+    labeled_bars(&entries, width)
+}
+
+/// Median scaled by 10, selecting the middle values in linear time
+fn median_tenths(values: &[U256]) -> U512 {
+    let (middle, odd) = (values.len() / 2, values.len() % 2 == 1);
+    let mut values = values.to_vec();
+    let (lower, &mut upper, _) = values.select_nth_unstable(middle);
+    if odd {
+        return U512::from(upper) * U512::from(10);
+    }
+    // Selection leaves the lower half unordered; its maximum is the other middle value
+    let below = lower.iter().max().copied().unwrap_or(upper);
+    (U512::from(below) + U512::from(upper)) * U512::from(5)
+}
+
+/// Formats a value with `decimals + 1` decimals like `format_grouped_units`
+fn format_tenths(value: U512, decimals: u8) -> String {
+    let digits = u512_to_string(value);
+    let decimals = decimals as usize + 1;
+    let (integer, fraction) = if digits.len() > decimals {
+        digits.split_at(digits.len() - decimals)
+    } else {
+        ("0", digits.as_str())
+    };
+    let fraction = format!("{:0>width$}", fraction, width = decimals);
+    let fraction = fraction.trim_end_matches('0');
+    let integer = group_digits(integer, 3, ',');
+    if fraction.is_empty() {
+        integer
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(values: &[u64]) -> Vec<U256> {
+        values.iter().map(|&value| U256::from(value)).collect()
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(format_stats(&[], 18), "count 0");
+    }
+
+    #[test]
+    fn test_single_value() {
+        assert_eq!(
+            format_stats(&values(&[1_234_500]), 3),
+            "count 1, min 1,234.5, max 1,234.5, sum 1,234.5, mean 1,234.5, median 1,234.5"
+        );
+        assert_eq!(format_stats(&[U256::ZERO], 18), "count 1, min 0, max 0, sum 0, mean 0, median 0");
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(
+            format_stats(&values(&[1_000, 3, 1, 2]), 0),
+            "count 4, min 1, max 1,000, sum 1,006, mean 251.5, median 2.5"
+        );
+        assert_eq!(
+            format_stats(&values(&[5, 1, 4, 2, 3]), 0),
+            "count 5, min 1, max 5, sum 15, mean 3, median 3"
+        );
+        // Equal middle values, and a median below one unit
+        assert_eq!(format_stats(&values(&[7, 7, 1, 9]), 0), "count 4, min 1, max 9, sum 24, mean 6, median 7");
+        assert_eq!(
+            format_stats(&values(&[1, 2]), 2),
+            "count 2, min 0.01, max 0.02, sum 0.03, mean 0.015, median 0.015"
+        );
+    }
+
+    #[test]
+    fn test_mean_rounds_down() {
+        // 10 / 3 = 3.333…
+        assert_eq!(format_stats(&values(&[1, 2, 7]), 0), "count 3, min 1, max 7, sum 10, mean 3.3, median 2");
+        let many = vec![U256::from(1); 1_001];
+        assert!(format_stats(&many, 0).starts_with("count 1,001, min 1, max 1, sum 1,001, mean 1,"));
+    }
+
+    #[test]
+    fn test_sum_overflow() {
+        let half = U256::MAX / U256::from(2) + U256::from(1);
+        let stats = format_stats(&[half, half], 0);
+        assert!(stats.contains(", sum overflow, "));
+        let mean = group_digits(&to_string(half), 3, ',');
+        assert!(stats.ends_with(&format!("mean {}, median {}", mean, mean)));
+        // Exactly U256::MAX still fits
+        let stats = format_stats(&[U256::MAX - U256::from(1), U256::from(1)], 0);
+        assert!(stats.contains(&format!("sum {},", group_digits(&to_string(U256::MAX), 3, ','))));
+    }
+
+    #[test]
+    fn test_full_range() {
+        let max = group_digits(&to_string(U256::MAX), 3, ',');
+        let half = group_digits(&to_string(U256::MAX / U256::from(2)), 3, ',');
+        assert_eq!(
+            format_stats(&[U256::MAX, U256::ZERO], 0),
+            format!("count 2, min 0, max {}, sum {}, mean {}.5, median {}.5", max, max, half, half)
+        );
+        let three = [U256::MAX, U256::ZERO, U256::MAX];
+        assert!(format_stats(&three, 0).ends_with(&format!("median {}", max)));
+    }
+
+    const DATASET: [u64; 10] = [25, 3, 99, 12, 40, 7, 31, 18, 15, 22];
+
+    #[test]
+    fn test_percentiles() {
+        let mut data = values(&DATASET);
+        assert_eq!(
+            format_percentiles(&mut data, &[0, 50, 90, 99, 100], 0),
+            "p0: 3 | p50: 18 | p90: 40 | p99: 99 | p100: 99"
+        );
+        // Sorted in place
+        assert_eq!(data, values(&[3, 7, 12, 15, 18, 22, 25, 31, 40, 99]));
+        let mut data = values(&[1_200_000, 4_800_000, 12_000_000, 600_000]);
+        assert_eq!(format_percentiles(&mut data, &[50, 75, 99], 3), "p50: 1,200 | p75: 4,800 | p99: 12,000");
+    }
+
+    #[test]
+    fn test_percentiles_degenerate() {
+        assert_eq!(format_percentiles(&mut [], &[50, 99], 0), "p50: n/a | p99: n/a");
+        assert_eq!(format_percentiles(&mut values(&[5]), &[0, 100, 255], 1), "p0: 0.5 | p100: 0.5 | p255: 0.5");
+        assert_eq!(format_percentiles(&mut values(&[5]), &[], 0), "");
+        assert_eq!(format_percentiles(&mut [U256::MAX, U256::ZERO], &[50], 0), "p50: 0");
+    }
+
+    #[test]
+    fn test_histogram() {
+        assert_eq!(
+            format_histogram(&values(&DATASET), 4, 10),
+            [
+                "3-27  ██████████ 7",
+                "28-51 ███░░░░░░░ 2",
+                "52-75 ░░░░░░░░░░ 0",
+                "76-99 █░░░░░░░░░ 1",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            format_histogram(&values(&[1_000, 1_999, 2_000, 2_999]), 2, 4),
+            "1,000-1,999 ████ 2\n2,000-2,999 ████ 2"
+        );
+    }
+
+    #[test]
+    fn test_histogram_degenerate() {
+        assert_eq!(format_histogram(&[], 4, 10), "");
+        assert_eq!(format_histogram(&values(&DATASET), 0, 10), "");
+        // All-equal values give one bucket
+        assert_eq!(format_histogram(&values(&[7, 7, 7]), 5, 4), "7 ████ 3");
+        // No more buckets than distinct values in the range
+        assert_eq!(format_histogram(&values(&[1, 3]), 10, 2), "1 ██ 1\n2 ░░ 0\n3 ██ 1");
+    }
+
+    #[test]
+    fn test_histogram_full_range() {
+        let chart = format_histogram(&[U256::ZERO, U256::MAX, U256::MAX], 2, 2);
+        let half = group_digits(&to_string(U256::from(1) << 255), 3, ',');
+        let below = group_digits(&to_string((U256::from(1) << 255) - U256::from(1)), 3, ',');
+        let max = group_digits(&to_string(U256::MAX), 3, ',');
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(&format!("0-{} ", below)) && lines[0].ends_with(" █░ 1"));
+        assert_eq!(lines[1], format!("{}-{} ██ 2", half, max));
+    }
+}
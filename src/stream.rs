@@ -0,0 +1,128 @@
+//! Per-second payment streams, displayed per natural period.
+
+use alloy_primitives::{U256, U512};
+
+use crate::units::{format_fixed_hundredths, u512_to_string};
+
+/// Display periods from shortest to longest, with their length in seconds.
+/// A month is 30 days and a year 365 days.
+const PERIODS: [(&str, u64); 6] = [
+    ("second", 1),
+    ("minute", 60),
+    ("hour", 3_600),
+    ("day", 86_400),
+    ("month", 30 * 86_400),
+    ("year", 365 * 86_400),
+];
+
+/// Index of the day period, the preferred display period
+const DAY: usize = 3;
+
+/// Formats a flow rate per the most natural period, e.g. "0.42 DAI/day" or
+/// "1,250 DAI/second".
+///
+/// The rate is shown per day when that amount lies within 0.01–10,000.
+/// Larger rates step down to hour, minute and second until the amount is at
+/// most 10,000; smaller ones step up to month (30 days) and year (365 days)
+/// until it is at least 0.01. Rates beyond either end stay per second or per
+/// year; zero is "0 DAI/year". Amounts are rounded half up to two decimal
+/// places with comma-grouped integer digits.
+pub fn format_flow_rate(wei_per_second: U256, decimals: u8, symbol: &str) -> String {
+    let scale = decimals as usize;
+    let rate = U512::from(wei_per_second);
+    let amount = |index: usize| rate * U512::from(PERIODS[index].1);
+    // amount * 100 >= 10^decimals and amount <= 10^(decimals + 4), compared
+    // by digit count so the unit never has to be materialized
+    let at_least_min = |index: usize| !below_pow10(amount(index) * U512::from(100), scale);
+    let at_most_max = |index: usize| {
+        let amount = amount(index);
+        amount.is_zero() || below_pow10(amount - U512::from(1), scale + 4)
+    };
+
+    let period = if !at_most_max(DAY) {
+        (0..DAY).rev().find(|&i| at_most_max(i)).unwrap_or(0)
+    } else if !at_least_min(DAY) {
+        (DAY + 1..PERIODS.len()).find(|&i| at_least_min(i)).unwrap_or(PERIODS.len() - 1)
+    } else {
+        DAY
+    };
+    format!("{} {}/{}", format_fixed_hundredths(amount(period), decimals), symbol, PERIODS[period].0)
+}
+
+/// Formats the amount streamed at `rate` over `elapsed_seconds`, e.g.
+/// "1,234.56 DAI", rounded like `format_flow_rate`.
+///
+/// The product is computed in 512 bits, so it is exact even when it does
+/// not fit in a `U256`.
+pub fn format_streamed_total(rate: U256, elapsed_seconds: U256, decimals: u8, symbol: &str) -> String {
+    let total = U512::from(rate) * U512::from(elapsed_seconds);
+    format!("{} {}", format_fixed_hundredths(total, decimals), symbol)
+}
+
+/// Whether `value < 10^exp`
+fn below_pow10(value: U512, exp: usize) -> bool {
+    value.is_zero() || u512_to_string(value).len() <= exp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const E18: u128 = 1_000_000_000_000_000_000;
+
+    fn rate(value: u128) -> U256 {
+        U256::from(value)
+    }
+
+    #[test]
+    fn test_flow_rate_period_selection() {
+        assert_eq!(format_flow_rate(rate(42 * E18 / 100 / 86_400 + 1), 18, "DAI"), "0.42 DAI/day");
+        assert_eq!(format_flow_rate(rate(E18 / 1_000), 18, "DAI"), "86.4 DAI/day");
+        assert_eq!(format_flow_rate(rate(E18), 18, "DAI"), "3,600 DAI/hour");
+        assert_eq!(format_flow_rate(rate(100 * E18), 18, "DAI"), "6,000 DAI/minute");
+        assert_eq!(format_flow_rate(rate(100_308_641_976), 18, "DAI"), "0.26 DAI/month");
+    }
+
+    #[test]
+    fn test_flow_rate_tiny_and_huge() {
+        assert_eq!(format_flow_rate(rate(1_000_000_000), 18, "DAI"), "0.03 DAI/year");
+        assert_eq!(format_flow_rate(rate(1), 18, "DAI"), "0 DAI/year");
+        assert_eq!(format_flow_rate(U256::ZERO, 18, "DAI"), "0 DAI/year");
+        assert_eq!(format_flow_rate(rate(1_250 * E18), 18, "DAI"), "1,250 DAI/second");
+        assert!(format_flow_rate(U256::MAX, 0, "WEI").ends_with(",935 WEI/second"));
+    }
+
+    #[test]
+    fn test_flow_rate_boundaries() {
+        // Smallest rate reaching 0.01 per day, and one wei less
+        assert_eq!(format_flow_rate(rate(115_740_740_741), 18, "DAI"), "0.01 DAI/day");
+        assert_eq!(format_flow_rate(rate(115_740_740_740), 18, "DAI"), "0.3 DAI/month");
+        // Largest rate staying within 10,000 per day, and one wei more
+        assert_eq!(format_flow_rate(rate(115_740_740_740_740_740), 18, "DAI"), "10,000 DAI/day");
+        assert_eq!(format_flow_rate(rate(115_740_740_740_740_741), 18, "DAI"), "416.67 DAI/hour");
+    }
+
+    #[test]
+    fn test_streamed_total() {
+        assert_eq!(format_streamed_total(rate(E18), U256::from(90), 18, "DAI"), "90 DAI");
+        assert_eq!(format_streamed_total(rate(E18 / 3), U256::from(1), 18, "DAI"), "0.33 DAI");
+        assert_eq!(format_streamed_total(rate(E18 - 1), U256::from(1), 18, "DAI"), "1 DAI");
+        assert_eq!(format_streamed_total(rate(u128::MAX), U256::from(u128::MAX), 0, "WEI"),
+            "115,792,089,237,316,195,423,570,985,008,687,907,852,589,419,931,798,687,112,530,834,793,049,593,217,025 WEI");
+    }
+
+    #[test]
+    fn test_any_decimals() {
+        assert_eq!(format_flow_rate(U256::MAX, 255, "DAI"), "0 DAI/year");
+        assert_eq!(format_streamed_total(U256::MAX, U256::MAX, 255, "DAI"), "0 DAI");
+        assert_eq!(format_flow_rate(U256::MAX, 78, "DAI"), "416.85 DAI/hour");
+        assert_eq!(format_flow_rate(U256::MAX, 79, "DAI"), "1,000.44 DAI/day");
+        assert_eq!(format_streamed_total(U256::MAX, U256::MAX, 150, "DAI"), "13,407.81 DAI");
+    }
+
+    #[test]
+    fn test_streamed_total_beyond_u256() {
+        let total = format_streamed_total(U256::MAX, U256::MAX, 0, "WEI");
+        assert!(total.starts_with("13,407,807,929,942,597,099,574,024,998,205,846,127,479,365,820,592,393,377,723,561,"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Embedding SVG documents in `data:` URIs for token metadata.
+
+use crate::encoding::{base64_encode, base64_encoded_len};
+
+/// Prefix for base64-embedded SVG
+const BASE64_PREFIX: &str = "data:image/svg+xml;base64,";
+
+/// Prefix for percent-encoded UTF-8 SVG
+const UTF8_PREFIX: &str = "data:image/svg+xml;utf8,";
+
+/// Uppercase hex digits, as conventionally used in percent-encoding
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// How an SVG document is embedded in a data URI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SvgEmbedding {
+    /// `data:image/svg+xml;base64,…`
+    Base64,
+    /// `data:image/svg+xml;utf8,…` with minimal percent-encoding
+    Utf8,
+}
+
+/// Embeds an SVG document as a base64 data URI.
+pub fn svg_data_uri_base64(svg: &str) -> String {
+    let mut result = String::with_capacity(BASE64_PREFIX.len() + base64_encoded_len(svg.len()));
+    result.push_str(BASE64_PREFIX);
+    result.push_str(&base64_encode(svg.as_bytes()));
+    result
+}
+
+/// Embeds an SVG document as a UTF-8 data URI.
+///
+/// Only characters that break data URIs are percent-encoded: `%`, `#`, `"`,
+/// `<`, `>`, `{`, `}`, `|`, `\`, `^`, `` ` ``, whitespace and other ASCII
+/// control characters. Non-ASCII text is kept as UTF-8.
+pub fn svg_data_uri_utf8(svg: &str) -> String {
+    let mut result = String::with_capacity(UTF8_PREFIX.len() + utf8_encoded_len(svg));
+    result.push_str(UTF8_PREFIX);
+    for c in svg.chars() {
+        if needs_encoding(c) {
+            result.push('%');
+            result.push(HEX_UPPER[(c as usize) >> 4] as char);
+            result.push(HEX_UPPER[(c as usize) & 0xf] as char);
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Embeds an SVG document using the chosen embedding.
+pub fn svg_data_uri(svg: &str, mode: SvgEmbedding) -> String {
+    match mode {
+        SvgEmbedding::Base64 => svg_data_uri_base64(svg),
+        SvgEmbedding::Utf8 => svg_data_uri_utf8(svg),
+    }
+}
+
+/// Returns the exact byte length of `svg_data_uri(svg, mode)` without
+/// building it, for comparing the storage cost of each embedding.
+pub fn estimate_embedded_size(svg: &str, mode: SvgEmbedding) -> usize {
+    match mode {
+        SvgEmbedding::Base64 => BASE64_PREFIX.len() + base64_encoded_len(svg.len()),
+        SvgEmbedding::Utf8 => UTF8_PREFIX.len() + utf8_encoded_len(svg),
+    }
+}
+
+fn needs_encoding(c: char) -> bool {
+    c.is_ascii_control()
+        || matches!(c, ' ' | '%' | '#' | '"' | '<' | '>' | '{' | '}' | '|' | '\\' | '^' | '`')
+}
+
+fn utf8_encoded_len(svg: &str) -> usize {
+    svg.bytes().map(|b| if needs_encoding(b as char) { 3 } else { 1 }).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = "<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <rect fill=\"#ff0000\" width=\"100%\"/>\n  <text>50% – ok</text>\n</svg>";
+
+    fn percent_decode(s: &str) -> String {
+        let bytes = s.as_bytes();
+        let mut out = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%' {
+                let hex = core::str::from_utf8(&bytes[i + 1..i + 3]).unwrap();
+                out.push(u8::from_str_radix(hex, 16).unwrap());
+                i += 3;
+            } else {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_utf8_round_trip() {
+        let uri = svg_data_uri_utf8(SVG);
+        let payload = uri.strip_prefix(UTF8_PREFIX).unwrap();
+        assert_eq!(percent_decode(payload), SVG);
+    }
+
+    #[test]
+    fn test_utf8_encodes_breaking_characters() {
+        let uri = svg_data_uri_utf8(SVG);
+        assert!(uri.contains("fill=%22%23ff0000%22"));
+        assert!(uri.contains("100%25"));
+        assert!(uri.starts_with("data:image/svg+xml;utf8,%3Csvg%20xmlns="));
+        assert!(uri.contains("%0A"));
+        assert!(!uri[UTF8_PREFIX.len()..].contains('#'));
+        // Non-ASCII text passes through unchanged
+        assert!(uri.contains('–'));
+    }
+
+    #[test]
+    fn test_base64_uri() {
+        assert_eq!(svg_data_uri_base64("<svg/>"), "data:image/svg+xml;base64,PHN2Zy8+");
+        assert_eq!(svg_data_uri("<svg/>", SvgEmbedding::Base64), svg_data_uri_base64("<svg/>"));
+        assert_eq!(svg_data_uri(SVG, SvgEmbedding::Utf8), svg_data_uri_utf8(SVG));
+    }
+
+    #[test]
+    fn test_size_estimates_match() {
+        for svg in [SVG, "", "<svg/>", "plain"] {
+            for mode in [SvgEmbedding::Base64, SvgEmbedding::Utf8] {
+                assert_eq!(estimate_embedded_size(svg, mode), svg_data_uri(svg, mode).len());
+            }
+        }
+    }
+}
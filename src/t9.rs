@@ -0,0 +1,92 @@
+//! Phone-keypad (T9) encoding of short strings into numbers.
+
+use alloy_primitives::U256;
+
+/// Keypad digit for each letter `a` to `z`
+const KEYPAD: &[u8; 26] = b"22233344455566677778889999";
+
+/// Error types for encoding strings with a restricted character set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharsetError {
+    /// Input was empty
+    Empty,
+    /// Character at byte offset `index` is not in the character set
+    InvalidChar { index: usize, found: char },
+    /// Encoded value does not fit in 256 bits
+    Overflow,
+}
+
+/// Encodes `s` as the digits typed on a phone keypad, e.g. "cat" → 228.
+///
+/// Letters map case-insensitively through the standard keypad (`abc` → 2,
+/// …, `wxyz` → 9) and ASCII digits map to themselves. Leading `0` and `1`
+/// digits are lost in the numeric result. Strings longer than 78 digits,
+/// or 78-digit values above `U256::MAX`, overflow.
+pub fn t9_encode(s: &str) -> Result<U256, CharsetError> {
+    if s.is_empty() {
+        return Err(CharsetError::Empty);
+    }
+    let ten = U256::from(10);
+    s.char_indices().try_fold(U256::ZERO, |value, (index, c)| {
+        let digit = match c {
+            '0'..='9' => c as u8 - b'0',
+            'a'..='z' | 'A'..='Z' => KEYPAD[(c.to_ascii_lowercase() as u8 - b'a') as usize] - b'0',
+            _ => return Err(CharsetError::InvalidChar { index, found: c }),
+        };
+        value
+            .checked_mul(ten)
+            .and_then(|v| v.checked_add(U256::from(digit)))
+            .ok_or(CharsetError::Overflow)
+    })
+}
+
+/// Returns the words of `dictionary` whose T9 encoding is `digits`, in
+/// dictionary order. Words that cannot be encoded are skipped.
+pub fn t9_candidates<'a>(digits: U256, dictionary: &[&'a str]) -> Vec<&'a str> {
+    dictionary
+        .iter()
+        .copied()
+        .filter(|word| t9_encode(word) == Ok(digits))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_words() {
+        assert_eq!(t9_encode("cat").unwrap(), U256::from(228));
+        assert_eq!(t9_encode("hello").unwrap(), U256::from(43556));
+        assert_eq!(t9_encode("wxyz").unwrap(), U256::from(9999));
+        assert_eq!(t9_encode("r2d2").unwrap(), U256::from(7232));
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        assert_eq!(t9_encode("CaT").unwrap(), t9_encode("cat").unwrap());
+        assert_eq!(t9_encode("HELLO").unwrap(), U256::from(43556));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(t9_encode(""), Err(CharsetError::Empty));
+        assert_eq!(t9_encode("ca t"), Err(CharsetError::InvalidChar { index: 2, found: ' ' }));
+        assert_eq!(t9_encode("café"), Err(CharsetError::InvalidChar { index: 3, found: 'é' }));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(crate::to_string(t9_encode(&"1".repeat(78)).unwrap()), "1".repeat(78));
+        assert_eq!(t9_encode(&"9".repeat(78)), Err(CharsetError::Overflow));
+        assert_eq!(t9_encode(&"a".repeat(79)), Err(CharsetError::Overflow));
+    }
+
+    #[test]
+    fn test_dictionary_matching() {
+        let dictionary = ["home", "good", "cat", "gone", "hood", "act", "bat"];
+        assert_eq!(t9_candidates(U256::from(4663), &dictionary), vec!["home", "good", "gone", "hood"]);
+        assert_eq!(t9_candidates(U256::from(228), &dictionary), vec!["cat", "act", "bat"]);
+        assert!(t9_candidates(U256::from(1), &dictionary).is_empty());
+    }
+}
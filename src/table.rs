@@ -0,0 +1,75 @@
+//! Plain-text tables with aligned columns.
+
+use crate::width::{text_width, WidthMode};
+
+/// Renders rows as aligned columns, measuring widths in characters.
+pub(crate) fn render_table(rows: &[Vec<String>]) -> String {
+    format_table(rows, WidthMode::Chars)
+}
+
+/// Renders rows as aligned columns separated by two spaces.
+///
+/// The first column is left-aligned and the others right-aligned, so
+/// numbers line up on their last digit. Widths are measured according to
+/// `mode`; rows may have different lengths.
+pub fn format_table(rows: &[Vec<String>], mode: WidthMode) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| text_width(cell, mode))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            let mut line = String::new();
+            for (column, cell) in row.iter().enumerate() {
+                let padding = " ".repeat(widths[column] - text_width(cell, mode));
+                if column == 0 {
+                    line.push_str(cell);
+                    if row.len() > 1 {
+                        line.push_str(&padding);
+                    }
+                } else {
+                    line.push_str("  ");
+                    line.push_str(&padding);
+                    line.push_str(cell);
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(|cell| cell.to_string()).collect()
+    }
+
+    #[test]
+    fn test_render_table() {
+        let rows = [row(&["total", "1,000 TKN"]), row(&["claimable", "5 TKN"])];
+        assert_eq!(render_table(&rows), "total      1,000 TKN\nclaimable      5 TKN");
+        assert_eq!(render_table(&[]), "");
+        assert_eq!(render_table(&[row(&["só", "1"]), row(&["a"])]), "só  1\na");
+    }
+
+    #[test]
+    fn test_display_width_alignment() {
+        let rows = [row(&["USDC", "1,000"]), row(&["流動性", "25"]), row(&["cafe\u{301}", "3"])];
+        assert_eq!(
+            format_table(&rows, WidthMode::Display),
+            "USDC    1,000\n流動性     25\ncafe\u{301}        3"
+        );
+        // By char count the CJK label overhangs its column
+        assert_eq!(format_table(&rows, WidthMode::Chars).lines().nth(1), Some("流動性       25"));
+    }
+}
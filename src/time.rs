@@ -0,0 +1,339 @@
+//! Durations, relative times, ISO-8601 timestamps and English dates from
+//! block times.
+
+use alloy_primitives::U256;
+
+use crate::to_string;
+
+/// Duration units from largest to smallest, with their length in seconds
+const DURATION_UNITS: [(&str, u64); 4] = [("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)];
+
+/// Seconds in a day
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Last second representable as a four-digit year, 9999-12-31T23:59:59Z
+const MAX_ISO_TIMESTAMP: u64 = 253_402_300_799;
+
+/// Formats a duration as its two most significant units, e.g. "2d 4h",
+/// "1h 5m" or "45s". A zero second unit is omitted ("2d"), smaller units
+/// are truncated, and zero is "0s".
+pub fn format_duration(seconds: U256) -> String {
+    let Some(major) = DURATION_UNITS.iter().position(|&(_, length)| seconds >= U256::from(length)) else {
+        return "0s".to_string();
+    };
+    let (suffix, length) = DURATION_UNITS[major];
+    let mut result = format!("{}{}", to_string(seconds / U256::from(length)), suffix);
+    if let Some(&(minor_suffix, minor_length)) = DURATION_UNITS.get(major + 1) {
+        let minor = seconds % U256::from(length) / U256::from(minor_length);
+        if !minor.is_zero() {
+            result.push(' ');
+            result.push_str(&to_string(minor));
+            result.push_str(minor_suffix);
+        }
+    }
+    result
+}
+
+/// Formats a duration with every non-zero unit, e.g. "7d 1s" or
+/// "1h 30m", so no seconds are lost. Zero is "0s".
+pub(crate) fn format_duration_exact(seconds: U256) -> String {
+    let mut rest = seconds;
+    let mut parts = Vec::new();
+    for &(suffix, length) in DURATION_UNITS.iter() {
+        let count = rest / U256::from(length);
+        if !count.is_zero() {
+            parts.push(format!("{}{}", to_string(count), suffix));
+            rest -= count * U256::from(length);
+        }
+    }
+    if parts.is_empty() {
+        return "0s".to_string();
+    }
+    parts.join(" ")
+}
+
+/// Describes `target` relative to `now`: "in 2d 4h", "3h ago" or "now".
+pub fn format_relative(target: U256, now: U256) -> String {
+    if target > now {
+        format!("in {}", format_duration(target - now))
+    } else if target < now {
+        format!("{} ago", format_duration(now - target))
+    } else {
+        "now".to_string()
+    }
+}
+
+/// Formats a Unix timestamp as ISO-8601 UTC, e.g. "2024-07-02T12:00:00Z".
+///
+/// Returns `None` past 9999-12-31T23:59:59Z, which has no four-digit year.
+pub fn format_iso8601(timestamp: U256) -> Option<String> {
+    if timestamp > U256::from(MAX_ISO_TIMESTAMP) {
+        return None;
+    }
+    let timestamp = timestamp.to::<u64>();
+    let (days, secs) = (timestamp / SECS_PER_DAY, timestamp % SECS_PER_DAY);
+    let (year, month, day) = civil_from_days(U256::from(days));
+    Some(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year.to::<u64>(),
+        month,
+        day,
+        secs / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    ))
+}
+
+/// Field order of the long date written by `format_date_long_with`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LongDateOrder {
+    /// "June 1, 2024", as in US English
+    #[default]
+    MonthDayYear,
+    /// "1 juin 2024", as in French or British English
+    DayMonthYear,
+    /// "1. Juni 2024", as in German
+    DayDotMonthYear,
+}
+
+/// Month and weekday names used by the `_with` date formatters, along with
+/// the field order of the long date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateNames<'a> {
+    /// Full month names, January first
+    pub months: [&'a str; 12],
+    /// Abbreviated month names, January first
+    pub months_short: [&'a str; 12],
+    /// Weekday names, Monday first as in ISO-8601
+    pub weekdays: [&'a str; 7],
+    /// Field order of `format_date_long_with`
+    pub long_order: LongDateOrder,
+}
+
+impl DateNames<'static> {
+    /// English names, as used by `format_date_long`, `format_date_short`
+    /// and `weekday`
+    pub const ENGLISH: Self = Self {
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        months_short: ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"],
+        weekdays: ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"],
+        long_order: LongDateOrder::MonthDayYear,
+    };
+}
+
+/// Formats the UTC date of a Unix timestamp in English, e.g. "June 1, 2024".
+pub fn format_date_long(timestamp: U256) -> String {
+    format_date_long_with(timestamp, &DateNames::ENGLISH)
+}
+
+/// Formats the UTC date of a Unix timestamp with the month name and field
+/// order taken from `names`, e.g. "1. Juni 2024" for `DayDotMonthYear`.
+///
+/// Every timestamp has a date; years past 9999 are written out in full.
+pub fn format_date_long_with(timestamp: U256, names: &DateNames) -> String {
+    let (year, month, day) = civil_from_days(timestamp / U256::from(SECS_PER_DAY));
+    let (month, year) = (names.months[month as usize - 1], to_string(year));
+    match names.long_order {
+        LongDateOrder::MonthDayYear => format!("{} {}, {}", month, day, year),
+        LongDateOrder::DayMonthYear => format!("{} {} {}", day, month, year),
+        LongDateOrder::DayDotMonthYear => format!("{}. {} {}", day, month, year),
+    }
+}
+
+/// Formats the UTC date of a Unix timestamp in English, e.g. "01 Jun 2024".
+pub fn format_date_short(timestamp: U256) -> String {
+    format_date_short_with(timestamp, &DateNames::ENGLISH)
+}
+
+/// Formats the UTC date of a Unix timestamp as "<dd> <month> <year>" with
+/// the abbreviated month taken from `names`. The order is fixed and does
+/// not follow `long_order`.
+pub fn format_date_short_with(timestamp: U256, names: &DateNames) -> String {
+    let (year, month, day) = civil_from_days(timestamp / U256::from(SECS_PER_DAY));
+    format!("{:02} {} {}", day, names.months_short[month as usize - 1], to_string(year))
+}
+
+/// English name of the UTC weekday of a Unix timestamp, e.g. "Thursday"
+/// for 0.
+pub fn weekday(timestamp: U256) -> &'static str {
+    weekday_with(timestamp, &DateNames::ENGLISH)
+}
+
+/// Name of the UTC weekday of a Unix timestamp, taken from `names`.
+pub fn weekday_with<'a>(timestamp: U256, names: &DateNames<'a>) -> &'a str {
+    // 1970-01-01 was a Thursday, index 3 counting from Monday
+    let days = timestamp / U256::from(SECS_PER_DAY);
+    let index = (days % U256::from(7)).to::<usize>();
+    names.weekdays[(index + 3) % 7]
+}
+
+/// ISO-8601 rendering of `timestamp`, or "timestamp N" past year 9999.
+pub(crate) fn timestamp_label(timestamp: U256) -> String {
+    format_iso8601(timestamp).unwrap_or_else(|| format!("timestamp {}", to_string(timestamp)))
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) civil date,
+/// following Howard Hinnant's `civil_from_days`. Only the count of 400-year
+/// eras needs more than 64 bits.
+fn civil_from_days(days: U256) -> (U256, u64, u64) {
+    let z = days + U256::from(719_468);
+    let era = z / U256::from(146_097);
+    let doe = (z - era * U256::from(146_097)).to::<u64>();
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = era * U256::from(400) + U256::from(yoe + u64::from(month <= 2));
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(value: u64) -> U256 {
+        U256::from(value)
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(U256::ZERO), "0s");
+        assert_eq!(format_duration(secs(45)), "45s");
+        assert_eq!(format_duration(secs(3_900)), "1h 5m");
+        assert_eq!(format_duration(secs(2 * 86_400 + 4 * 3_600 + 59)), "2d 4h");
+        assert_eq!(format_duration(secs(2 * 86_400 + 5 * 60)), "2d");
+        assert_eq!(format_duration(secs(60)), "1m");
+        assert!(format_duration(U256::MAX).ends_with("d 10h"));
+    }
+
+    #[test]
+    fn test_format_duration_exact() {
+        assert_eq!(format_duration_exact(U256::ZERO), "0s");
+        assert_eq!(format_duration_exact(secs(7 * 86_400 + 1)), "7d 1s");
+        assert_eq!(format_duration_exact(secs(2 * 86_400 + 4 * 3_600 + 59)), "2d 4h 59s");
+        assert_eq!(format_duration_exact(secs(3_900)), "1h 5m");
+        assert!(format_duration_exact(U256::MAX).ends_with("d 10h 12m 15s"));
+    }
+
+    #[test]
+    fn test_format_relative() {
+        assert_eq!(format_relative(secs(100), secs(100)), "now");
+        assert_eq!(format_relative(secs(3_700), secs(100)), "in 1h");
+        assert_eq!(format_relative(secs(100), secs(190)), "1m 30s ago");
+    }
+
+    #[test]
+    fn test_format_iso8601() {
+        assert_eq!(format_iso8601(U256::ZERO).unwrap(), "1970-01-01T00:00:00Z");
+        assert_eq!(format_iso8601(secs(1_719_921_600)).unwrap(), "2024-07-02T12:00:00Z");
+        assert_eq!(format_iso8601(secs(951_782_400)).unwrap(), "2000-02-29T00:00:00Z");
+        assert_eq!(format_iso8601(secs(MAX_ISO_TIMESTAMP)).unwrap(), "9999-12-31T23:59:59Z");
+        assert_eq!(format_iso8601(secs(MAX_ISO_TIMESTAMP + 1)), None);
+        assert_eq!(format_iso8601(U256::MAX), None);
+    }
+
+    #[test]
+    fn test_weekday() {
+        assert_eq!(weekday(U256::ZERO), "Thursday");
+        assert_eq!(weekday(secs(86_399)), "Thursday");
+        assert_eq!(weekday(secs(86_400)), "Friday");
+        assert_eq!(weekday(secs(1_717_200_000)), "Saturday");
+        assert_eq!(weekday(secs(951_782_400)), "Tuesday");
+        assert_eq!(weekday(secs(MAX_ISO_TIMESTAMP)), "Friday");
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date_long(U256::ZERO), "January 1, 1970");
+        assert_eq!(format_date_short(U256::ZERO), "01 Jan 1970");
+        assert_eq!(format_date_long(secs(1_717_200_000)), "June 1, 2024");
+        assert_eq!(format_date_short(secs(1_717_200_000)), "01 Jun 2024");
+        assert_eq!(format_date_long(secs(951_782_400)), "February 29, 2000");
+        assert_eq!(format_date_short(secs(951_782_400 + 86_399)), "29 Feb 2000");
+        assert_eq!(format_date_long(secs(MAX_ISO_TIMESTAMP)), "December 31, 9999");
+        assert_eq!(format_date_short(secs(MAX_ISO_TIMESTAMP + 1)), "01 Jan 10000");
+    }
+
+    #[test]
+    fn test_far_future() {
+        let max = "February 19, 3669305236998687180674831492239425019668248843096144521164705134005822";
+        assert_eq!(format_date_long(U256::MAX), max);
+        assert_eq!(weekday(U256::MAX), "Tuesday");
+        // The calendar repeats every 400 years, weekdays included
+        let cycle = U256::from(146_097u64 * 86_400);
+        let later = U256::from(1_717_200_000) + cycle * U256::from(10).pow(U256::from(60));
+        assert_eq!(
+            format_date_long(later),
+            format!("June 1, {}", to_string(U256::from(2024) + U256::from(400) * U256::from(10).pow(U256::from(60))))
+        );
+        assert_eq!(weekday(later), "Saturday");
+    }
+
+    #[test]
+    fn test_custom_names() {
+        let names = DateNames {
+            months: [
+                "janvier",
+                "février",
+                "mars",
+                "avril",
+                "mai",
+                "juin",
+                "juillet",
+                "août",
+                "septembre",
+                "octobre",
+                "novembre",
+                "décembre",
+            ],
+            months_short: [
+                "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.", "déc.",
+            ],
+            weekdays: ["lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche"],
+            long_order: LongDateOrder::DayMonthYear,
+        };
+        assert_eq!(format_date_long_with(secs(951_782_400), &names), "29 février 2000");
+        assert_eq!(format_date_long_with(secs(1_717_200_000), &names), "1 juin 2024");
+        assert_eq!(format_date_short_with(secs(1_717_200_000), &names), "01 juin 2024");
+        assert_eq!(weekday_with(U256::ZERO, &names), "jeudi");
+    }
+    #[test]
+    fn test_long_date_orders() {
+        let german = DateNames {
+            months: [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            long_order: LongDateOrder::DayDotMonthYear,
+            ..DateNames::ENGLISH
+        };
+        assert_eq!(format_date_long_with(secs(1_717_200_000), &german), "1. Juni 2024");
+        let british = DateNames { long_order: LongDateOrder::DayMonthYear, ..DateNames::ENGLISH };
+        assert_eq!(format_date_long_with(secs(1_717_200_000), &british), "1 June 2024");
+        assert_eq!(format_date_long_with(secs(1_717_200_000), &DateNames::ENGLISH), "June 1, 2024");
+    }
+}
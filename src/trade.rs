@@ -0,0 +1,103 @@
+//! Slippage and price-impact phrases for DEX event logs and reverts.
+
+use alloy_primitives::{U256, U512};
+
+use crate::{format_units, to_string};
+
+/// Basis points in 100%
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Price impact below this many basis points (1%) is negligible
+const MODERATE_IMPACT_BPS: u64 = 100;
+
+/// Price impact at or above this many basis points (5%) is severe
+const SEVERE_IMPACT_BPS: u64 = 500;
+
+/// Formats the minimum output of a swap with its slippage tolerance, e.g.
+/// "min received 98.5 (1.5% slippage)".
+///
+/// Slippage is `(expected - minimum) / expected`, rounded half up to a
+/// hundredth of a percent. A minimum at or above the expected amount is 0%
+/// slippage; a zero expected amount has no meaningful percentage and is
+/// rendered as "(slippage n/a)".
+pub fn format_slippage(expected: U256, minimum: U256, decimals: u8) -> String {
+    let received = format_units(minimum, decimals);
+    if expected.is_zero() {
+        return format!("min received {} (slippage n/a)", received);
+    }
+
+    let shortfall = expected.saturating_sub(minimum);
+    // Computed in U512 so `shortfall * 10_000` cannot overflow
+    let expected = U512::from(expected);
+    let bps = (U512::from(shortfall) * U512::from(BPS_DENOMINATOR) + expected / U512::from(2)) / expected;
+    let bps = U256::from(bps.to::<u64>());
+    format!("min received {} ({} slippage)", received, format_bps_percent(bps))
+}
+
+/// Describes a price impact given in basis points.
+///
+/// Below 1% the impact is "negligible"; from 1% up to 5% it is
+/// "moderate (x%)", and 5% or more is "severe (x%)".
+pub fn format_price_impact(bps: U256) -> String {
+    if bps < U256::from(MODERATE_IMPACT_BPS) {
+        "negligible".to_string()
+    } else if bps < U256::from(SEVERE_IMPACT_BPS) {
+        format!("moderate ({})", format_bps_percent(bps))
+    } else {
+        format!("severe ({})", format_bps_percent(bps))
+    }
+}
+
+/// Renders basis points as a percentage with trailing zeros trimmed.
+fn format_bps_percent(bps: U256) -> String {
+    let hundred = U256::from(100);
+    let whole = to_string(bps / hundred);
+    let cents = (bps % hundred).to::<u64>();
+    match cents {
+        0 => format!("{}%", whole),
+        c if c % 10 == 0 => format!("{}.{}%", whole, c / 10),
+        c => format!("{}.{:02}%", whole, c),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn units(value: u64) -> U256 {
+        U256::from(value)
+    }
+
+    #[test]
+    fn test_format_slippage() {
+        assert_eq!(format_slippage(units(100_000), units(98_500), 3), "min received 98.5 (1.5% slippage)");
+        assert_eq!(format_slippage(units(3), units(2), 0), "min received 2 (33.33% slippage)");
+        assert_eq!(format_slippage(units(3), units(1), 0), "min received 1 (66.67% slippage)");
+    }
+
+    #[test]
+    fn test_zero_and_full_slippage() {
+        assert_eq!(format_slippage(units(1000), units(1000), 0), "min received 1000 (0% slippage)");
+        assert_eq!(format_slippage(units(1000), units(1200), 0), "min received 1200 (0% slippage)");
+        assert_eq!(format_slippage(units(1000), U256::ZERO, 0), "min received 0 (100% slippage)");
+        assert!(format_slippage(U256::MAX, U256::ZERO, 18).ends_with("(100% slippage)"));
+    }
+
+    #[test]
+    fn test_zero_expected_guard() {
+        assert_eq!(format_slippage(U256::ZERO, U256::ZERO, 0), "min received 0 (slippage n/a)");
+        assert_eq!(format_slippage(U256::ZERO, units(5), 0), "min received 5 (slippage n/a)");
+    }
+
+    #[test]
+    fn test_format_price_impact_thresholds() {
+        assert_eq!(format_price_impact(U256::ZERO), "negligible");
+        assert_eq!(format_price_impact(units(99)), "negligible");
+        assert_eq!(format_price_impact(units(100)), "moderate (1%)");
+        assert_eq!(format_price_impact(units(230)), "moderate (2.3%)");
+        assert_eq!(format_price_impact(units(499)), "moderate (4.99%)");
+        assert_eq!(format_price_impact(units(500)), "severe (5%)");
+        assert_eq!(format_price_impact(units(1200)), "severe (12%)");
+        assert_eq!(format_price_impact(units(10_000)), "severe (100%)");
+    }
+}
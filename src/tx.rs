@@ -0,0 +1,113 @@
+//! One-line summaries of queued transactions for relayer logs.
+
+use alloy_primitives::{Address, U256};
+
+use crate::selector::selector_signature;
+use crate::{abbreviate_address, bytes_to_hex_string, format_ether_smart};
+
+/// Summarizes a transaction as a single log line.
+///
+/// - empty data: "send 1.5 ETH to 0x1234…abcd"
+/// - data sent to the zero address: "deploy contract (N bytes) with 0 ETH"
+/// - data shorter than a selector: "call with N-byte data on 0xAb58…cF12 with 0 ETH"
+/// - otherwise: "call transfer(address,uint256) on 0xAb58…cF12 with 0 ETH",
+///   falling back to the selector hex for unknown selectors
+///
+/// Values are rendered with `format_ether_smart`, so a million ETH or more
+/// is compact, e.g. "2.5M ETH" or "1.1e59 ETH", and dust amounts are shown
+/// in wei or gwei.
+pub fn format_tx_summary(to: Address, value_wei: U256, data: &[u8]) -> String {
+    let eth = format_ether_smart(value_wei);
+    if data.is_empty() {
+        return format!("send {} to {}", eth, abbreviate_address(to));
+    }
+    if to == Address::ZERO {
+        return format!("deploy contract ({} bytes) with {}", data.len(), eth);
+    }
+    if data.len() < 4 {
+        return format!("call with {}-byte data on {} with {}", data.len(), abbreviate_address(to), eth);
+    }
+
+    let selector = [data[0], data[1], data[2], data[3]];
+    let target = match selector_signature(selector, &[]) {
+        Some(signature) => signature.to_string(),
+        None => bytes_to_hex_string(&selector),
+    };
+    format!("call {} on {} with {}", target, abbreviate_address(to), eth)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(byte: u8) -> Address {
+        Address::from_slice(&[byte; 20])
+    }
+
+    fn eth(tenths: u64) -> U256 {
+        U256::from(tenths) * U256::from(100_000_000_000_000_000u64)
+    }
+
+    #[test]
+    fn test_plain_transfer() {
+        let to = addr(0x12);
+        assert_eq!(
+            format_tx_summary(to, eth(15), &[]),
+            format!("send 1.5 ETH to {}", abbreviate_address(to))
+        );
+        assert_eq!(
+            format_tx_summary(to, U256::ZERO, &[]),
+            format!("send 0 ETH to {}", abbreviate_address(to))
+        );
+    }
+
+    #[test]
+    fn test_known_call() {
+        let to = addr(0xab);
+        let mut data = vec![0xa9, 0x05, 0x9c, 0xbb];
+        data.extend_from_slice(&[0u8; 64]);
+        assert_eq!(
+            format_tx_summary(to, U256::ZERO, &data),
+            format!("call transfer(address,uint256) on {} with 0 ETH", abbreviate_address(to))
+        );
+    }
+
+    #[test]
+    fn test_unknown_call() {
+        let to = addr(0xab);
+        assert_eq!(
+            format_tx_summary(to, eth(1), &[0xde, 0xad, 0xbe, 0xef, 0x00]),
+            format!("call 0xdeadbeef on {} with 0.1 ETH", abbreviate_address(to))
+        );
+    }
+
+    #[test]
+    fn test_contract_creation() {
+        assert_eq!(
+            format_tx_summary(Address::ZERO, U256::ZERO, &[0x60, 0x80, 0x60, 0x40, 0x52]),
+            "deploy contract (5 bytes) with 0 ETH"
+        );
+    }
+
+    #[test]
+    fn test_short_data() {
+        let to = addr(0x01);
+        assert_eq!(
+            format_tx_summary(to, U256::ZERO, &[0xa9, 0x05]),
+            format!("call with 2-byte data on {} with 0 ETH", abbreviate_address(to))
+        );
+    }
+
+    #[test]
+    fn test_enormous_values_are_compact() {
+        let to = addr(0x01);
+        assert_eq!(
+            format_tx_summary(to, eth(25_000_000), &[]),
+            format!("send 2.5M ETH to {}", abbreviate_address(to))
+        );
+        assert_eq!(
+            format_tx_summary(to, U256::MAX, &[]),
+            format!("send 1.1e59 ETH to {}", abbreviate_address(to))
+        );
+    }
+}
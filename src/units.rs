@@ -0,0 +1,366 @@
+//! Fixed-point token amounts and shortened addresses for display.
+
+use alloy_primitives::{Address, U256, U512};
+
+use crate::decimal::add_decimal_strings;
+use crate::fluent::group_digits;
+use crate::{address_to_checksum_hex_string, pow10, to_compact_string, to_string};
+
+/// Wei amount from which `format_ether_smart` switches from wei to gwei
+const GWEI_THRESHOLD: u64 = 100_000;
+
+/// Wei amount (0.001 ETH) from which `format_ether_smart` switches to ETH
+const ETHER_THRESHOLD: u64 = 1_000_000_000_000_000;
+
+/// Whole-ETH amount from which ETH values are rendered in compact notation
+const COMPACT_ETHER_THRESHOLD: u64 = 1_000_000;
+
+/// Significant figures shown by the ether formatters
+const SIGNIFICANT_FIGURES: usize = 3;
+
+/// Binary byte-size units used by `format_byte_size`
+const BYTE_UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+/// Display unit for ether amounts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EtherUnit {
+    Wei,
+    Gwei,
+    Ether,
+}
+
+impl EtherUnit {
+    fn decimals(self) -> u8 {
+        match self {
+            EtherUnit::Wei => 0,
+            EtherUnit::Gwei => 9,
+            EtherUnit::Ether => 18,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            EtherUnit::Wei => "wei",
+            EtherUnit::Gwei => "gwei",
+            EtherUnit::Ether => "ETH",
+        }
+    }
+}
+
+/// Splits a fixed-point value into its integer digits and its fractional
+/// digits, zero-padded to exactly `decimals` characters.
+pub(crate) fn split_decimal(value: U256, decimals: u8) -> (String, String) {
+    split_digits(&to_string(value), decimals)
+}
+
+/// Splits the decimal digits of a fixed-point value like `split_decimal`.
+fn split_digits(digits: &str, decimals: u8) -> (String, String) {
+    let decimals = decimals as usize;
+    if digits.len() > decimals {
+        let split = digits.len() - decimals;
+        (digits[..split].to_string(), digits[split..].to_string())
+    } else {
+        ("0".to_string(), format!("{:0>width$}", digits, width = decimals))
+    }
+}
+
+/// Formats a fixed-point token amount exactly, e.g. 1.5 for 1500000000000000000
+/// with 18 decimals. Trailing fractional zeros are dropped.
+pub fn format_units(value: U256, decimals: u8) -> String {
+    let (integer, fraction) = split_decimal(value, decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+/// Formats a fixed-point amount with comma-grouped integer digits and at
+/// least `min_decimals` fractional digits.
+pub(crate) fn format_grouped_units(value: U256, decimals: u8, min_decimals: usize) -> String {
+    format_grouped_digits(&to_string(value), decimals, min_decimals)
+}
+
+/// Formats a fixed-point amount given as decimal digits like
+/// `format_grouped_units`, for values wider than a `U256`.
+pub(crate) fn format_grouped_digits(digits: &str, decimals: u8, min_decimals: usize) -> String {
+    let (integer, fraction) = split_digits(digits, decimals);
+    let integer = group_digits(&integer, 3, ',');
+    let keep = fraction.trim_end_matches('0').len().max(min_decimals.min(fraction.len()));
+    if keep == 0 {
+        integer
+    } else {
+        format!("{}.{}", integer, &fraction[..keep])
+    }
+}
+
+/// Renders `amount / unit` rounded half up to two decimal places.
+pub(crate) fn format_hundredths(amount: U512, unit: U512) -> String {
+    // Integer and fractional parts are rounded separately so `amount * 100`
+    // cannot overflow
+    let mut integer = amount / unit;
+    let hundred = U512::from(100);
+    let remainder = amount % unit;
+    let mut cents = (remainder * hundred + unit / U512::from(2)) / unit;
+    if cents == hundred {
+        integer += U512::from(1);
+        cents = U512::ZERO;
+    }
+
+    let integer = group_digits(&u512_to_string(integer), 3, ',');
+    match cents.to::<u8>() {
+        0 => integer,
+        c if c % 10 == 0 => format!("{}.{}", integer, c / 10),
+        c => format!("{}.{:02}", integer, c),
+    }
+}
+
+/// Renders a fixed-point amount with `decimals` fractional digits rounded
+/// half up to two decimal places, like `format_hundredths`.
+///
+/// The rounding works on the decimal digits rather than on a power-of-ten
+/// unit, so every `u8` scale is accepted.
+pub(crate) fn format_fixed_hundredths(amount: U512, decimals: u8) -> String {
+    let (integer, fraction) = split_digits(&u512_to_string(amount), decimals);
+    let cents = format!("{}{:0<2}", integer, &fraction[..fraction.len().min(2)]);
+    let cents = if fraction.as_bytes().get(2).is_some_and(|&digit| digit >= b'5') {
+        add_decimal_strings(&cents, "1").expect("cents are decimal digits")
+    } else {
+        cents
+    };
+    format_grouped_digits(&cents, 2, 0)
+}
+
+/// Decimal digits of a `U512`, built from base-10^19 chunks.
+pub(crate) fn u512_to_string(mut value: U512) -> String {
+    let chunk = U512::from(10_000_000_000_000_000_000u64);
+    let mut chunks = Vec::new();
+    while !value.is_zero() {
+        chunks.push((value % chunk).to::<u64>());
+        value /= chunk;
+    }
+    let Some(first) = chunks.pop() else {
+        return "0".to_string();
+    };
+    let mut result = first.to_string();
+    for part in chunks.iter().rev() {
+        result.push_str(&format!("{:019}", part));
+    }
+    result
+}
+
+/// Formats a wei amount in the most readable unit.
+///
+/// Below 1e5 wei the unit is wei, below 0.001 ETH it is gwei, and above
+/// that ETH; zero is "0 ETH". See `format_ether_in` for the rounding rules.
+/// The unit is chosen after rounding, so 999,999,999,999,999 wei, which
+/// rounds to a million gwei, is "0.001 ETH".
+pub fn format_ether_smart(wei: U256) -> String {
+    if wei.is_zero() {
+        return format_ether_in(wei, EtherUnit::Ether);
+    }
+    let unit = if wei < U256::from(GWEI_THRESHOLD) {
+        EtherUnit::Wei
+    } else if wei < U256::from(ETHER_THRESHOLD) && !rounds_to_ether(wei) {
+        EtherUnit::Gwei
+    } else {
+        EtherUnit::Ether
+    };
+    format_ether_in(wei, unit)
+}
+
+/// Formats a wei amount in a fixed unit, e.g. "1.2 gwei" or "1,234.5 ETH".
+///
+/// Values are rounded half up to 3 significant figures, but always keep
+/// every integer digit and one decimal place, so large amounts are not
+/// rounded to tens or hundreds. Integer digits are grouped with commas.
+/// Amounts that round to a million ETH or more are shown compactly, e.g.
+/// "2.5M ETH", so 999,999.95 ETH is "1M ETH" rather than "1,000,000 ETH".
+pub fn format_ether_in(wei: U256, unit: EtherUnit) -> String {
+    let (rounded, places) = round_significant(wei, unit.decimals());
+    if unit == EtherUnit::Ether {
+        let whole = rounded / pow10(places).expect("places are at most 18");
+        if whole >= U256::from(COMPACT_ETHER_THRESHOLD) {
+            return format!("{} {}", to_compact_string(whole), unit.symbol());
+        }
+    }
+
+    let (integer, fraction) = split_decimal(rounded, places as u8);
+    let integer = group_digits(&integer, 3, ',');
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{} {}", integer, unit.symbol())
+    } else {
+        format!("{}.{} {}", integer, fraction, unit.symbol())
+    }
+}
+
+/// Rounds a fixed-point amount half up to 3 significant figures, keeping
+/// every integer digit and at least one decimal place. Returns the rounded
+/// amount with the decimal places kept, and that number of places.
+fn round_significant(wei: U256, decimals: u8) -> (U256, usize) {
+    let (integer, fraction) = split_decimal(wei, decimals);
+    let places = if integer != "0" {
+        SIGNIFICANT_FIGURES.saturating_sub(integer.len()).max(1)
+    } else {
+        let leading_zeros = fraction.len() - fraction.trim_start_matches('0').len();
+        leading_zeros + SIGNIFICANT_FIGURES
+    }
+    .min(decimals as usize);
+
+    let step = pow10(decimals as usize - places).expect("step is at most 10^18");
+    let mut rounded = wei / step;
+    if wei % step >= (step + U256::from(1)) / U256::from(2) && step > U256::from(1) {
+        rounded += U256::from(1);
+    }
+    (rounded, places)
+}
+
+/// Whether a gwei-range amount below 0.001 ETH rounds up to it in gwei
+fn rounds_to_ether(wei: U256) -> bool {
+    let decimals = EtherUnit::Gwei.decimals() as usize;
+    let (rounded, places) = round_significant(wei, decimals as u8);
+    let step = pow10(decimals - places).expect("step is at most 10^9");
+    rounded * step >= U256::from(ETHER_THRESHOLD)
+}
+
+/// Formats a byte count with binary units, e.g. "512 B" or "1.5 KiB",
+/// rounded half up to two decimal places.
+pub fn format_byte_size(len: usize) -> String {
+    let len = len as u64;
+    let mut unit = 0;
+    while unit + 1 < BYTE_UNITS.len() && len >> (10 * (unit + 1)) != 0 {
+        unit += 1;
+    }
+    let amount = format_hundredths(U512::from(len), U512::from(1u64) << (10 * unit));
+    format!("{} {}", amount, BYTE_UNITS[unit])
+}
+
+/// Shortens an address to its checksummed first and last four hex digits,
+/// e.g. "0xAb58…cF12".
+pub fn abbreviate_address(addr: Address) -> String {
+    let full = address_to_checksum_hex_string(addr);
+    format!("{}…{}", &full[..6], &full[38..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_units() {
+        let one_eth = U256::from(1_000_000_000_000_000_000u64);
+        assert_eq!(format_units(U256::ZERO, 18), "0");
+        assert_eq!(format_units(one_eth, 18), "1");
+        assert_eq!(format_units(one_eth * U256::from(3) / U256::from(2), 18), "1.5");
+        assert_eq!(format_units(U256::from(1), 18), "0.000000000000000001");
+        assert_eq!(format_units(U256::from(1234), 0), "1234");
+        assert_eq!(format_units(U256::from(1234), 2), "12.34");
+    }
+
+    #[test]
+    fn test_split_decimal() {
+        assert_eq!(split_decimal(U256::from(5), 3), ("0".to_string(), "005".to_string()));
+        assert_eq!(split_decimal(U256::from(12345), 3), ("12".to_string(), "345".to_string()));
+        assert_eq!(split_decimal(U256::ZERO, 0), ("0".to_string(), String::new()));
+    }
+
+    fn wei(value: u128) -> U256 {
+        U256::from(value)
+    }
+
+    #[test]
+    fn test_format_ether_smart_thresholds() {
+        assert_eq!(format_ether_smart(U256::ZERO), "0 ETH");
+        assert_eq!(format_ether_smart(wei(1)), "1 wei");
+        assert_eq!(format_ether_smart(wei(950)), "950 wei");
+        assert_eq!(format_ether_smart(wei(99_999)), "99,999 wei");
+        assert_eq!(format_ether_smart(wei(100_000)), "0.0001 gwei");
+        assert_eq!(format_ether_smart(wei(1_200_000_000)), "1.2 gwei");
+        // Rounds up to the ETH threshold, so shown in ETH
+        assert_eq!(format_ether_smart(wei(999_999_999_999_999)), "0.001 ETH");
+        assert_eq!(format_ether_smart(wei(999_999_950_000_000)), "0.001 ETH");
+        assert_eq!(format_ether_smart(wei(999_999_949_999_999)), "999,999.9 gwei");
+        assert_eq!(format_ether_smart(wei(1_000_000_000_000_000)), "0.001 ETH");
+        assert_eq!(format_ether_smart(wei(50_000_000_000_000_000)), "0.05 ETH");
+        assert_eq!(format_ether_smart(wei(1_000_000_000_000_000_000)), "1 ETH");
+    }
+
+    #[test]
+    fn test_format_ether_rounding() {
+        let eth = 1_000_000_000_000_000_000u128;
+        assert_eq!(format_ether_smart(wei(eth * 12345 / 10)), "1,234.5 ETH");
+        assert_eq!(format_ether_smart(wei(eth * 123456 / 100000)), "1.23 ETH");
+        assert_eq!(format_ether_smart(wei(eth * 12355 / 10000)), "1.24 ETH");
+        assert_eq!(format_ether_smart(wei(eth * 9999 / 100000)), "0.1 ETH");
+        assert_eq!(format_ether_smart(wei(eth * 512345 / 10000000)), "0.0512 ETH");
+        assert_eq!(format_ether_smart(wei(eth * 25_000_000)), "25M ETH");
+    }
+
+    #[test]
+    fn test_format_ether_compact_after_rounding() {
+        let eth = 1_000_000_000_000_000_000u128;
+        assert_eq!(format_ether_smart(wei(eth * 99_999_995 / 100)), "1M ETH");
+        assert_eq!(format_ether_smart(wei(eth * 99_999_996 / 100)), "1M ETH");
+        assert_eq!(format_ether_smart(wei(eth * 99_999_995 / 100 - 1)), "999,999.9 ETH");
+        assert_eq!(format_ether_smart(wei(eth * 1_000_000)), "1M ETH");
+        assert_eq!(format_ether_in(wei(eth * 99_999_995 / 100), EtherUnit::Gwei), "999,999,950,000,000 gwei");
+    }
+
+    #[test]
+    fn test_format_ether_max_does_not_panic() {
+        let max = format_ether_smart(U256::MAX);
+        assert_eq!(max, "1.1e59 ETH");
+        assert_eq!(format_ether_in(U256::MAX, EtherUnit::Wei), group_digits(&to_string(U256::MAX), 3, ',') + " wei");
+    }
+
+    #[test]
+    fn test_format_ether_forced_unit() {
+        let one_eth = wei(1_000_000_000_000_000_000);
+        assert_eq!(format_ether_in(one_eth, EtherUnit::Gwei), "1,000,000,000 gwei");
+        assert_eq!(format_ether_in(one_eth, EtherUnit::Wei), "1,000,000,000,000,000,000 wei");
+        assert_eq!(format_ether_in(wei(1), EtherUnit::Ether), "0.000000000000000001 ETH");
+        assert_eq!(format_ether_in(U256::ZERO, EtherUnit::Gwei), "0 gwei");
+    }
+
+    #[test]
+    fn test_u512_to_string() {
+        assert_eq!(u512_to_string(U512::from(U256::MAX)), to_string(U256::MAX));
+        assert_eq!(u512_to_string(U512::from(10_000_000_000_000_000_000u64)), "10000000000000000000");
+        assert_eq!(u512_to_string(U512::ZERO), "0");
+    }
+
+    #[test]
+    fn test_format_fixed_hundredths() {
+        let unit = U512::from(1_000_000_000_000_000_000u64);
+        for amount in [0u64, 1, 4_999_999_999_999_999, 5_000_000_000_000_000, 994_999_999_999_999_999, 995_000_000_000_000_000] {
+            let amount = U512::from(amount);
+            assert_eq!(format_fixed_hundredths(amount, 18), format_hundredths(amount, unit));
+        }
+        assert_eq!(format_fixed_hundredths(U512::from(1_234_567u64), 0), "1,234,567");
+        assert_eq!(format_fixed_hundredths(U512::from(1_234_567u64), 1), "123,456.7");
+        assert_eq!(format_fixed_hundredths(U512::from(99_999u64), 3), "100");
+        assert_eq!(format_fixed_hundredths(U512::from(U256::MAX), 255), "0");
+    }
+
+    #[test]
+    fn test_abbreviate_address() {
+        assert_eq!(abbreviate_address(Address::ZERO), "0x0000…0000");
+        let addr = Address::from_slice(&[0x12; 20]);
+        let full = address_to_checksum_hex_string(addr);
+        let short = abbreviate_address(addr);
+        assert!(short.starts_with(&full[..6]) && short.ends_with(&full[38..]));
+        assert_eq!(short.chars().count(), 11);
+    }
+
+    #[test]
+    fn test_format_byte_size() {
+        assert_eq!(format_byte_size(0), "0 B");
+        assert_eq!(format_byte_size(1023), "1,023 B");
+        assert_eq!(format_byte_size(1024), "1 KiB");
+        assert_eq!(format_byte_size(1536), "1.5 KiB");
+        assert_eq!(format_byte_size(24_576), "24 KiB");
+        assert_eq!(format_byte_size(5 * 1024 * 1024 + 10_486), "5.01 MiB");
+    }
+}
@@ -0,0 +1,213 @@
+//! Validating base URIs before storage and joining token URI segments.
+
+use std::borrow::Cow;
+
+use alloy_primitives::U256;
+
+use crate::to_string;
+
+/// Schemes accepted by `normalize_base_uri`
+pub const DEFAULT_URI_SCHEMES: &[&str] = &["https", "ipfs", "ar", "data"];
+
+/// Longest base URI accepted by `normalize_base_uri`, in bytes
+pub const MAX_BASE_URI_LEN: usize = 2048;
+
+/// Error types for base URI validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UriError {
+    /// URI is longer than the allowed number of bytes
+    TooLong { max: usize, found: usize },
+    /// Whitespace or control character at byte offset `index`
+    InvalidChar { index: usize, found: char },
+    /// URI has no "scheme:" prefix
+    MissingScheme,
+    /// Scheme is not in the allowed list
+    DisallowedScheme,
+    /// Hierarchical URI has an empty authority, e.g. "https:///a"
+    MissingAuthority,
+    /// Hierarchical URI has a `?query`, after which no token id can be appended
+    UnexpectedQuery,
+}
+
+/// Validates and normalizes a base URI with the default schemes and length
+/// limit. See `normalize_base_uri_with`.
+pub fn normalize_base_uri(s: &str) -> Result<String, UriError> {
+    normalize_base_uri_with(s, DEFAULT_URI_SCHEMES, MAX_BASE_URI_LEN)
+}
+
+/// Validates a base URI and normalizes it for storage.
+///
+/// The URI must be at most `max_len` bytes, contain no whitespace or
+/// control characters, and use one of `schemes` (compared
+/// case-insensitively; the scheme is lowercased on output). Any `#fragment`
+/// is removed. Every scheme except `data` is hierarchical: it must be
+/// followed by "//" and a non-empty authority (the part before the next
+/// `/`), must not have a `?query`, and the result ends with exactly one `/`
+/// so token IDs can be appended directly.
+pub fn normalize_base_uri_with(s: &str, schemes: &[&str], max_len: usize) -> Result<String, UriError> {
+    if s.len() > max_len {
+        return Err(UriError::TooLong { max: max_len, found: s.len() });
+    }
+    if let Some((index, found)) = s.char_indices().find(|(_, c)| c.is_whitespace() || c.is_control()) {
+        return Err(UriError::InvalidChar { index, found });
+    }
+
+    let (scheme, rest) = s.split_once(':').ok_or(UriError::MissingScheme)?;
+    if scheme.is_empty() {
+        return Err(UriError::MissingScheme);
+    }
+    let scheme = scheme.to_ascii_lowercase();
+    if !schemes.iter().any(|allowed| allowed.eq_ignore_ascii_case(&scheme)) {
+        return Err(UriError::DisallowedScheme);
+    }
+    let rest = rest.split_once('#').map_or(rest, |(before, _)| before);
+
+    if scheme == "data" {
+        return Ok(format!("{}:{}", scheme, rest));
+    }
+    let path = rest.strip_prefix("//").ok_or(UriError::MissingAuthority)?.trim_end_matches('/');
+    if path.split('/').next().unwrap_or_default().is_empty() {
+        return Err(UriError::MissingAuthority);
+    }
+    if path.contains('?') {
+        return Err(UriError::UnexpectedQuery);
+    }
+    Ok(format!("{}://{}/", scheme, path))
+}
+
+/// Joins a base URI and a path segment with exactly one `/` between them,
+/// whatever slashes either side already has.
+pub fn join_uri(base: &str, segment: &str) -> String {
+    format!("{}/{}", base.trim_end_matches('/'), segment.trim_start_matches('/'))
+}
+
+/// Resolves an ERC-721 `tokenURI` the usual way: the per-token override if
+/// one is set, else the base URI joined with the decimal token id, else "".
+///
+/// An override is returned borrowed, byte-for-byte, without allocating.
+/// The base is treated as a directory: exactly one `/` separates it from
+/// the id whether or not it ends with one. `suffix` is appended after the
+/// id with a single leading dot, so "json" and ".json" both give "1.json".
+/// Empty strings count as unset, as an empty storage string does on chain.
+pub fn resolve_token_uri<'a>(
+    base: Option<&str>,
+    override_uri: Option<&'a str>,
+    token_id: U256,
+    suffix: Option<&str>,
+) -> Cow<'a, str> {
+    if let Some(uri) = override_uri.filter(|uri| !uri.is_empty()) {
+        return Cow::Borrowed(uri);
+    }
+    let base = match base.filter(|base| !base.is_empty()) {
+        Some(base) => base,
+        None => return Cow::Borrowed(""),
+    };
+    let mut uri = join_uri(base, &to_string(token_id));
+    if let Some(suffix) = suffix.map(|suffix| suffix.trim_start_matches('.')).filter(|suffix| !suffix.is_empty()) {
+        uri.push('.');
+        uri.push_str(suffix);
+    }
+    Cow::Owned(uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_each_scheme() {
+        assert_eq!(normalize_base_uri("https://example.com/meta").unwrap(), "https://example.com/meta/");
+        assert_eq!(normalize_base_uri("ipfs://bafybeigdyr/").unwrap(), "ipfs://bafybeigdyr/");
+        assert_eq!(normalize_base_uri("ar://txid//").unwrap(), "ar://txid/");
+        assert_eq!(
+            normalize_base_uri("data:application/json;base64,").unwrap(),
+            "data:application/json;base64,"
+        );
+        assert_eq!(normalize_base_uri("HTTPS://Example.com").unwrap(), "https://Example.com/");
+    }
+
+    #[test]
+    fn test_fragment_stripped() {
+        assert_eq!(normalize_base_uri("https://example.com/a/#top").unwrap(), "https://example.com/a/");
+        assert_eq!(normalize_base_uri("data:text/plain,hi#x").unwrap(), "data:text/plain,hi");
+    }
+
+    #[test]
+    fn test_rejections() {
+        assert_eq!(normalize_base_uri("http://example.com"), Err(UriError::DisallowedScheme));
+        assert_eq!(normalize_base_uri("example.com/meta"), Err(UriError::MissingScheme));
+        assert_eq!(normalize_base_uri(":foo"), Err(UriError::MissingScheme));
+        assert_eq!(normalize_base_uri("ipfs:bafy"), Err(UriError::MissingAuthority));
+        assert_eq!(normalize_base_uri("https:///"), Err(UriError::MissingAuthority));
+        assert_eq!(normalize_base_uri("https:///a"), Err(UriError::MissingAuthority));
+        assert_eq!(normalize_base_uri("ipfs:////cid"), Err(UriError::MissingAuthority));
+        assert_eq!(normalize_base_uri("https://x.io/a?v=1"), Err(UriError::UnexpectedQuery));
+        assert_eq!(normalize_base_uri("https://x.io?v=1#top"), Err(UriError::UnexpectedQuery));
+        assert_eq!(normalize_base_uri("data:text/plain,a?b").unwrap(), "data:text/plain,a?b");
+        assert_eq!(
+            normalize_base_uri("https://example.com/my meta"),
+            Err(UriError::InvalidChar { index: 22, found: ' ' })
+        );
+        assert_eq!(
+            normalize_base_uri("ipfs://a\nb"),
+            Err(UriError::InvalidChar { index: 8, found: '\n' })
+        );
+        let long = format!("https://{}", "a".repeat(MAX_BASE_URI_LEN));
+        assert_eq!(normalize_base_uri(&long), Err(UriError::TooLong { max: 2048, found: 2056 }));
+    }
+
+    #[test]
+    fn test_custom_schemes() {
+        assert_eq!(normalize_base_uri_with("http://x", &["http"], 100).unwrap(), "http://x/");
+        assert_eq!(normalize_base_uri_with("https://x", &["http"], 100), Err(UriError::DisallowedScheme));
+        assert_eq!(normalize_base_uri_with("https://x", &["https"], 5), Err(UriError::TooLong { max: 5, found: 9 }));
+    }
+
+    #[test]
+    fn test_join_uri() {
+        assert_eq!(join_uri("ipfs://cid/", "1.json"), "ipfs://cid/1.json");
+        assert_eq!(join_uri("ipfs://cid", "1.json"), "ipfs://cid/1.json");
+        assert_eq!(join_uri("ipfs://cid/", "/1.json"), "ipfs://cid/1.json");
+        assert_eq!(join_uri("ipfs://cid", "/1.json"), "ipfs://cid/1.json");
+        assert_eq!(join_uri("https://x.com/a//", "//b"), "https://x.com/a/b");
+    }
+
+    #[test]
+    fn test_override_is_borrowed() {
+        let uri = resolve_token_uri(Some("ipfs://cid/"), Some("ar://special"), U256::from(7), Some("json"));
+        assert!(matches!(uri, Cow::Borrowed("ar://special")));
+        // Not normalized in any way
+        let uri = resolve_token_uri(None, Some("ipfs://x//7"), U256::from(7), None);
+        assert!(matches!(uri, Cow::Borrowed("ipfs://x//7")));
+    }
+
+    #[test]
+    fn test_base_and_id_are_owned() {
+        let uri = resolve_token_uri(Some("ipfs://cid/"), None, U256::from(42), None);
+        assert!(matches!(uri, Cow::Owned(_)));
+        assert_eq!(uri, "ipfs://cid/42");
+        assert_eq!(resolve_token_uri(Some("ipfs://cid"), None, U256::from(42), None), "ipfs://cid/42");
+        assert_eq!(resolve_token_uri(Some("https://x.com/m//"), Some(""), U256::ZERO, None), "https://x.com/m/0");
+        assert_eq!(
+            resolve_token_uri(Some("ipfs://cid/"), None, U256::MAX, None),
+            format!("ipfs://cid/{}", to_string(U256::MAX))
+        );
+    }
+
+    #[test]
+    fn test_suffix_with_and_without_dot() {
+        for suffix in ["json", ".json"] {
+            assert_eq!(resolve_token_uri(Some("ipfs://cid"), None, U256::from(1), Some(suffix)), "ipfs://cid/1.json");
+        }
+        assert_eq!(resolve_token_uri(Some("ipfs://cid/"), None, U256::from(1), Some("")), "ipfs://cid/1");
+        // The suffix only applies to composed URIs
+        assert_eq!(resolve_token_uri(Some("ipfs://cid/"), Some("ar://a"), U256::from(1), Some("json")), "ar://a");
+    }
+
+    #[test]
+    fn test_nothing_configured() {
+        let uri = resolve_token_uri(None, None, U256::from(1), Some("json"));
+        assert!(matches!(uri, Cow::Borrowed("")));
+        assert_eq!(resolve_token_uri(Some(""), Some(""), U256::from(1), None), "");
+    }
+}
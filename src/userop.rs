@@ -0,0 +1,118 @@
+//! Readable summaries of ERC-4337 user operations for bundler and
+//! paymaster logs.
+
+use alloy_primitives::{Address, U256};
+
+use crate::fluent::group_digits;
+use crate::table::render_table;
+use crate::{address_to_checksum_hex_string, format_byte_size, format_ether_in, to_hex_string, to_string, EtherUnit};
+
+/// Renders the fields of a user operation as an aligned table:
+///
+/// ```text
+/// sender                0x52908400098527886E0F7030069857D2E4169EE7
+/// nonce                                            key 0x00, seq 7
+/// call gas                                                 150,000
+/// verification gas                                         250,000
+/// pre-verification gas                                      48,000
+/// max fee                                                  30 gwei
+/// max priority fee                                        1.5 gwei
+/// init code                                                    0 B
+/// call data                                                  228 B
+/// ```
+///
+/// The nonce is split as in the EntryPoint: the upper 192 bits are the key,
+/// shown in hex, and the lower 64 bits the sequence number.
+#[allow(clippy::too_many_arguments)]
+pub fn format_user_op(
+    sender: Address,
+    nonce: U256,
+    call_gas: U256,
+    verification_gas: U256,
+    pre_verification_gas: U256,
+    max_fee: U256,
+    max_priority_fee: U256,
+    init_code_len: usize,
+    call_data_len: usize,
+) -> String {
+    let key = nonce >> 64;
+    let seq = nonce & U256::from(u64::MAX);
+    let gas = |value: U256| group_digits(&to_string(value), 3, ',');
+
+    let rows = [
+        vec!["sender".to_string(), address_to_checksum_hex_string(sender)],
+        vec!["nonce".to_string(), format!("key {}, seq {}", to_hex_string(key), to_string(seq))],
+        vec!["call gas".to_string(), gas(call_gas)],
+        vec!["verification gas".to_string(), gas(verification_gas)],
+        vec!["pre-verification gas".to_string(), gas(pre_verification_gas)],
+        vec!["max fee".to_string(), format_ether_in(max_fee, EtherUnit::Gwei)],
+        vec!["max priority fee".to_string(), format_ether_in(max_priority_fee, EtherUnit::Gwei)],
+        vec!["init code".to_string(), format_byte_size(init_code_len)],
+        vec!["call data".to_string(), format_byte_size(call_data_len)],
+    ];
+    render_table(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GWEI: u64 = 1_000_000_000;
+
+    fn sender() -> Address {
+        Address::from([
+            0x52, 0x90, 0x84, 0x00, 0x09, 0x85, 0x27, 0x88, 0x6e, 0x0f,
+            0x70, 0x30, 0x06, 0x98, 0x57, 0xd2, 0xe4, 0x16, 0x9e, 0xe7,
+        ])
+    }
+
+    #[test]
+    fn test_format_user_op_deployed_account() {
+        let summary = format_user_op(
+            sender(),
+            U256::from(7),
+            U256::from(150_000),
+            U256::from(250_000),
+            U256::from(48_000),
+            U256::from(30 * GWEI),
+            U256::from(3 * GWEI / 2),
+            0,
+            228,
+        );
+        assert_eq!(
+            summary,
+            "sender                0x52908400098527886E0F7030069857D2E4169EE7\n\
+             nonce                                            key 0x00, seq 7\n\
+             call gas                                                 150,000\n\
+             verification gas                                         250,000\n\
+             pre-verification gas                                      48,000\n\
+             max fee                                                  30 gwei\n\
+             max priority fee                                        1.5 gwei\n\
+             init code                                                    0 B\n\
+             call data                                                  228 B"
+        );
+    }
+
+    #[test]
+    fn test_format_user_op_with_init_code_and_nonce_key() {
+        let nonce = (U256::from(0xabcd) << 64) | U256::from(3);
+        let summary = format_user_op(
+            sender(),
+            nonce,
+            U256::from(1_000_000),
+            U256::from(1_500_000),
+            U256::from(60_000),
+            U256::from(GWEI / 10),
+            U256::from(GWEI / 100),
+            3_072,
+            516,
+        );
+        let lines: Vec<&str> = summary.lines().collect();
+        assert!(lines[1].ends_with("key 0xabcd, seq 3"));
+        assert!(lines[2].ends_with(" 1,000,000"));
+        assert!(lines[5].ends_with(" 0.1 gwei"));
+        assert!(lines[6].ends_with(" 0.01 gwei"));
+        assert!(lines[7].ends_with(" 3 KiB"));
+        assert!(lines.iter().all(|line| line.chars().count() == lines[0].chars().count()));
+    }
+}
@@ -0,0 +1,157 @@
+//! Vesting schedule summaries for token vesting wallets.
+
+use alloy_primitives::{U256, U512};
+
+use crate::format_relative;
+use crate::table::render_table;
+use crate::units::format_grouped_units;
+
+/// Renders a vesting schedule as a table of amounts and time to full vest:
+///
+/// ```text
+/// total      1,000 TKN
+/// vested       500 TKN
+/// claimable    500 TKN
+/// remaining    500 TKN
+/// full vest  in 5d
+/// ```
+///
+/// Tokens vest linearly from `start` to `end`, but as in OpenZeppelin's
+/// `VestingWalletCliff` nothing has vested before the `cliff` timestamp;
+/// from then on `vested` is the linear amount. Everything vested is
+/// claimable, and `remaining` is what has yet to vest.
+///
+/// Degenerate schedules render rather than panic: with `end <= start` the
+/// whole amount vests at `start`, a cliff after `end` delays full vesting
+/// to the cliff, and before `start` nothing has vested.
+pub fn format_vesting_schedule(
+    total: U256,
+    start: U256,
+    cliff: U256,
+    end: U256,
+    now: U256,
+    decimals: u8,
+    symbol: &str,
+) -> String {
+    let vested = vested_amount(total, start, cliff, end, now);
+    let full_at = if end > start { end } else { start }.max(cliff);
+    let full_vest = if now >= full_at {
+        "complete".to_string()
+    } else {
+        format_relative(full_at, now)
+    };
+
+    let amount = |value: U256| format!("{} {}", format_grouped_units(value, decimals, 0), symbol);
+    let rows = [
+        vec!["total".to_string(), amount(total)],
+        vec!["vested".to_string(), amount(vested)],
+        vec!["claimable".to_string(), amount(vested)],
+        vec!["remaining".to_string(), amount(total - vested)],
+        vec!["full vest".to_string(), full_vest],
+    ];
+    render_table(&rows)
+}
+
+/// Linearly vested amount at `now`, zero before the cliff, computed in 512
+/// bits so `total * elapsed` cannot overflow.
+fn vested_amount(total: U256, start: U256, cliff: U256, end: U256, now: U256) -> U256 {
+    if now < start || now < cliff {
+        U256::ZERO
+    } else if end <= start || now >= end {
+        total
+    } else {
+        let vested = U512::from(total) * U512::from(now - start) / U512::from(end - start);
+        vested.to::<U256>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 86_400;
+    const START: u64 = 1_700_000_000;
+
+    fn tokens(whole: u64) -> U256 {
+        U256::from(whole) * U256::from(10).pow(U256::from(18))
+    }
+
+    /// 1,000 TKN vesting over 10 days with a 2-day cliff
+    fn schedule(now: u64) -> String {
+        format_vesting_schedule(
+            tokens(1_000),
+            U256::from(START),
+            U256::from(START + 2 * DAY),
+            U256::from(START + 10 * DAY),
+            U256::from(now),
+            18,
+            "TKN",
+        )
+    }
+
+    #[test]
+    fn test_before_cliff() {
+        assert_eq!(
+            schedule(START + DAY),
+            "total      1,000 TKN\nvested         0 TKN\nclaimable      0 TKN\nremaining  1,000 TKN\nfull vest      in 9d"
+        );
+    }
+
+    #[test]
+    fn test_at_cliff() {
+        assert_eq!(
+            schedule(START + 2 * DAY),
+            "total      1,000 TKN\nvested       200 TKN\nclaimable    200 TKN\nremaining    800 TKN\nfull vest      in 8d"
+        );
+    }
+
+    #[test]
+    fn test_midway() {
+        assert_eq!(
+            schedule(START + 5 * DAY),
+            "total      1,000 TKN\nvested       500 TKN\nclaimable    500 TKN\nremaining    500 TKN\nfull vest      in 5d"
+        );
+        assert!(schedule(START + 5 * DAY + 3_600).contains("vested     504.166666666666666666 TKN"));
+    }
+
+    #[test]
+    fn test_after_end() {
+        assert_eq!(
+            schedule(START + 11 * DAY),
+            "total      1,000 TKN\nvested     1,000 TKN\nclaimable  1,000 TKN\nremaining      0 TKN\nfull vest   complete"
+        );
+    }
+
+    #[test]
+    fn test_before_start() {
+        assert!(schedule(START - DAY).starts_with("total      1,000 TKN\nvested         0 TKN"));
+    }
+
+    #[test]
+    fn test_degenerate_schedule() {
+        // End before start, cliff after both: everything vests at the cliff
+        let render = |now: u64| {
+            format_vesting_schedule(
+                U256::from(50),
+                U256::from(100),
+                U256::from(300),
+                U256::from(50),
+                U256::from(now),
+                0,
+                "TKN",
+            )
+        };
+        assert_eq!(
+            render(99),
+            "total         50 TKN\nvested         0 TKN\nclaimable      0 TKN\nremaining     50 TKN\nfull vest  in 3m 21s"
+        );
+        assert_eq!(
+            render(200),
+            "total         50 TKN\nvested         0 TKN\nclaimable      0 TKN\nremaining     50 TKN\nfull vest  in 1m 40s"
+        );
+        assert_eq!(
+            render(300),
+            "total        50 TKN\nvested       50 TKN\nclaimable    50 TKN\nremaining     0 TKN\nfull vest  complete"
+        );
+    }
+}
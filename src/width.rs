@@ -0,0 +1,165 @@
+//! Monospace display width, for aligning text that mixes ASCII with East
+//! Asian characters and emoji.
+
+use crate::grapheme::ZERO_WIDTH_JOINER;
+
+/// How text width is measured when padding and aligning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WidthMode {
+    /// One column per `char`, which is right for most scripts
+    #[default]
+    Chars,
+    /// Terminal columns per `display_width`
+    Display,
+}
+
+/// Zero-width ranges, then double-width ones, each sorted by start and
+/// non-overlapping within the table
+///
+/// Zero width:
+/// - U+0300–036F, 1AB0–1AFF, 1DC0–1DFF, 20D0–20FF, FE20–FE2F: combining marks
+/// - U+200B–200F: zero width space, non-joiner, joiner and direction marks
+/// - U+2060–2064 and FEFF: word joiner, invisible operators, BOM
+/// - U+FE00–FE0F and E0100–E01EF: variation selectors
+/// - U+1F3FB–1F3FF: emoji skin tone modifiers
+///
+/// Double width:
+/// - U+1100–115F: Hangul Jamo initial consonants
+/// - U+2E80–303E, 3041–33FF: CJK radicals, punctuation, kana, Bopomofo and
+///   compatibility blocks
+/// - U+3400–4DBF, 4E00–9FFF, F900–FAFF, 20000–2FFFD, 30000–3FFFD: CJK
+///   ideographs
+/// - U+A000–A4CF: Yi
+/// - U+AC00–D7A3: Hangul syllables
+/// - U+FE30–FE4F: CJK compatibility forms
+/// - U+FF00–FF60, FFE0–FFE6: fullwidth forms
+/// - U+1F300–1F64F (minus the skin tones), 1F680–1F6FF, 1F900–1F9FF: emoji
+const WIDTH_RANGES: [(u32, u32, usize); 28] = [
+    (0x0300, 0x036F, 0),
+    (0x1100, 0x115F, 2),
+    (0x1AB0, 0x1AFF, 0),
+    (0x1DC0, 0x1DFF, 0),
+    (0x200B, 0x200F, 0),
+    (0x2060, 0x2064, 0),
+    (0x20D0, 0x20FF, 0),
+    (0x2E80, 0x303E, 2),
+    (0x3041, 0x33FF, 2),
+    (0x3400, 0x4DBF, 2),
+    (0x4E00, 0x9FFF, 2),
+    (0xA000, 0xA4CF, 2),
+    (0xAC00, 0xD7A3, 2),
+    (0xF900, 0xFAFF, 2),
+    (0xFE00, 0xFE0F, 0),
+    (0xFE20, 0xFE2F, 0),
+    (0xFE30, 0xFE4F, 2),
+    (0xFEFF, 0xFEFF, 0),
+    (0xFF00, 0xFF60, 2),
+    (0xFFE0, 0xFFE6, 2),
+    (0x1F300, 0x1F3FA, 2),
+    (0x1F3FB, 0x1F3FF, 0),
+    (0x1F400, 0x1F64F, 2),
+    (0x1F680, 0x1F6FF, 2),
+    (0x1F900, 0x1F9FF, 2),
+    (0x20000, 0x2FFFD, 2),
+    (0x30000, 0x3FFFD, 2),
+    (0xE0100, 0xE01EF, 0),
+];
+
+/// Returns the number of monospace terminal columns `s` occupies.
+///
+/// Characters in the ranges documented on `WIDTH_RANGES` take 0 or 2
+/// columns and everything else 1. A character following a zero width
+/// joiner is merged into the preceding emoji and takes 0, so a ZWJ family
+/// sequence is as wide as a single emoji. Control characters are not
+/// special-cased.
+pub fn display_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut joined = false;
+    for c in s.chars() {
+        if !joined {
+            width += char_width(c);
+        }
+        joined = c == ZERO_WIDTH_JOINER;
+    }
+    width
+}
+
+/// Measures `s` in columns according to `mode`.
+pub fn text_width(s: &str, mode: WidthMode) -> usize {
+    match mode {
+        WidthMode::Chars => s.chars().count(),
+        WidthMode::Display => display_width(s),
+    }
+}
+
+/// Pads `s` with spaces on the left to `width` columns; longer text is
+/// returned unchanged.
+pub fn pad_left(s: &str, width: usize, mode: WidthMode) -> String {
+    let padding = width.saturating_sub(text_width(s, mode));
+    format!("{}{}", " ".repeat(padding), s)
+}
+
+/// Pads `s` with spaces on the right to `width` columns; longer text is
+/// returned unchanged.
+pub fn pad_right(s: &str, width: usize, mode: WidthMode) -> String {
+    let padding = width.saturating_sub(text_width(s, mode));
+    format!("{}{}", s, " ".repeat(padding))
+}
+
+fn char_width(c: char) -> usize {
+    let code = c as u32;
+    let index = WIDTH_RANGES.partition_point(|&(_, end, _)| end < code);
+    match WIDTH_RANGES.get(index) {
+        Some(&(start, _, width)) if start <= code => width,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranges_sorted() {
+        for pair in WIDTH_RANGES.windows(2) {
+            assert!(pair[0].0 <= pair[0].1);
+            assert!(pair[0].1 < pair[1].0, "{:x?}", pair);
+        }
+    }
+
+    #[test]
+    fn test_cjk_and_fullwidth() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("流動性"), 6);
+        assert_eq!(display_width("토큰"), 4);
+        assert_eq!(display_width("ＵＳＤ"), 6);
+        assert_eq!(display_width("カナ。"), 6);
+        assert_eq!(display_width("ｶﾅ"), 2);
+        assert_eq!(display_width(""), 0);
+    }
+
+    #[test]
+    fn test_emoji_zwj_sequence() {
+        assert_eq!(display_width("🚀"), 2);
+        // Man, ZWJ, woman, ZWJ, girl renders as one family glyph
+        assert_eq!(display_width("\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}"), 2);
+        assert_eq!(display_width("\u{1F44D}\u{1F3FD}"), 2);
+        assert_eq!(display_width("ok 👍"), 5);
+    }
+
+    #[test]
+    fn test_combining_accents() {
+        assert_eq!(display_width("e\u{0301}"), 1);
+        assert_eq!(display_width("cafe\u{0301}"), 4);
+        assert_eq!("cafe\u{0301}".chars().count(), 5);
+        assert_eq!(display_width("\u{FEFF}x"), 1);
+    }
+
+    #[test]
+    fn test_padding() {
+        assert_eq!(pad_right("流動", 6, WidthMode::Display), "流動  ");
+        assert_eq!(pad_right("流動", 6, WidthMode::Chars), "流動    ");
+        assert_eq!(pad_left("e\u{0301}", 3, WidthMode::Display), "  e\u{0301}");
+        assert_eq!(pad_left("long", 2, WidthMode::Chars), "long");
+    }
+}
@@ -0,0 +1,217 @@
+//! Newtype wrappers pairing a `U256` with a string representation.
+//!
+//! Each wrapper's `Display` matches the corresponding free function and its
+//! `FromStr` is the strict inverse, so `"123".parse::<Dec>()?.0` round-trips.
+
+use core::fmt;
+use core::str::FromStr;
+
+use alloy_primitives::U256;
+
+use crate::parse::{parse_decimal, parse_digits, parse_hex, parse_u256, ParseError};
+use crate::{to_hex_string, to_hex_string_with_length, to_string, to_string_radix, StringsError};
+
+/// Decimal representation, as produced by `to_string`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Dec(pub U256);
+
+/// Variable-length "0x" hex representation, as produced by `to_hex_string`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex(pub U256);
+
+/// Fixed-length "0x" hex representation of `LENGTH` bytes, as produced by
+/// `to_hex_string_with_length`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fixed<const LENGTH: usize>(U256);
+
+/// Number parsed from either decimal or "0x" hex, displayed as decimal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AnyNum(pub U256);
+
+/// Unprefixed representation in base `BASE`, as produced by `to_string_radix`
+///
+/// `BASE` is checked at compile time to be in `2..=36`:
+///
+/// ```compile_fail
+/// use alloy_primitives::U256;
+/// use strings_utils_stylus::Radix;
+/// let _ = format!("{}", Radix::<1>(U256::ZERO));
+/// ```
+///
+/// ```compile_fail
+/// use alloy_primitives::U256;
+/// use strings_utils_stylus::Radix;
+/// let _ = format!("{}", Radix::<37>(U256::ZERO));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Radix<const BASE: u8>(pub U256);
+
+impl<const LENGTH: usize> Fixed<LENGTH> {
+    /// Wraps `value`, failing if it does not fit in `LENGTH` bytes.
+    pub fn new(value: U256) -> Result<Self, StringsError> {
+        if LENGTH < 32 && value >> (8 * LENGTH) != U256::ZERO {
+            return Err(StringsError::InsufficientHexLength { value, length: LENGTH });
+        }
+        Ok(Self(value))
+    }
+
+    /// Returns the wrapped value.
+    pub fn value(self) -> U256 {
+        self.0
+    }
+}
+
+impl fmt::Display for Dec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&to_string(self.0))
+    }
+}
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&to_hex_string(self.0))
+    }
+}
+
+impl<const LENGTH: usize> fmt::Display for Fixed<LENGTH> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `new` guarantees the value fits
+        let s = to_hex_string_with_length(self.0, LENGTH).map_err(|_| fmt::Error)?;
+        f.pad(&s)
+    }
+}
+
+impl fmt::Display for AnyNum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&to_string(self.0))
+    }
+}
+
+impl<const BASE: u8> fmt::Display for Radix<BASE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const { assert!(BASE >= 2 && BASE <= 36, "Radix BASE must be in 2..=36") };
+        f.pad(&to_string_radix(self.0, BASE))
+    }
+}
+
+impl FromStr for Dec {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_decimal(s).map(Dec)
+    }
+}
+
+impl FromStr for Hex {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_hex(s).map(Hex)
+    }
+}
+
+impl<const LENGTH: usize> FromStr for Fixed<LENGTH> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix("0x").ok_or(ParseError::MissingHexPrefix)?;
+        if digits.len() != 2 * LENGTH {
+            return Err(ParseError::InvalidLength { expected: 2 * LENGTH, found: digits.len() });
+        }
+        parse_digits(digits, 2, 16).map(Fixed)
+    }
+}
+
+impl FromStr for AnyNum {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_u256(s).map(AnyNum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dec_round_trip() {
+        for value in [U256::ZERO, U256::from(12345), U256::MAX] {
+            let s = Dec(value).to_string();
+            assert_eq!(s.parse::<Dec>().unwrap(), Dec(value));
+        }
+        assert_eq!("123".parse::<Dec>().unwrap().0, U256::from(123));
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        for value in [U256::ZERO, U256::from(255), U256::from(256), U256::MAX] {
+            let s = Hex(value).to_string();
+            assert_eq!(s.parse::<Hex>().unwrap(), Hex(value));
+        }
+        assert_eq!(Hex(U256::from(255)).to_string(), "0xff");
+    }
+
+    #[test]
+    fn test_fixed_round_trip() {
+        let fixed = Fixed::<2>::new(U256::from(0xff)).unwrap();
+        assert_eq!(fixed.to_string(), "0x00ff");
+        assert_eq!("0x00ff".parse::<Fixed<2>>().unwrap(), fixed);
+        assert_eq!(fixed.value(), U256::from(0xff));
+        let full = Fixed::<32>::new(U256::MAX).unwrap();
+        assert_eq!(full.to_string().parse::<Fixed<32>>().unwrap(), full);
+    }
+
+    #[test]
+    fn test_fixed_rejects_oversized_value() {
+        assert!(matches!(
+            Fixed::<1>::new(U256::from(0x100)),
+            Err(StringsError::InsufficientHexLength { length: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_any_num_detects_prefix() {
+        assert_eq!("255".parse::<AnyNum>().unwrap(), AnyNum(U256::from(255)));
+        assert_eq!("0xff".parse::<AnyNum>().unwrap(), AnyNum(U256::from(255)));
+        assert_eq!(AnyNum(U256::from(255)).to_string(), "255");
+    }
+
+    #[test]
+    fn test_parse_failures() {
+        assert_eq!("".parse::<Dec>(), Err(ParseError::Empty));
+        assert_eq!("0xff".parse::<Dec>(), Err(ParseError::InvalidDigit { index: 1, found: 'x' }));
+        assert_eq!("ff".parse::<Hex>(), Err(ParseError::MissingHexPrefix));
+        assert_eq!("0x".parse::<Hex>(), Err(ParseError::Empty));
+        assert_eq!(
+            "0xff".parse::<Fixed<2>>(),
+            Err(ParseError::InvalidLength { expected: 4, found: 2 })
+        );
+        assert_eq!("00ff".parse::<Fixed<2>>(), Err(ParseError::MissingHexPrefix));
+        assert_eq!(
+            "0x0z".parse::<Fixed<1>>(),
+            Err(ParseError::InvalidDigit { index: 3, found: 'z' })
+        );
+        assert_eq!("1.5".parse::<AnyNum>(), Err(ParseError::InvalidDigit { index: 1, found: '.' }));
+    }
+
+    #[test]
+    fn test_radix_display() {
+        let v = U256::from(0x1234);
+        assert_eq!(Radix::<2>(U256::from(5)).to_string(), "101");
+        assert_eq!(Radix::<8>(U256::from(64)).to_string(), "100");
+        assert_eq!(Radix::<36>(U256::from(36 * 36 - 1)).to_string(), "zz");
+        assert_eq!(Radix::<10>(U256::MAX).to_string(), to_string(U256::MAX));
+        // to_hex_string pads to whole bytes, so compare on even-length values
+        for value in [U256::from(0xff), v, U256::MAX] {
+            assert_eq!(Radix::<16>(value).to_string(), to_hex_string(value)[2..]);
+        }
+        assert_eq!(format!("{:_>8}", Radix::<2>(U256::from(5))), "_____101");
+    }
+
+    #[test]
+    fn test_display_respects_width() {
+        assert_eq!(format!("{:>6}", Dec(U256::from(42))), "    42");
+        assert_eq!(format!("{:<6}|", Hex(U256::from(1))), "0x01  |");
+    }
+}